@@ -7,6 +7,8 @@
 //
 
 // Modules
+#[cfg(feature = "nibble")]
+mod nibble;
 #[cfg(feature = "print")]
 mod print;
 #[cfg(feature = "punct")]
@@ -0,0 +1,171 @@
+//  NIBBLE.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines convenient macros for the `nibble` parse library.
+//
+
+
+/***** LIBRARY *****/
+/// Macro for defining a set of UTF-8 keywords that can be parsed as one.
+///
+/// For every keyword, this generates:
+/// - A struct with the same name as the variant that implements `Tag<u8>` (and thus `Parsable`)
+///   for just that keyword; and
+/// - A variant in the given enum wrapping that struct.
+///
+/// The enum itself implements `Located`, `Node`, `Term` and `Parsable<u8>`, the latter of which
+/// matches any of the keywords.
+///
+/// Parsing the enum always matches the _longest_ keyword possible, regardless of the order in
+/// which they are given. This is done in one pass over the input using a `TagTrie`, which is
+/// built the first time the enum is parsed. For example, if both `"in"` and `"instanceof"` are keywords, then
+/// `"instanceof"` is parsed as the latter instead of as `"in"` followed by `"stanceof"`. Note,
+/// however, that word boundaries are not checked; so `"from"` does match the start of
+/// `"fromage"`.
+///
+/// Because the structs are defined next to the enum, it's best to call this macro in a dedicated
+/// module (especially when keywords shadow prelude names, like `From`).
+///
+/// # Examples
+/// ```ignore
+/// use ast_toolkit2::nibble::{Parsable as _, Slice, keywords};
+///
+/// keywords! {
+///     #[derive(Debug)]
+///     pub enum Kw {
+///         Select = "select",
+///         From = "from",
+///         Where = "where",
+///     }
+/// }
+///
+/// let (kw, _) = Kw::parse(Slice::new(b"from".as_slice())).unwrap();
+/// assert!(matches!(kw, Kw::From(_)));
+/// ```
+#[macro_export]
+macro_rules! keywords {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident {
+            $($(#[$vattr:meta])* $var:ident = $tag:literal),+ $(,)?
+        }
+    ) => {
+        // The individual keywords
+        $(
+            #[doc = ::std::concat!("The `", $tag, "`-keyword.")]
+            #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+            $vis struct $var(pub ::ast_toolkit2::loc::Loc);
+            impl ::ast_toolkit2::loc::Located for $var {
+                #[inline]
+                fn loc(&self) -> ::ast_toolkit2::loc::Loc { self.0 }
+            }
+            impl ::ast_toolkit2::tree::Node for $var {
+                #[inline]
+                fn kind(&self) -> ::ast_toolkit2::tree::NodeKind { ::ast_toolkit2::tree::NodeKind::Term }
+
+                #[inline]
+                fn walk(&self, visitor: &mut dyn ::ast_toolkit2::tree::visit::Visitor) -> ::std::ops::ControlFlow<()> {
+                    ::ast_toolkit2::tree::visit::walk_term(self, visitor)
+                }
+            }
+            impl ::ast_toolkit2::tree::Term for $var {}
+            impl ::ast_toolkit2::tree::Tag<u8> for $var {
+                const TAG: &'static [u8] = $tag.as_bytes();
+
+                #[inline]
+                fn with_loc(loc: ::ast_toolkit2::loc::Loc) -> Self { Self(loc) }
+            }
+        )+
+
+        // The set of them
+        $(#[$attr])*
+        $vis enum $name {
+            $(
+                $(#[$vattr])*
+                $var($var),
+            )+
+        }
+        impl $name {
+            /// All the keywords in this set, in the order they were given.
+            pub const TAGS: &'static [&'static str] = &[$($tag),+];
+        }
+        impl ::ast_toolkit2::loc::Located for $name {
+            #[inline]
+            fn loc(&self) -> ::ast_toolkit2::loc::Loc {
+                match self {
+                    $(Self::$var(kw) => kw.0,)+
+                }
+            }
+        }
+        impl ::ast_toolkit2::tree::Node for $name {
+            #[inline]
+            fn kind(&self) -> ::ast_toolkit2::tree::NodeKind { ::ast_toolkit2::tree::NodeKind::Term }
+
+            #[inline]
+            fn walk(&self, visitor: &mut dyn ::ast_toolkit2::tree::visit::Visitor) -> ::std::ops::ControlFlow<()> {
+                ::ast_toolkit2::tree::visit::walk_term(self, visitor)
+            }
+        }
+        impl ::ast_toolkit2::tree::Term for $name {}
+        impl ::ast_toolkit2::nibble::Parsable<u8> for $name {
+            type Formatter = ::ast_toolkit2::nibble::keywords::KeywordsFormatter;
+            type Error = ::std::convert::Infallible;
+
+            #[inline]
+            fn expects() -> Self::Formatter { ::ast_toolkit2::nibble::keywords::KeywordsFormatter::new(Self::TAGS) }
+
+            #[inline]
+            fn parse(
+                input: ::ast_toolkit2::nibble::Slice<u8>,
+            ) -> ::std::result::Result<(Self, ::ast_toolkit2::nibble::Slice<u8>), ::ast_toolkit2::nibble::NibbleError<Self::Formatter, Self::Error>> {
+                // The trie is built once, on first use
+                static TRIE: ::std::sync::OnceLock<::ast_toolkit2::nibble::trie::TagTrie> = ::std::sync::OnceLock::new();
+                let ((i, loc), rem) = TRIE.get_or_init(|| ::ast_toolkit2::nibble::trie::TagTrie::new(Self::TAGS)).longest(input)?;
+                let mut j: usize = 0;
+                $(
+                    if i == j {
+                        return ::std::result::Result::Ok((Self::$var($var(loc)), rem));
+                    }
+                    j += 1;
+                )+
+                ::std::unreachable!()
+            }
+        }
+    };
+}
+
+
+
+/// Macro for constructing a node together with the [`Loc`] of the input it was parsed from.
+///
+/// It takes the input before parsing the node's parts, the remainder after it, and then a struct
+/// or tuple constructor. The [`Loc`] spanning the consumed input (see `Slice::loc_until()`) is
+/// then added as a field called `loc` (for structs) or as the last field (for tuples). It is
+/// converted with [`Into`], so any type implementing `From<Loc>` works.
+///
+/// # Examples
+/// ```ignore
+/// use ast_toolkit2::loc::Loc;
+/// use ast_toolkit2::nibble::{Parsable as _, Slice, spanned};
+///
+/// struct Pair {
+///     lhs: Hello,
+///     rhs: World,
+///     loc: Loc,
+/// }
+///
+/// let input = Slice::new(b"HelloWorld".as_slice());
+/// let (lhs, rem) = Hello::parse(input).unwrap();
+/// let (rhs, rem) = World::parse(rem).unwrap();
+/// let pair = spanned!(input, rem, Pair { lhs, rhs });
+/// ```
+#[macro_export]
+macro_rules! spanned {
+    ($input:expr, $rem:expr, $($name:ident)::+ { $($field:ident $(: $value:expr)?),* $(,)? }) => {
+        $($name)::+ { $($field $(: $value)?,)* loc: ::std::convert::Into::into(::ast_toolkit2::nibble::Slice::loc_until(&$input, &$rem)) }
+    };
+    ($input:expr, $rem:expr, $($name:ident)::+ ( $($value:expr),* $(,)? )) => {
+        $($name)::+($($value,)* ::std::convert::Into::into(::ast_toolkit2::nibble::Slice::loc_until(&$input, &$rem)))
+    };
+}
@@ -5,20 +5,239 @@
 //!   Defines common algorithms and utilities used across derive macros.
 //
 
+#[cfg(feature = "tree")]
+use proc_macro2::{Literal as Literal2, TokenStream as TokenStream2, TokenTree as TokenTree2};
 use proc_macro2::Span;
+#[cfg(feature = "tree")]
+use quote::{ToTokens as _, format_ident, quote};
 use syn::punctuated::Punctuated;
+#[cfg(feature = "tree")]
+use syn::spanned::Spanned as _;
 use syn::{Generics, Ident, Path, PathArguments, PathSegment, TraitBound, TraitBoundModifier, TypeParamBound};
+#[cfg(feature = "tree")]
+use syn::{Attribute, Data, DataUnion, Error, Field, Fields, Meta, Type, WherePredicate, parse_quote};
+
+
+/***** HELPER FUNCTIONS *****/
+/// Scans a list of attributes for the `#[child]`-attribute.
+///
+/// # Arguments
+/// - `attrs`: Some list of attributes.
+///
+/// # Returns
+/// Whether the attribute was found or not.
+#[cfg(feature = "tree")]
+fn has_child_attr<'a>(attrs: impl IntoIterator<Item = &'a Attribute>) -> Result<bool, Error> {
+    for attr in attrs {
+        match &attr.meta {
+            Meta::Path(p) if p.is_ident("child") => return Ok(true),
+            Meta::List(l) if l.path.is_ident("child") => {
+                return Err(Error::new(l.span(), "Unsure what to do with attribute; at a field, use `#[child]` or none."));
+            },
+            Meta::NameValue(nv) if nv.path.is_ident("child") => {
+                return Err(Error::new(nv.span(), "Unsure what to do with attribute; at a field, use `#[child]` or none."));
+            },
+
+            // The rest we ignore, part of other crates (or macros)
+            _ => continue,
+        }
+    }
+    Ok(false)
+}
+
+/// Checks whether a type mentions any of the type parameters in the given generics.
+///
+/// # Arguments
+/// - `ty`: The [`Type`] to scan.
+/// - `generics`: The [`Generics`] that define the type parameters to look for.
+///
+/// # Returns
+/// True if any identifier in `ty` is one of the type parameters, or false otherwise.
+#[cfg(feature = "tree")]
+fn mentions_type_param(ty: &Type, generics: &Generics) -> bool {
+    fn scan(tokens: TokenStream2, params: &[&Ident]) -> bool {
+        tokens.into_iter().any(|tt| match tt {
+            TokenTree2::Ident(ident) => params.contains(&&ident),
+            TokenTree2::Group(group) => scan(group.stream(), params),
+            TokenTree2::Punct(_) | TokenTree2::Literal(_) => false,
+        })
+    }
+    let params: Vec<&Ident> = generics.type_params().map(|p| &p.ident).collect();
+    !params.is_empty() && scan(ty.to_token_stream(), &params)
+}
+
+/// Finds the fields marked as `#[child]`.
+///
+/// # Arguments
+/// - `fields`: The [`Fields`] to search.
+///
+/// # Returns
+/// A list of the indices and types of the children.
+#[cfg(feature = "tree")]
+fn find_children(fields: &Fields) -> Result<Vec<(usize, &Type)>, Error> {
+    let mut res: Vec<(usize, &Type)> = Vec::new();
+    for (i, Field { attrs, ty, .. }) in fields.iter().enumerate() {
+        if has_child_attr(attrs)? {
+            res.push((i, ty));
+        }
+    }
+    Ok(res)
+}
+
+
+
 
 
 /***** LIBRARY *****/
+/// Generates an expression that does something with the `#[child]`-fields of `self`.
+///
+/// # Arguments
+/// - `trt`: The name of the trait for which we're finding children. Only used for error messages.
+/// - `data`: The [`Data`] of the type to find the children of.
+/// - `generics`: The [`Generics`] of the type to find the children of.
+/// - `bounds`: A list of where-predicates to which a `Node`-bound is added for every child type
+///   that mentions a type parameter. Other child types aren't bounded, as that would overflow
+///   for recursive types (e.g., with a `Box<Self>` child).
+/// - `body`: Generates an expression given references to every child (in order).
+///
+/// # Returns
+/// An expression that evaluates `body` for the children of `self`.
+#[cfg(feature = "tree")]
+pub fn with_children(
+    trt: &'static str,
+    data: &Data,
+    generics: &Generics,
+    bounds: &mut Vec<WherePredicate>,
+    body: impl Fn(Vec<TokenStream2>) -> TokenStream2,
+) -> Result<TokenStream2, Error> {
+    match data {
+        Data::Struct(s) => {
+            let mut refs: Vec<TokenStream2> = Vec::new();
+            for (i, ty) in find_children(&s.fields)? {
+                if mentions_type_param(ty, generics) {
+                    bounds.push(parse_quote! { #ty: ::ast_toolkit2::tree::Node });
+                }
+                let name: TokenTree2 = match &s.fields.iter().nth(i).unwrap().ident {
+                    Some(name) => TokenTree2::Ident(name.clone()),
+                    None => TokenTree2::Literal(Literal2::usize_unsuffixed(i)),
+                };
+                refs.push(quote! { &self.#name });
+            }
+            Ok(body(refs))
+        },
+
+        Data::Enum(e) => {
+            let mut arms: Vec<TokenStream2> = Vec::with_capacity(e.variants.len());
+            for variant in &e.variants {
+                let ident: &Ident = &variant.ident;
+                let children = find_children(&variant.fields)?;
+                let mut names: Vec<Ident> = Vec::with_capacity(children.len());
+                for (i, ty) in &children {
+                    if mentions_type_param(ty, generics) {
+                        bounds.push(parse_quote! { #ty: ::ast_toolkit2::tree::Node });
+                    }
+                    names.push(match &variant.fields.iter().nth(*i).unwrap().ident {
+                        Some(name) => name.clone(),
+                        None => format_ident!("field{i}"),
+                    });
+                }
+                let expr: TokenStream2 = body(names.iter().map(|name| quote! { #name }).collect());
+                match &variant.fields {
+                    Fields::Named(_) => arms.push(quote! { Self::#ident { #(#names,)* .. } => #expr, }),
+                    Fields::Unnamed(u) => {
+                        let pats = (0..u.unnamed.len()).map(|i| match children.iter().position(|(j, _)| i == *j) {
+                            Some(pos) => names[pos].clone(),
+                            None => Ident::new("_", Span::call_site()),
+                        });
+                        arms.push(quote! { Self::#ident(#(#pats),*) => #expr, });
+                    },
+                    Fields::Unit => arms.push(quote! { Self::#ident => #expr, }),
+                }
+            }
+            Ok(quote! {
+                match self {
+                    #(#arms)*
+                }
+            })
+        },
+
+        Data::Union(DataUnion { union_token, .. }) => Err(Error::new(union_token.span, format!("Can only derive `{trt}` on structs or enums"))),
+    }
+}
+
+/// Generates the hidden constant through which the `Node`-derive knows which of `Term` or
+/// `NonTerm` is derived for a type.
+///
+/// This is an inherent constant, which takes precedence over the fallback in
+/// `ast_toolkit2::tree::__private::NodeKindFallback` if it exists.
+///
+/// # Arguments
+/// - `ident`: The name of the type.
+/// - `generics`: The (original, unbounded) [`Generics`] of the type.
+/// - `kind`: The variant of `NodeKind` to emit.
+///
+/// # Returns
+/// An inherent impl defining the constant.
+#[cfg(feature = "tree")]
+pub fn node_kind_marker(ident: &Ident, generics: &Generics, kind: TokenStream2) -> TokenStream2 {
+    let (impl_gen, ty_gen, where_clauses) = generics.split_for_impl();
+    quote! {
+        impl #impl_gen #ident #ty_gen #where_clauses {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            const __AST_TOOLKIT2_NODE_KIND: ::std::option::Option<::ast_toolkit2::tree::NodeKind> =
+                ::std::option::Option::Some(::ast_toolkit2::tree::NodeKind::#kind);
+        }
+    }
+}
+
+/// Checks whether any field of the given type is marked as a `#[child]`.
+///
+/// # Arguments
+/// - `data`: The [`Data`] of the type to find the children of.
+///
+/// # Returns
+/// True if at least one `#[child]` was found, or false otherwise.
+#[cfg(feature = "tree")]
+pub fn has_children(data: &Data) -> Result<bool, Error> {
+    match data {
+        Data::Struct(s) => Ok(!find_children(&s.fields)?.is_empty()),
+        Data::Enum(e) => {
+            for variant in &e.variants {
+                if !find_children(&variant.fields)?.is_empty() {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        },
+        Data::Union(_) => Ok(false),
+    }
+}
+
+
 /// Given a set of [`Generics`], assigns each of them the `Located`-trait.
 ///
 /// # Arguments
 /// - `path`: An iterable of strings that defines the path to add that refers to the
 ///   to-be-implemented trait.
 /// - `gens`: The [`Generics`] to inject the additional trait bounds in.
-pub fn inject_trait_bound<const LEN: usize>(path: [&'static str; LEN], gens: &mut Generics) {
+#[cfg(feature = "tree")]
+#[inline]
+pub fn inject_trait_bound<const LEN: usize>(path: [&'static str; LEN], gens: &mut Generics) { inject_trait_bound_except(path, gens, &[]) }
+
+/// Given a set of [`Generics`], assigns each of them the `Located`-trait except for the given
+/// ones.
+///
+/// # Arguments
+/// - `path`: An iterable of strings that defines the path to add that refers to the
+///   to-be-implemented trait.
+/// - `gens`: The [`Generics`] to inject the additional trait bounds in.
+/// - `skip`: A list of generic type parameters that should _not_ be bounded.
+pub fn inject_trait_bound_except<const LEN: usize>(path: [&'static str; LEN], gens: &mut Generics, skip: &[Ident]) {
     for param in gens.type_params_mut() {
+        if skip.contains(&param.ident) {
+            continue;
+        }
         param.bounds.push(TypeParamBound::Trait(TraitBound {
             paren_token: None,
             modifier: TraitBoundModifier::None,
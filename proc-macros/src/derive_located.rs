@@ -6,13 +6,43 @@
 //
 
 use proc_macro2::{Literal as Literal2, Span, TokenStream as TokenStream2, TokenTree as TokenTree2};
-use quote::quote;
+use quote::{ToTokens as _, quote};
 use syn::parse::{Error, Parser as _};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned as _;
-use syn::{Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Fields, Generics, Ident, Meta, Token, Variant};
+use syn::{Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, ExprPath, Fields, Generics, Ident, Meta, Token, Variant};
+
+use crate::common::inject_trait_bound_except;
+
+
+/***** HELPERS *****/
+/// Defines how multiple `loc`-fields are combined.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Strategy {
+    /// Only the first field is used (or, if there are `#[loc(optional)]` fields, the first one that
+    /// is there).
+    First,
+    /// The fields' locs are `Loc::extend()`ed.
+    Extend,
+    /// The fields' locs are `Loc::intersect()`ed.
+    Intersect,
+}
+impl Strategy {
+    /// Returns the name of the method to fold the fields' locs with.
+    ///
+    /// # Returns
+    /// An [`Ident`] naming the method on `Loc`, or [`None`] if only the first field is used.
+    fn method(&self) -> Option<Ident> {
+        match self {
+            Self::First => None,
+            Self::Extend => Some(Ident::new("extend", Span::call_site())),
+            Self::Intersect => Some(Ident::new("intersect", Span::call_site())),
+        }
+    }
+}
+
+
 
-use crate::common::inject_trait_bound;
 
 
 /***** HELPER FUNCTIONS *****/
@@ -37,9 +67,15 @@ fn has_loc_attr<'a>(attrs: impl IntoIterator<Item = &'a Attribute>) -> Result<bo
                 } else if inner.iter().all(|m| if let Meta::Path(p) = m { p.is_ident("skip") } else { false }) {
                     // All of them are skip attributes. The message is clear.
                     continue;
+                } else if inner.iter().all(|m| if let Meta::Path(p) = m { p.is_ident("optional") } else { false }) {
+                    // It's a `#[loc]` with extra steps
+                    return Ok(true);
                 } else {
                     // Something else!
-                    return Err(Error::new(l.path.span(), "Unsure what to do with attribute; at a field, use `#[loc]`, `#[loc(skip)]` or none."));
+                    return Err(Error::new(
+                        l.path.span(),
+                        "Unsure what to do with attribute; at a field, use `#[loc]`, `#[loc(optional)]`, `#[loc(skip)]` or none.",
+                    ));
                 }
             },
             // Never OK
@@ -74,9 +110,15 @@ fn has_loc_skip_attr<'a>(attrs: impl IntoIterator<Item = &'a Attribute>) -> Resu
                 } else if inner.iter().all(|m| if let Meta::Path(p) = m { p.is_ident("skip") } else { false }) {
                     // All of them are skip attributes. The message is clear.
                     return Ok(true);
+                } else if inner.iter().all(|m| if let Meta::Path(p) = m { p.is_ident("optional") } else { false }) {
+                    // Not skipped, just optional
+                    continue;
                 } else {
                     // Something else!
-                    return Err(Error::new(l.path.span(), "Unsure what to do with attribute; at a field, use `#[loc]`, `#[loc(skip)]` or none."));
+                    return Err(Error::new(
+                        l.path.span(),
+                        "Unsure what to do with attribute; at a field, use `#[loc]`, `#[loc(optional)]`, `#[loc(skip)]` or none.",
+                    ));
                 }
             },
             // We do accept it but not relevant for us
@@ -93,6 +135,258 @@ fn has_loc_skip_attr<'a>(attrs: impl IntoIterator<Item = &'a Attribute>) -> Resu
     Ok(false)
 }
 
+/// Scans a list of attributes for the `#[loc(optional)]`-attribute.
+///
+/// Assumes that [`has_loc_attr()`] or [`has_loc_skip_attr()`] has already validated them.
+///
+/// # Arguments
+/// - `attrs`: Some list of attributes.
+///
+/// # Returns
+/// Whether the attribute was found or not.
+fn has_loc_optional_attr<'a>(attrs: impl IntoIterator<Item = &'a Attribute>) -> bool {
+    attrs.into_iter().any(|attr| match &attr.meta {
+        Meta::List(l) if l.path.is_ident("loc") => Punctuated::<Meta, Token![,]>::parse_terminated
+            .parse2(l.tokens.clone())
+            .map(|inner| inner.iter().any(|m| if let Meta::Path(p) = m { p.is_ident("optional") } else { false }))
+            .unwrap_or(false),
+        _ => false,
+    })
+}
+
+/// Reduces a list of loc fields to those needed for [`Strategy::First`].
+///
+/// Since `#[loc(optional)]` fields may turn out to be [`None`], this keeps every field up to and
+/// including the first non-optional one. [`fold_locs()`] then picks the first of them that is
+/// there.
+///
+/// # Arguments
+/// - `loc_fields`: The indices of the loc fields to reduce. Will be sorted.
+/// - `fields`: The [`Fields`] the indices refer to.
+fn truncate_first(loc_fields: &mut Vec<usize>, fields: &Fields) {
+    loc_fields.sort(); // Just to be safe
+    let optional: Vec<bool> = fields.iter().map(|f| has_loc_optional_attr(&f.attrs)).collect();
+    if let Some(i) = loc_fields.iter().position(|i| !optional[*i]) {
+        loc_fields.truncate(i + 1);
+    }
+}
+
+/// Generates the expression that combines the locs of the given fields.
+///
+/// # Arguments
+/// - `method`: The method to combine them with. Only [`None`] if there is one field.
+/// - `fields`: The expressions referencing each of the fields, together with whether they are
+///   `#[loc(optional)]`.
+///
+/// # Returns
+/// An expression evaluating to the `Loc` of all fields.
+fn fold_locs(method: Option<&Ident>, fields: &[(TokenStream2, bool)]) -> TokenStream2 {
+    if fields.len() == 1 && !fields[0].1 {
+        // Simplest case: just the one
+        let field: &TokenStream2 = &fields[0].0;
+        quote! {{ ::ast_toolkit2::loc::Located::loc(#field) }}
+    } else if fields.iter().all(|(_, optional)| !optional) {
+        // Simple case: all of them are there
+        let first: &TokenStream2 = &fields[0].0;
+        let rest = fields[1..].iter().map(|(field, _)| field);
+        quote! {{
+            let mut res: ::ast_toolkit2::loc::Loc = ::ast_toolkit2::loc::Located::loc(#first);
+            #(res.#method(::ast_toolkit2::loc::Located::loc(#rest));)*
+            res
+        }}
+    } else {
+        // Complex case: only combine those that are there
+        let fields = fields.iter().map(|(field, optional)| {
+            let loc = if *optional {
+                quote! { ::std::option::Option::map(::std::option::Option::as_ref(#field), ::ast_toolkit2::loc::Located::loc) }
+            } else {
+                quote! { ::std::option::Option::Some(::ast_toolkit2::loc::Located::loc(#field)) }
+            };
+            let combine = match method {
+                Some(method) => quote! { res.#method(loc); },
+                None => quote! {},
+            };
+            quote! {
+                if let ::std::option::Option::Some(loc) = #loc {
+                    match &mut res {
+                        ::std::option::Option::Some(res) => { #combine },
+                        ::std::option::Option::None => res = ::std::option::Option::Some(loc),
+                    }
+                }
+            }
+        });
+        quote! {{
+            let mut res: ::std::option::Option<::ast_toolkit2::loc::Loc> = ::std::option::Option::None;
+            #(#fields)*
+            ::std::option::Option::unwrap_or_else(res, ::ast_toolkit2::loc::Loc::new)
+        }}
+    }
+}
+
+/// Scans a list of toplevel attributes for generics given in `#[loc(skip_bound(...))]`.
+///
+/// # Arguments
+/// - `attrs`: Some list of attributes.
+/// - `generics`: The [`Generics`] of the type, to check the given parameters exist.
+///
+/// # Returns
+/// The identifiers of the generic type parameters that should not be bound by `Located`.
+fn find_skip_bounds(attrs: &[Attribute], generics: &Generics) -> Result<Vec<Ident>, Error> {
+    let mut res: Vec<Ident> = Vec::new();
+    for attr in attrs {
+        match &attr.meta {
+            Meta::List(l) if l.path.is_ident("loc") => {
+                let inner: Punctuated<Meta, Token![,]> = Punctuated::parse_terminated.parse2(l.tokens.clone())?;
+                for meta in inner {
+                    if let Meta::List(l) = meta
+                        && l.path.is_ident("skip_bound")
+                    {
+                        let params: Punctuated<Ident, Token![,]> = Punctuated::parse_terminated.parse2(l.tokens)?;
+                        for param in params {
+                            if !generics.type_params().any(|p| p.ident == param) {
+                                return Err(Error::new(param.span(), format!("Unknown generic type parameter `{param}`")));
+                            }
+                            res.push(param);
+                        }
+                    }
+                }
+            },
+
+            // The rest we ignore, part of other crates (or macros)
+            _ => continue,
+        }
+    }
+    Ok(res)
+}
+
+/// Checks that a list of variant attributes has no `#[loc(skip_bound(...))]`.
+///
+/// Bounds are injected for the type as a whole, so skipping them per variant isn't meaningful.
+///
+/// # Arguments
+/// - `attrs`: The attributes of some variant.
+///
+/// # Errors
+/// This function errors if any `#[loc(skip_bound(...))]` is found.
+fn check_no_skip_bounds(attrs: &[Attribute]) -> Result<(), Error> {
+    for attr in attrs {
+        match &attr.meta {
+            Meta::List(l) if l.path.is_ident("loc") => {
+                let inner: Punctuated<Meta, Token![,]> = Punctuated::parse_terminated.parse2(l.tokens.clone())?;
+                for meta in inner {
+                    if let Meta::List(l) = meta
+                        && l.path.is_ident("skip_bound")
+                    {
+                        return Err(Error::new(l.path.span(), "`skip_bound` is only allowed on the type, not on variants"));
+                    }
+                }
+            },
+
+            // The rest we ignore, part of other crates (or macros)
+            _ => continue,
+        }
+    }
+    Ok(())
+}
+
+/// Scans a list of toplevel attributes for a `#[loc(strategy = ...)]`.
+///
+/// If it occurs multiple times, the last one is used. This allows variants to override the
+/// strategy given for the whole enum.
+///
+/// # Arguments
+/// - `attrs`: Some list of attributes.
+///
+/// # Returns
+/// The [`Strategy`] to combine multiple fields with.
+fn find_strategy<'a>(attrs: impl IntoIterator<Item = &'a Attribute>) -> Result<Strategy, Error> {
+    let mut res: Strategy = Strategy::Extend;
+    for attr in attrs {
+        match &attr.meta {
+            Meta::List(l) if l.path.is_ident("loc") => {
+                let inner: Punctuated<Meta, Token![,]> = Punctuated::parse_terminated.parse2(l.tokens.clone())?;
+                for meta in inner {
+                    if let Meta::NameValue(nv) = meta
+                        && nv.path.is_ident("strategy")
+                    {
+                        let value: Ident = syn::parse2(nv.value.to_token_stream())?;
+                        res = if value == "first" {
+                            Strategy::First
+                        } else if value == "extend" {
+                            Strategy::Extend
+                        } else if value == "intersect" {
+                            Strategy::Intersect
+                        } else {
+                            return Err(Error::new(value.span(), "Unknown strategy; use `first`, `extend` or `intersect`"));
+                        };
+                    }
+                }
+            },
+
+            // The rest we ignore, part of other crates (or macros)
+            _ => continue,
+        }
+    }
+    Ok(res)
+}
+
+/// Scans a list of toplevel attributes for a `#[loc(call = ...)]`.
+///
+/// # Arguments
+/// - `attrs`: Some list of attributes.
+///
+/// # Returns
+/// The [`ExprPath`] of the function or method to call, if any.
+fn find_call(attrs: &[Attribute]) -> Result<Option<ExprPath>, Error> {
+    let mut res: Option<ExprPath> = None;
+    let mut others: Vec<Span> = Vec::new();
+    for attr in attrs {
+        match &attr.meta {
+            Meta::List(l) if l.path.is_ident("loc") => {
+                let inner: Punctuated<Meta, Token![,]> = Punctuated::parse_terminated.parse2(l.tokens.clone())?;
+                for meta in inner {
+                    match meta {
+                        Meta::NameValue(nv) if nv.path.is_ident("call") => res = Some(syn::parse2(nv.value.to_token_stream())?),
+                        meta => others.push(meta.span()),
+                    }
+                }
+            },
+
+            // The rest we ignore, part of other crates (or macros)
+            _ => continue,
+        }
+    }
+    if res.is_some()
+        && let Some(span) = others.first()
+    {
+        return Err(Error::new(*span, "Cannot combine `#[loc(call = ...)]` with other `#[loc]`-attributes"));
+    }
+    Ok(res)
+}
+
+/// Checks that none of the fields or variants of a type have `#[loc]`-attributes.
+///
+/// Used when a toplevel `#[loc(call = ...)]` is given, as those would be ignored.
+///
+/// # Arguments
+/// - `data`: The [`Data`] of the type to check.
+///
+/// # Errors
+/// This function errors if any field or variant has a `#[loc]`-attribute.
+fn check_no_loc_attrs(data: &Data) -> Result<(), Error> {
+    let attrs: Vec<&Attribute> = match data {
+        Data::Struct(s) => s.fields.iter().flat_map(|f| &f.attrs).collect(),
+        Data::Enum(e) => e.variants.iter().flat_map(|v| v.attrs.iter().chain(v.fields.iter().flat_map(|f| &f.attrs))).collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    for attr in attrs {
+        if attr.path().is_ident("loc") {
+            return Err(Error::new(attr.span(), "Cannot combine `#[loc(call = ...)]` with `#[loc]`-attributes on fields or variants"));
+        }
+    }
+    Ok(())
+}
+
 /// Given a set of [`Fields`], attempts to find the `loc`-field.
 ///
 /// It scans for either:
@@ -122,10 +416,18 @@ pub fn find_loc_fields<'a>(trt: &'static str, attrs: impl IntoIterator<Item = &'
                         // Now we control all attributes, so only do sensible ones
                         Meta::Path(p) if p.is_ident("all") => do_all = Some(p.span()),
                         Meta::Path(p) if p.is_ident("new") => do_new = Some(p.span()),
+                        // Handled by `find_skip_bounds()` and `find_strategy()`
+                        Meta::List(l) if l.path.is_ident("skip_bound") => continue,
+                        Meta::NameValue(nv) if nv.path.is_ident("strategy") => continue,
+                        // NOTE: A toplevel `call` is handled by `find_call()` before we ever get here,
+                        // so if we see one, it's given somewhere we don't support it
+                        Meta::NameValue(nv) if nv.path.is_ident("call") => {
+                            return Err(Error::new(nv.span(), "`call` is only allowed on the type, and only when deriving `Located`"));
+                        },
                         meta => {
                             return Err(Error::new(
                                 meta.span(),
-                                &format!(
+                                format!(
                                     "Unknown attribute{}",
                                     if let Some(ident) = meta.path().get_ident() { format!(" {ident}") } else { String::new() }
                                 ),
@@ -151,7 +453,7 @@ pub fn find_loc_fields<'a>(trt: &'static str, attrs: impl IntoIterator<Item = &'
         // We do all fields... except those with `skip`!
         // NOTE: Some gymnastics are required here to deal with `has_loc_skip_attr()` possibly
         // failing.
-        return Ok(fields
+        return fields
             .iter()
             .enumerate()
             .filter_map(|(i, f)| match has_loc_skip_attr(&f.attrs) {
@@ -159,7 +461,7 @@ pub fn find_loc_fields<'a>(trt: &'static str, attrs: impl IntoIterator<Item = &'
                 Ok(false) => Some(Ok(i)),
                 Err(err) => Some(Err(err)),
             })
-            .collect::<Result<Vec<usize>, Error>>()?);
+            .collect::<Result<Vec<usize>, Error>>();
     } else if do_all.is_none() && do_new.is_some() {
         return Ok(Vec::new());
     } else if let (Some(_), Some(do_new)) = (do_all, do_new) {
@@ -197,11 +499,11 @@ pub fn find_loc_fields<'a>(trt: &'static str, attrs: impl IntoIterator<Item = &'
             } else if !too_many_candidates.is_empty() {
                 Err(Error::new(
                     fields.span(),
-                    &format!("Failed to find any `#[loc]` field but found more than one `loc` fields; cannot derive `{trt}`"),
+                    format!("Failed to find any `#[loc]` field but found more than one `loc` fields; cannot derive `{trt}`"),
                 ))
             } else {
                 // ...or absolutely nothing
-                Err(Error::new(fields.span(), &format!("Failed to find any `#[loc]` field or a field named `loc`; cannot derive `{trt}`")))
+                Err(Error::new(fields.span(), format!("Failed to find any `#[loc]` field or a field named `loc`; cannot derive `{trt}`")))
             }
         },
 
@@ -223,11 +525,11 @@ pub fn find_loc_fields<'a>(trt: &'static str, attrs: impl IntoIterator<Item = &'
             if !res.is_empty() {
                 Ok(res)
             } else {
-                Err(Error::new(fields.span(), &format!("Failed to find any `#[loc]` field; cannot derive `{trt}`")))
+                Err(Error::new(fields.span(), format!("Failed to find any `#[loc]` field; cannot derive `{trt}`")))
             }
         },
 
-        Fields::Unit => Err(Error::new(fields.span(), &format!("No fields present; cannot derive `{trt}`"))),
+        Fields::Unit => Err(Error::new(fields.span(), format!("No fields present; cannot derive `{trt}`"))),
     }
 }
 
@@ -249,58 +551,45 @@ fn handle_struct(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, Da
         } });
     }
 
-    // Injects the generics
-    inject_trait_bound(["ast_toolkit2", "loc", "Located"], &mut generics);
+    // Only keep what we need of them
+    let strategy: Strategy = find_strategy(&attrs)?;
+    if strategy == Strategy::First {
+        truncate_first(&mut loc_fields, &fields);
+    }
 
-    // Use that to build an impl
-    if loc_fields.len() == 1 {
-        let field = loc_fields.pop().unwrap();
-        let name: TokenTree2 = match &fields.iter().nth(field).unwrap().ident {
-            Some(name) => TokenTree2::Ident(name.clone()),
-            None => TokenTree2::Literal(Literal2::usize_unsuffixed(field)),
-        };
-        let (impl_gen, ty_gen, where_bounds) = generics.split_for_impl();
-        Ok(quote! { impl #impl_gen ::ast_toolkit2::loc::Located for #ident #ty_gen #where_bounds {
-            #[inline]
-            fn loc(&self) -> ::ast_toolkit2::loc::Loc {
-                ::ast_toolkit2::loc::Located::loc(&self.#name)
-            }
-        } })
-    } else {
-        // Collect how to refer to each field
-        let mut names: Vec<TokenTree2> = Vec::with_capacity(loc_fields.len());
-        loc_fields.sort(); // Just to be safe
-        let mut iter = fields.iter();
-        let mut last_i: usize = 0;
-        for i in loc_fields {
-            // Find the pointer-to field
-            while last_i < i {
-                iter.next();
-                last_i += 1;
-            }
-            let field = iter.next().unwrap();
+    // Injects the generics
+    let skip: Vec<Ident> = find_skip_bounds(&attrs, &generics)?;
+    inject_trait_bound_except(["ast_toolkit2", "loc", "Located"], &mut generics, &skip);
+
+    // Collect how to refer to each field
+    let mut names: Vec<(TokenStream2, bool)> = Vec::with_capacity(loc_fields.len());
+    loc_fields.sort(); // Just to be safe
+    let mut iter = fields.iter();
+    let mut last_i: usize = 0;
+    for i in loc_fields {
+        // Find the pointer-to field
+        while last_i < i {
+            iter.next();
             last_i += 1;
-
-            // Build the resulting identifier
-            names.push(match &field.ident {
-                Some(name) => TokenTree2::Ident(name.clone()),
-                None => TokenTree2::Literal(Literal2::usize_unsuffixed(i)),
-            });
         }
+        let field = iter.next().unwrap();
+        last_i += 1;
 
-        // Now build the full impl
-        let (impl_gen, ty_gen, where_bounds) = generics.split_for_impl();
-        let first: &TokenTree2 = names.first().unwrap();
-        let rest: &[TokenTree2] = &names[1..];
-        Ok(quote! { impl #impl_gen ::ast_toolkit2::loc::Located for #ident #ty_gen #where_bounds {
-            #[inline]
-            fn loc(&self) -> ::ast_toolkit2::loc::Loc {
-                let mut res: ::ast_toolkit2::loc::Loc = ::ast_toolkit2::loc::Located::loc(&self.#first);
-                #(res.extend(::ast_toolkit2::loc::Located::loc(&self.#rest));)*
-                res
-            }
-        } })
+        // Build the resulting identifier
+        let name: TokenTree2 = match &field.ident {
+            Some(name) => TokenTree2::Ident(name.clone()),
+            None => TokenTree2::Literal(Literal2::usize_unsuffixed(i)),
+        };
+        names.push((quote! { &self.#name }, has_loc_optional_attr(&field.attrs)));
     }
+
+    // Now build the full impl
+    let (impl_gen, ty_gen, where_bounds) = generics.split_for_impl();
+    let body: TokenStream2 = fold_locs(strategy.method().as_ref(), &names);
+    Ok(quote! { impl #impl_gen ::ast_toolkit2::loc::Located for #ident #ty_gen #where_bounds {
+        #[inline]
+        fn loc(&self) -> ::ast_toolkit2::loc::Loc #body
+    } })
 }
 
 
@@ -308,16 +597,24 @@ fn handle_struct(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, Da
 /// Handler for enums.
 fn handle_enum(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, data: DataEnum) -> Result<TokenStream2, Error> {
     // For every variant...
-    let mut variants: Vec<(Ident, bool, usize, Vec<(usize, Ident)>)> = Vec::with_capacity(data.variants.len());
+    #[allow(clippy::type_complexity)]
+    let mut variants: Vec<(Ident, bool, usize, Option<Ident>, Vec<(usize, Ident, bool)>)> = Vec::with_capacity(data.variants.len());
     for Variant { attrs: vattrs, ident, fields, .. } in data.variants {
         // Search the fields for our darling fields
-        let loc_fields = find_loc_fields("Located", attrs.iter().chain(vattrs.iter()), &fields)?;
+        check_no_skip_bounds(&vattrs)?;
+        let mut loc_fields = find_loc_fields("Located", attrs.iter().chain(vattrs.iter()), &fields)?;
         if loc_fields.is_empty() {
             // Special case: the user gave us `#[loc(new)]` on this type or variant
-            variants.push((ident, matches!(fields, Fields::Named(_)), fields.len(), Vec::new()));
+            variants.push((ident, matches!(fields, Fields::Named(_)), fields.len(), None, Vec::new()));
             continue;
         }
 
+        // Only keep what we need of them
+        let strategy: Strategy = find_strategy(attrs.iter().chain(vattrs.iter()))?;
+        if strategy == Strategy::First {
+            truncate_first(&mut loc_fields, &fields);
+        }
+
         // Store the fields we have selected
         let is_named: bool = matches!(fields, Fields::Named(_));
         let total_fields: usize = fields.len();
@@ -334,12 +631,17 @@ fn handle_enum(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, data
             last_i += 1;
 
             // Build the resulting identifier
-            res.push((i, match field.ident {
-                Some(name) => name,
-                None => Ident::new(&format!("field{i}"), field.span()),
-            }));
+            let optional: bool = has_loc_optional_attr(&field.attrs);
+            res.push((
+                i,
+                match field.ident {
+                    Some(name) => name,
+                    None => Ident::new(&format!("field{i}"), field.span()),
+                },
+                optional,
+            ));
         }
-        variants.push((ident, is_named, total_fields, res));
+        variants.push((ident, is_named, total_fields, strategy.method(), res));
     }
 
     // Early-escape: if there are no variants, we don't generate the normal impl
@@ -352,7 +654,7 @@ fn handle_enum(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, data
         } });
     }
     // Check if all fields are empty
-    if variants.iter().all(|(_, _, _, res)| res.is_empty()) {
+    if variants.iter().all(|(_, _, _, _, res)| res.is_empty()) {
         // Special case: the user gave us `#[loc(new)]` on _all_ variants
         let (impl_gen, ty_gen, where_bounds) = generics.split_for_impl();
         return Ok(quote! { impl #impl_gen ::ast_toolkit2::loc::Located for #ident #ty_gen #where_bounds {
@@ -360,11 +662,12 @@ fn handle_enum(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, data
             fn loc(&self) -> ::ast_toolkit2::loc::Loc { ::ast_toolkit2::loc::Loc::new() }
         } });
     }
-    inject_trait_bound(["ast_toolkit2", "loc", "Located"], &mut generics);
+    let skip: Vec<Ident> = find_skip_bounds(&attrs, &generics)?;
+    inject_trait_bound_except(["ast_toolkit2", "loc", "Located"], &mut generics, &skip);
 
     // With that done, build the impl for each variant
     let mut inner: Vec<TokenStream2> = Vec::with_capacity(variants.len());
-    for (variant, is_named, total_fields, fields) in variants {
+    for (variant, is_named, total_fields, method, fields) in variants {
         // Special case: the user gave `#[loc(new)]` _only_ for this variant
         if fields.is_empty() {
             inner.push(quote! { Self::#variant { .. } => ::ast_toolkit2::loc::Loc::new(), });
@@ -372,16 +675,14 @@ fn handle_enum(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, data
         }
 
         // Else, we do the complex case
+        let body: TokenStream2 = fold_locs(method.as_ref(), &fields.iter().map(|(_, n, optional)| (quote! { #n }, *optional)).collect::<Vec<_>>());
         if is_named {
-            let nfields: Vec<&Ident> = fields.iter().map(|(_, n)| n).collect();
-            let name: &Ident = nfields.first().unwrap();
-            let rest: &[&Ident] = &nfields[1..];
-            inner.push(quote! { Self::#variant{ #(#nfields,)* .. } => { let mut res = ::ast_toolkit2::loc::Located::loc(#name); #(res.extend(::ast_toolkit2::loc::Located::loc(#rest));)* res }, });
+            let nfields = fields.iter().map(|(_, n, _)| n);
+            inner.push(quote! { Self::#variant{ #(#nfields,)* .. } => #body, });
         } else {
-            let ufields: Vec<&Ident> = fields.iter().map(|(_, n)| n).collect();
             let mut all_ufields = Vec::with_capacity(total_fields);
             let mut last_i: usize = 0;
-            for (i, n) in &fields {
+            for (i, n, _) in &fields {
                 while last_i < *i {
                     all_ufields.push(Ident::new("_", Span::call_site()));
                     last_i += 1;
@@ -393,9 +694,7 @@ fn handle_enum(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, data
                 all_ufields.push(Ident::new("_", Span::call_site()));
                 last_i += 1;
             }
-            let name: &Ident = ufields.first().unwrap();
-            let rest: &[&Ident] = &ufields[1..];
-            inner.push(quote! { Self::#variant(#(#all_ufields),*) => { let mut res = ::ast_toolkit2::loc::Located::loc(#name); #(res.extend(::ast_toolkit2::loc::Located::loc(#rest));)* res } });
+            inner.push(quote! { Self::#variant(#(#all_ufields),*) => #body, });
         }
     }
 
@@ -416,9 +715,70 @@ fn handle_enum(attrs: Vec<Attribute>, ident: Ident, mut generics: Generics, data
 /// Main handler for the macro.
 pub fn handle(item: TokenStream2) -> Result<TokenStream2, Error> {
     let DeriveInput { attrs, ident, generics, data, .. } = syn::parse2(item)?;
+
+    // Special case: the user gave us `#[loc(call = ...)]`
+    if let Some(call) = find_call(&attrs)? {
+        check_no_loc_attrs(&data)?;
+        let body: TokenStream2 = match call.path.get_ident() {
+            Some(method) => quote! { self.#method() },
+            None => quote! { #call(self) },
+        };
+        let (impl_gen, ty_gen, where_bounds) = generics.split_for_impl();
+        return Ok(quote! { impl #impl_gen ::ast_toolkit2::loc::Located for #ident #ty_gen #where_bounds {
+            #[inline]
+            fn loc(&self) -> ::ast_toolkit2::loc::Loc { #body }
+        } });
+    }
+
     match data {
         Data::Struct(data_struct) => handle_struct(attrs, ident, generics, data_struct),
         Data::Enum(data_enum) => handle_enum(attrs, ident, generics, data_enum),
         Data::Union(DataUnion { union_token, .. }) => Err(Error::new(union_token.span, "Can only derive `Located` on structs or enums")),
     }
 }
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_call() {
+        // Combined with field-level markers
+        let err = handle(quote! {
+            #[loc(call = compute_loc)]
+            struct Foo {
+                #[loc]
+                loc: Loc,
+            }
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Cannot combine `#[loc(call = ...)]` with `#[loc]`-attributes on fields or variants");
+
+        // At a variant
+        let err = handle(quote! {
+            enum Foo {
+                #[loc(call = compute_loc)]
+                A(Loc),
+            }
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "`call` is only allowed on the type, and only when deriving `Located`");
+    }
+
+    #[test]
+    fn test_handle_skip_bound() {
+        let err = handle(quote! {
+            enum Foo<T> {
+                #[loc(skip_bound(T))]
+                A(Loc, ::std::marker::PhantomData<T>),
+            }
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "`skip_bound` is only allowed on the type, not on variants");
+    }
+}
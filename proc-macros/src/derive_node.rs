@@ -7,23 +7,48 @@
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{Data, DataUnion, DeriveInput, Error};
+use syn::{DeriveInput, Error, WherePredicate};
 
-use crate::common::inject_trait_bound;
+use crate::common::{has_children, inject_trait_bound, with_children};
 
 
 /***** LIBRARY *****/
 /// Main handler for the macro.
 pub fn handle(item: TokenStream2) -> Result<TokenStream2, Error> {
     let DeriveInput { ident, data, mut generics, .. } = syn::parse2(item)?;
-    match data {
-        Data::Struct(_) | Data::Enum(_) => {
-            inject_trait_bound(["ast_toolkit2", "loc", "Located"], &mut generics);
-            let (impl_gen, ty_gen, where_clauses) = generics.split_for_impl();
-            Ok(quote! {
-                impl #impl_gen ::ast_toolkit2::tree::Node for #ident #ty_gen #where_clauses {}
-            })
-        },
-        Data::Union(DataUnion { union_token, .. }) => Err(Error::new(union_token.span, "Can only derive `Node` on structs or enums")),
-    }
+
+    // The kind is decided by the `Term`- or `NonTerm`-derive, if any. Otherwise, nodes with
+    // children are walked as non-terminals, and the rest as terminals.
+    let mut bounds: Vec<WherePredicate> = Vec::new();
+    let fallback: TokenStream2 = if has_children(&data)? { quote! { NonTerm } } else { quote! { Term } };
+    let body: TokenStream2 = with_children("Node", &data, &generics, &mut bounds, |refs| {
+        quote! {
+            match ::ast_toolkit2::tree::Node::kind(self) {
+                ::ast_toolkit2::tree::NodeKind::NonTerm => {
+                    ::ast_toolkit2::tree::visit::walk_nonterm(self, [#(#refs as &dyn ::ast_toolkit2::tree::Node),*], visitor)
+                },
+                ::ast_toolkit2::tree::NodeKind::Term => ::ast_toolkit2::tree::visit::walk_term(self, visitor),
+            }
+        }
+    })?;
+
+    inject_trait_bound(["ast_toolkit2", "loc", "Located"], &mut generics);
+    generics.make_where_clause().predicates.extend(bounds);
+    let (impl_gen, ty_gen, where_clauses) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_gen ::ast_toolkit2::tree::Node for #ident #ty_gen #where_clauses {
+            #[inline]
+            fn kind(&self) -> ::ast_toolkit2::tree::NodeKind {
+                #[allow(unused_imports)]
+                use ::ast_toolkit2::tree::__private::NodeKindFallback as _;
+                match Self::__AST_TOOLKIT2_NODE_KIND {
+                    ::std::option::Option::Some(kind) => kind,
+                    ::std::option::Option::None => ::ast_toolkit2::tree::NodeKind::#fallback,
+                }
+            }
+
+            #[inline]
+            fn walk(&self, visitor: &mut dyn ::ast_toolkit2::tree::visit::Visitor) -> ::std::ops::ControlFlow<()> { #body }
+        }
+    })
 }
@@ -7,23 +7,28 @@
 
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
-use syn::{Data, DataUnion, DeriveInput, Error};
+use syn::{DeriveInput, Error, WherePredicate};
 
-use crate::common::inject_trait_bound;
+use crate::common::{inject_trait_bound, node_kind_marker, with_children};
 
 
 /***** LIBRARY *****/
 /// Main handler for the macro.
 pub fn handle(item: TokenStream2) -> Result<TokenStream2, Error> {
     let DeriveInput { ident, data, mut generics, .. } = syn::parse2(item)?;
-    match data {
-        Data::Struct(_) | Data::Enum(_) => {
-            inject_trait_bound(["ast_toolkit2", "loc", "Located"], &mut generics);
-            let (impl_gen, ty_gen, where_clauses) = generics.split_for_impl();
-            Ok(quote! {
-                impl #impl_gen ::ast_toolkit2::tree::NonTerm for #ident #ty_gen #where_clauses {}
-            })
-        },
-        Data::Union(DataUnion { union_token, .. }) => Err(Error::new(union_token.span, "Can only derive `NonTerm` on structs or enums")),
-    }
+    let mut bounds: Vec<WherePredicate> = Vec::new();
+    let body: TokenStream2 =
+        with_children("NonTerm", &data, &generics, &mut bounds, |refs| quote! { ::std::vec![#(#refs as &dyn ::ast_toolkit2::tree::Node),*] })?;
+
+    let marker: TokenStream2 = node_kind_marker(&ident, &generics, quote! { NonTerm });
+    inject_trait_bound(["ast_toolkit2", "loc", "Located"], &mut generics);
+    generics.make_where_clause().predicates.extend(bounds);
+    let (impl_gen, ty_gen, where_clauses) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_gen ::ast_toolkit2::tree::NonTerm for #ident #ty_gen #where_clauses {
+            #[inline]
+            fn children(&self) -> ::std::vec::Vec<&dyn ::ast_toolkit2::tree::Node> { #body }
+        }
+        #marker
+    })
 }
@@ -0,0 +1,289 @@
+//  DERIVE PARSABLE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements the derive macro for `Parsable`.
+//
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{ToTokens as _, format_ident, quote};
+use syn::parse::Parser as _;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned as _;
+use syn::{Attribute, Data, DataEnum, DataUnion, DeriveInput, Error, Fields, GenericParam, Ident, Meta, Token, Type, TypeParam, parse_quote};
+
+
+/***** CONSTANTS *****/
+/// The maximum arity of tuples implementing `Parsable`.
+const MAX_ARITY: usize = 8;
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Defines how to parse the given toplevel attributes.
+///
+/// # Arguments
+/// - `attrs`: The toplevel attributes to scan through.
+///
+/// # Returns
+/// The element type given with `#[parsable(elem = ...)]`, if any.
+fn parse_attrs(attrs: &[Attribute]) -> Result<Option<Type>, Error> {
+    let mut elem: Option<Type> = None;
+    for attr in attrs {
+        match &attr.meta {
+            Meta::List(l) if l.path.is_ident("parsable") => {
+                let inner: Punctuated<Meta, Token![,]> = Punctuated::parse_terminated.parse2(l.tokens.clone())?;
+                for meta in inner {
+                    match meta {
+                        Meta::NameValue(nv) if nv.path.is_ident("elem") => {
+                            if elem.is_some() {
+                                return Err(Error::new(nv.path.span(), "Cannot define `#[parsable(elem = ...)]` twice"));
+                            }
+                            let value = &nv.value;
+                            elem = Some(syn::parse2(quote! { #value })?);
+                        },
+                        meta => return Err(Error::new(meta.span(), "Unknown attribute; at the toplevel, use `#[parsable(elem = ...)]` or none.")),
+                    }
+                }
+            },
+            Meta::Path(p) if p.is_ident("parsable") => {
+                return Err(Error::new(p.span(), "Unsure what to do with attribute; at the toplevel, use `#[parsable(elem = ...)]` or none."));
+            },
+            Meta::NameValue(nv) if nv.path.is_ident("parsable") => {
+                return Err(Error::new(nv.span(), "Unsure what to do with attribute; at the toplevel, use `#[parsable(elem = ...)]` or none."));
+            },
+
+            // Rest is ignored
+            _ => continue,
+        }
+    }
+    Ok(elem)
+}
+
+/// Scans a list of attributes of a variant for the `#[parsable(skip)]`-attribute.
+///
+/// # Arguments
+/// - `attrs`: Some list of attributes.
+///
+/// # Returns
+/// Whether the attribute was found or not.
+fn has_skip_attr(attrs: &[Attribute]) -> Result<bool, Error> {
+    for attr in attrs {
+        match &attr.meta {
+            Meta::List(l) if l.path.is_ident("parsable") => {
+                let inner: Punctuated<Meta, Token![,]> = Punctuated::parse_terminated.parse2(l.tokens.clone())?;
+                if let Some(meta) = inner.iter().find(|m| if let Meta::Path(p) = m { !p.is_ident("skip") } else { true }) {
+                    return Err(Error::new(meta.span(), "Unknown attribute; at a variant, use `#[parsable(skip)]` or none."));
+                } else if !inner.is_empty() {
+                    return Ok(true);
+                }
+            },
+            Meta::Path(p) if p.is_ident("parsable") => {
+                return Err(Error::new(p.span(), "Unsure what to do with attribute; at a variant, use `#[parsable(skip)]` or none."));
+            },
+            Meta::NameValue(nv) if nv.path.is_ident("parsable") => {
+                return Err(Error::new(nv.span(), "Unsure what to do with attribute; at a variant, use `#[parsable(skip)]` or none."));
+            },
+
+            // Rest is ignored
+            _ => continue,
+        }
+    }
+    Ok(false)
+}
+
+/// Builds the (possibly nested) `Either` type that tries the given alternatives in order.
+///
+/// # Arguments
+/// - `seqs`: The types of the alternatives to parse.
+///
+/// # Returns
+/// The type to parse.
+fn alt_type(seqs: &[TokenStream2]) -> TokenStream2 {
+    if seqs.len() == 1 {
+        seqs[0].clone()
+    } else {
+        let head = &seqs[0];
+        let tail = alt_type(&seqs[1..]);
+        quote! { ::ast_toolkit2::nibble::combinators::Either<#head, #tail> }
+    }
+}
+
+/// Builds the pattern that matches the `i`th alternative of a parsed [`alt_type()`].
+///
+/// # Arguments
+/// - `i`: The index of the alternative to match.
+/// - `n`: The total number of alternatives.
+/// - `pat`: The pattern to match the alternative itself with.
+///
+/// # Returns
+/// The pattern to match on.
+fn alt_pat(i: usize, n: usize, pat: TokenStream2) -> TokenStream2 {
+    let mut res: TokenStream2 = if i + 1 < n { quote! { ::ast_toolkit2::nibble::combinators::Either::Left(#pat) } } else { pat };
+    for _ in 0..i {
+        res = quote! { ::ast_toolkit2::nibble::combinators::Either::Right(#res) };
+    }
+    res
+}
+
+/// Builds the (possibly nested) tuple type that parses the given fields in sequence.
+///
+/// Tuples only implement `Parsable` up to [`MAX_ARITY`], so longer sequences nest their tail in
+/// the last element.
+///
+/// # Arguments
+/// - `tys`: The types of the fields to parse.
+///
+/// # Returns
+/// The type to parse.
+fn seq_type(tys: &[&Type]) -> TokenStream2 {
+    if tys.len() == 1 {
+        let ty = &tys[0];
+        quote! { #ty }
+    } else if tys.len() <= MAX_ARITY {
+        quote! { (#(#tys,)*) }
+    } else {
+        let (head, tail) = tys.split_at(MAX_ARITY - 1);
+        let tail = seq_type(tail);
+        quote! { (#(#head,)* #tail,) }
+    }
+}
+
+/// Builds the (possibly nested) tuple pattern that destructures a parsed [`seq_type()`].
+///
+/// # Arguments
+/// - `names`: The names to bind every field value to.
+///
+/// # Returns
+/// The pattern to match on.
+fn seq_pat(names: &[Ident]) -> TokenStream2 {
+    if names.len() == 1 {
+        let name = &names[0];
+        quote! { #name }
+    } else if names.len() <= MAX_ARITY {
+        quote! { (#(#names,)*) }
+    } else {
+        let (head, tail) = names.split_at(MAX_ARITY - 1);
+        let tail = seq_pat(tail);
+        quote! { (#(#head,)* #tail,) }
+    }
+}
+
+/// Analyzes a set of fields for parsing them in sequence.
+///
+/// # Arguments
+/// - `fields`: The [`Fields`] to parse.
+/// - `span`: A [`Span`] to report errors at if there are no fields.
+///
+/// # Returns
+/// A tuple of the type to parse, the pattern to destructure it with and the expression that
+/// constructs the fields from the pattern's bindings (without the path to the struct or
+/// variant).
+fn analyze_fields(fields: &Fields, span: Span) -> Result<(TokenStream2, TokenStream2, TokenStream2), Error> {
+    if fields.is_empty() {
+        return Err(Error::new(span, "Cannot derive `Parsable` for something without fields, as there would be nothing to parse"));
+    }
+
+    let tys: Vec<&Type> = fields.iter().map(|f| &f.ty).collect();
+    let names: Vec<Ident> = (0..tys.len()).map(|i| format_ident!("__field{i}")).collect();
+    let cons: TokenStream2 = match fields {
+        Fields::Named(_) => {
+            let idents = fields.iter().map(|f| f.ident.as_ref().unwrap());
+            quote! { { #(#idents: #names),* } }
+        },
+        Fields::Unnamed(_) => quote! { ( #(#names),* ) },
+        Fields::Unit => unreachable!(),
+    };
+    Ok((seq_type(&tys), seq_pat(&names), cons))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Main handler for the macro.
+pub fn handle(item: TokenStream2) -> Result<TokenStream2, Error> {
+    let DeriveInput { attrs, ident, data, mut generics, .. } = syn::parse2(item)?;
+    let ty_gen: TokenStream2 = generics.split_for_impl().1.to_token_stream();
+
+    // Decide the element type, introducing a new generic if it's not fixed
+    let elem: Type = match parse_attrs(&attrs)? {
+        Some(elem) => elem,
+        None => {
+            let param = Ident::new("__E", Span::call_site());
+            generics.params.push(GenericParam::Type(TypeParam::from(param.clone())));
+            parse_quote! { #param }
+        },
+    };
+
+    // Find what to parse, and how to turn it into Self
+    let (seq, body): (TokenStream2, TokenStream2) = match data {
+        Data::Struct(s) => {
+            let (seq, pat, cons) = analyze_fields(&s.fields, ident.span())?;
+            let body = quote! {
+                let (#pat, rem) = <#seq as ::ast_toolkit2::nibble::Parsable<#elem>>::parse(input)?;
+                ::std::result::Result::Ok((Self #cons, rem))
+            };
+            (seq, body)
+        },
+
+        Data::Enum(DataEnum { variants, .. }) => {
+            // Collect the alternatives to try
+            let mut alts: Vec<(Ident, TokenStream2, TokenStream2, TokenStream2)> = Vec::with_capacity(variants.len());
+            for variant in variants {
+                if has_skip_attr(&variant.attrs)? {
+                    continue;
+                }
+                if variant.fields.is_empty() {
+                    return Err(Error::new(
+                        variant.ident.span(),
+                        "Cannot derive `Parsable` for a variant without fields, as there would be nothing to parse; use `#[parsable(skip)]` to exclude it",
+                    ));
+                }
+                let (seq, pat, cons) = analyze_fields(&variant.fields, variant.ident.span())?;
+                alts.push((variant.ident, seq, pat, cons));
+            }
+            if alts.is_empty() {
+                return Err(Error::new(ident.span(), "Cannot derive `Parsable` for an enum without (non-skipped) variants, as there would be nothing to parse"));
+            }
+
+            // Then parse them as nested eithers
+            let seq: TokenStream2 = alt_type(&alts.iter().map(|(_, seq, _, _)| seq.clone()).collect::<Vec<TokenStream2>>());
+            let n: usize = alts.len();
+            let arms = alts.into_iter().enumerate().map(|(i, (name, _, pat, cons))| {
+                let pat = alt_pat(i, n, pat);
+                quote! { #pat => Self::#name #cons, }
+            });
+            let body = quote! {
+                let (res, rem) = <#seq as ::ast_toolkit2::nibble::Parsable<#elem>>::parse(input)?;
+                ::std::result::Result::Ok((match res { #(#arms)* }, rem))
+            };
+            (seq, body)
+        },
+
+        Data::Union(DataUnion { union_token, .. }) => return Err(Error::new(union_token.span, "Can only derive `Parsable` on structs or enums")),
+    };
+
+    // Build the impl
+    generics.make_where_clause().predicates.push(parse_quote! { #seq: ::ast_toolkit2::nibble::Parsable<#elem> });
+    let (impl_gen, _, where_clauses) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_gen ::ast_toolkit2::nibble::Parsable<#elem> for #ident #ty_gen #where_clauses {
+            type Formatter = <#seq as ::ast_toolkit2::nibble::Parsable<#elem>>::Formatter;
+            type Error = <#seq as ::ast_toolkit2::nibble::Parsable<#elem>>::Error;
+
+            #[inline]
+            fn expects() -> Self::Formatter { <#seq as ::ast_toolkit2::nibble::Parsable<#elem>>::expects() }
+
+            #[inline]
+            fn parse(
+                input: ::ast_toolkit2::nibble::Slice<#elem>,
+            ) -> ::std::result::Result<(Self, ::ast_toolkit2::nibble::Slice<#elem>), ::ast_toolkit2::nibble::NibbleError<Self::Formatter, Self::Error>> {
+                #body
+            }
+        }
+    })
+}
@@ -9,24 +9,48 @@ use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{quote, quote_spanned};
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned as _;
-use syn::{Attribute, Data, DataEnum, DataUnion, DeriveInput, Error, Expr, LitInt, Meta, Token, Type};
+use syn::{Attribute, Data, DataEnum, DataUnion, DeriveInput, Error, Expr, Ident, LitByteStr, LitInt, LitStr, Meta, Token, Type, parse_quote};
 
 use crate::common::inject_trait_bound;
 
 
 /***** HELPERS *****/
 /// Defines how to parse the contents of a single `#[tag(...)]`-attribute.
-struct Attr(Type, Expr);
+///
+/// This is either `ELEM, TAG` or a string literal that is parsed as its UTF-8 bytes. Both may be
+/// followed by `, no_loc`.
+struct Attr(Type, Expr, bool);
 impl Parse for Attr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let ty: Type = input.parse()?;
-        input.parse::<Token![,]>()?;
-        let expr: Expr = input.parse()?;
+        let (ty, expr): (Type, Expr) = if input.peek(LitStr) {
+            // It's a string tag, so parse it as the bytes of the string
+            let lit: LitStr = input.parse()?;
+            let bytes = LitByteStr::new(lit.value().as_bytes(), lit.span());
+            (parse_quote! { u8 }, parse_quote! { #bytes })
+        } else {
+            let ty: Type = input.parse()?;
+            input
+                .parse::<Token![,]>()
+                .map_err(|err| Error::new(err.span(), "Expected a comma between the element type and the tag (e.g., `#[tag(u8, b\"foo\")]`)"))?;
+            (ty, input.parse()?)
+        };
+
+        // Parse the optional `no_loc`
+        let mut no_loc: bool = false;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let ident: Ident = input.parse()?;
+            if ident != "no_loc" {
+                return Err(Error::new(ident.span(), format!("Unknown tag option `{ident}`; use `no_loc` or none.")));
+            }
+            no_loc = true;
+        }
+
         //  Must be all
         if !input.is_empty() {
             return Err(input.error("Unexpected tokens after type and expression"));
         }
-        Ok(Self(ty, expr))
+        Ok(Self(ty, expr, no_loc))
     }
 }
 
@@ -36,17 +60,20 @@ impl Parse for Attr {
 
 /***** HELPER FUNCTIONS *****/
 /// Defines how to parse the given toplevel attributes of a struct.
-fn parse_attrs(attrs: &[Attribute], span: Span) -> Result<(Type, Expr), Error> {
-    let mut res: Option<(Span, (Type, Expr))> = None;
+///
+/// # Returns
+/// The element type, the tag and whether `no_loc` was given.
+fn parse_attrs(attrs: &[Attribute], span: Span) -> Result<(Type, Expr, bool), Error> {
+    let mut res: Option<(Span, (Type, Expr, bool))> = None;
     for attr in attrs {
         match &attr.meta {
             // First, find the `#[tag(...)]`-part to select on the tags beloning to us
             Meta::List(l) if l.path.is_ident("tag") => {
                 // Parse the inner of the list as our attribute pair
-                let Attr(ty, expr) = syn::parse2(l.tokens.clone())?;
+                let Attr(ty, expr, no_loc) = syn::parse2(l.tokens.clone())?;
                 match res {
                     Some((span, _)) => return Err(Error::new(span, "Cannot define `#[tag(...)]` twice")),
-                    None => res = Some((l.path.span(), (ty, expr))),
+                    None => res = Some((l.path.span(), (ty, expr, no_loc))),
                 }
             },
 
@@ -66,7 +93,7 @@ fn parse_attrs(attrs: &[Attribute], span: Span) -> Result<(Type, Expr), Error> {
 pub fn handle(item: TokenStream2) -> Result<TokenStream2, Error> {
     // We'll need to dive into some attributes
     let DeriveInput { attrs, ident, data, mut generics, .. } = syn::parse2(item)?;
-    let (elem, tag): (Type, Expr) = parse_attrs(&attrs, ident.span())?;
+    let (elem, tag, no_loc): (Type, Expr, bool) = parse_attrs(&attrs, ident.span())?;
 
     // Now build the impl
     match data {
@@ -74,33 +101,39 @@ pub fn handle(item: TokenStream2) -> Result<TokenStream2, Error> {
             inject_trait_bound(["ast_toolkit2", "loc", "Located"], &mut generics);
             let (impl_gen, ty_gen, where_clauses) = generics.split_for_impl();
 
-            // Find the fields that are loc'd and compute a list of "index jumps"; i.e., the number
-            // of elements to skip until one arrives at the next index.
-            let mut locs: Vec<usize> = crate::derive_located::find_loc_fields("Tag", &attrs, &s.fields)?;
-            for i in 1..locs.len() {
-                locs[i] -= locs[i - 1];
+            // Find the field that is loc'd; a tag can only carry one location
+            let locs: Vec<usize> = if no_loc {
+                Vec::new()
+            } else {
+                crate::derive_located::find_loc_fields("Tag", &attrs, &s.fields).map_err(|err| {
+                    Error::new(
+                        err.span(),
+                        format!("{err}\n\nIf the tag shouldn't store its loc, use `#[tag(..., no_loc)]` to make `Tag::with_loc()` discard it"),
+                    )
+                })?
+            };
+            if let Some(i) = locs.get(1) {
+                // SAFETY: We expect all found loc field indices to be in range
+                let field = s.fields.iter().nth(*i).unwrap();
+                let span: Span = field.attrs.iter().find(|attr| attr.path().is_ident("loc")).map(|attr| attr.span()).unwrap_or_else(|| field.span());
+                return Err(Error::new(span, "A tag can only carry one location, but more than one `#[loc]` field was selected; cannot derive `Tag`"));
             }
 
-            // Then generate an implementation overwriting each of those fields
-            let mut fields = s.fields.into_iter().enumerate();
-            let locs: Vec<TokenStream2> = locs
-                .into_iter()
-                .map(|di| {
-                    // SAFETY: We expect both all found loc fields indices to be in range, and our
-                    // difference algorithm above to work.
-                    let (i, field) = fields.nth(di.saturating_sub(1)).unwrap();
-                    if let Some(name) = field.ident {
-                        quote_spanned! { name.span() => res.#name = ::std::convert::Into::into(loc); }
-                    } else {
-                        // Avoid quote adding a `usize` suffix to the identifier by explicitly
-                        // turning it into a literal integer
-                        let i = LitInt::new(&i.to_string(), field.span());
-                        quote_spanned! { field.span() => res.#i = ::std::convert::Into::into(loc); }
-                    }
-                })
-                .collect();
+            // Then generate an implementation overwriting that field
+            let loc: Option<TokenStream2> = locs.first().map(|i| {
+                // SAFETY: We expect all found loc field indices to be in range
+                let field = s.fields.iter().nth(*i).unwrap();
+                if let Some(name) = &field.ident {
+                    quote_spanned! { name.span() => res.#name = ::std::convert::Into::into(loc); }
+                } else {
+                    // Avoid quote adding a `usize` suffix to the identifier by explicitly
+                    // turning it into a literal integer
+                    let i = LitInt::new(&i.to_string(), field.span());
+                    quote_spanned! { field.span() => res.#i = ::std::convert::Into::into(loc); }
+                }
+            });
 
-            // Then build an impl that generates the default and replaces loc fields if they are
+            // Then build an impl that generates the default and replaces the loc field if it is
             // there
             let default_impl = quote_spanned! { ident.span() => ::std::default::Default::default() };
             Ok(quote! {
@@ -113,7 +146,7 @@ pub fn handle(item: TokenStream2) -> Result<TokenStream2, Error> {
                     #[inline]
                     fn with_loc(loc: ::ast_toolkit2::loc::Loc) -> Self {
                         let mut res: Self = #default_impl;
-                        #(#locs)*
+                        #loc
                         res
                     }
                 }
@@ -124,3 +157,55 @@ pub fn handle(item: TokenStream2) -> Result<TokenStream2, Error> {
         Data::Enum(DataEnum { enum_token, .. }) => Err(Error::new(enum_token.span, "Can only derive `Tag` on structs")),
     }
 }
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attr_missing_comma() {
+        let err = syn::parse2::<Attr>(quote!(u8 b"foo")).err().unwrap();
+        assert_eq!(err.to_string(), "Expected a comma between the element type and the tag (e.g., `#[tag(u8, b\"foo\")]`)");
+    }
+
+    #[test]
+    fn test_handle_multiple_locs() {
+        let err = handle(quote! {
+            #[tag(u8, b"foo")]
+            struct Foo {
+                #[loc]
+                loc1: Loc,
+                #[loc]
+                loc2: Loc,
+            }
+        })
+        .unwrap_err();
+        assert!(err.to_string().starts_with("A tag can only carry one location"), "{err}");
+
+        let err = handle(quote! {
+            #[tag(u8, b"foo")]
+            #[loc(all)]
+            struct Foo(Loc, Loc);
+        })
+        .unwrap_err();
+        assert!(err.to_string().starts_with("A tag can only carry one location"), "{err}");
+    }
+
+    #[test]
+    fn test_handle_call() {
+        let err = handle(quote! {
+            #[tag(u8, b"foo")]
+            #[loc(call = compute_loc)]
+            struct Foo {
+                loc: Loc,
+            }
+        })
+        .unwrap_err();
+        assert!(err.to_string().starts_with("`call` is only allowed on the type, and only when deriving `Located`"), "{err}");
+    }
+}
@@ -14,13 +14,15 @@ mod derive_located;
 mod derive_node;
 #[cfg(feature = "tree")]
 mod derive_nonterm;
+#[cfg(feature = "nibble")]
+mod derive_parsable;
 #[cfg(feature = "tree")]
 mod derive_tag;
 #[cfg(feature = "tree")]
 mod derive_term;
 
 // Imports
-#[cfg(any(feature = "loc", feature = "tree"))]
+#[cfg(any(feature = "loc", feature = "nibble", feature = "tree"))]
 use proc_macro::TokenStream;
 
 
@@ -81,6 +83,41 @@ use proc_macro::TokenStream;
 /// assert_eq!(Bar { foo: Loc::encapsulate_range(0, ..2), bar: Loc::encapsulate_range(0, 2..4) }.loc(), Loc::encapsulate_range(0, ..4));
 /// ```
 ///
+/// You can choose a different way of combining them by giving a strategy at the toplevel (or at a
+/// variant, for enums). Next to `extend`, which is the default, there is `intersect` (which
+/// `Loc::intersect()`s them) and `first` (which only uses the first field):
+/// ```ignore
+/// use ast_toolkit2::loc::{Loc, Located};
+///
+/// #[derive(Located)]
+/// #[loc(strategy = first)]
+/// struct Baz {
+///     #[loc]
+///     foo: Loc,
+///     #[loc]
+///     bar: Loc,
+/// }
+///
+/// assert_eq!(Baz { foo: Loc::encapsulate_range(0, ..2), bar: Loc::encapsulate_range(0, 2..4) }.loc(), Loc::encapsulate_range(0, ..2));
+/// ```
+///
+/// Fields that are optionally there (e.g., `Option<Loc>` for synthesized nodes) can be marked
+/// with `#[loc(optional)]`. Only those that are [`Some`] will be combined, and if none of them
+/// are, an empty `Loc::new()` is returned. With `strategy = first`, this picks the first field
+/// that is there:
+/// ```ignore
+/// use ast_toolkit2::loc::{Loc, Located};
+///
+/// #[derive(Located)]
+/// struct Synth {
+///     #[loc(optional)]
+///     loc: Option<Loc>,
+/// }
+///
+/// assert_eq!(Synth { loc: Some(Loc::encapsulate(0)) }.loc(), Loc::encapsulate(0));
+/// assert_eq!(Synth { loc: None }.loc(), Loc::new());
+/// ```
+///
 /// If you want to use all fields anyway, you can also use:
 /// ```ignore
 /// use ast_toolkit2::loc::{Loc, Located};
@@ -142,6 +179,25 @@ use proc_macro::TokenStream;
 /// assert_eq!(Quux { foo: "Hello, world!".into() }.loc(), Loc::new());
 /// ```
 ///
+/// If the `Loc` cannot be read from fields (e.g., because it's computed from a list of tokens),
+/// you can also have the impl call a method (or function taking `&self`) instead:
+/// ```ignore
+/// use ast_toolkit2::loc::{Loc, Located};
+///
+/// #[derive(Located)]
+/// #[loc(call = compute_loc)]
+/// struct Quuz {
+///     tokens: Vec<Loc>,
+/// }
+/// impl Quuz {
+///     fn compute_loc(&self) -> Loc { Loc::merge_all(self.tokens.iter().copied()) }
+/// }
+///
+/// assert_eq!(Quuz { tokens: vec![Loc::encapsulate_range(0, ..2), Loc::encapsulate_range(0, 2..4)] }.loc(), Loc::encapsulate_range(0, ..4));
+/// ```
+/// This cannot be combined with any other `#[loc]`-attribute, neither at the toplevel nor at the
+/// fields or variants. It is also only allowed on the type itself, not on individual variants.
+///
 /// ## Deriving nested
 /// Note that the implementation actually makes use of the `Located::loc()`-implementation of your
 /// field. So, this is possible:
@@ -159,8 +215,26 @@ use proc_macro::TokenStream;
 /// Like most derive macros, this macro will automatically add a `Located`-bound on all generic
 /// types declared on the implemented type.
 ///
-/// Note, however, that this is _not_ the case if you declare `#loc(new)` (in which case no field
-/// is used for the impl) or if you have an empty enum.
+/// Note, however, that this is _not_ the case if you declare `#loc(new)` or `#[loc(call = ...)]`
+/// (in which case no field is used for the impl) or if you have an empty enum.
+///
+/// You can also exclude specific generics from being bound (e.g., because they only appear in a
+/// [`PhantomData`](std::marker::PhantomData)) by listing them at the toplevel:
+/// ```ignore
+/// use std::marker::PhantomData;
+///
+/// use ast_toolkit2::loc::{Loc, Located};
+///
+/// #[derive(Located)]
+/// #[loc(skip_bound(T))]
+/// struct Typed<T: ?Sized> {
+///     loc: Loc,
+///     _t:  PhantomData<T>,
+/// }
+///
+/// assert_eq!(Typed::<str> { loc: Loc::encapsulate(0), _t: PhantomData }.loc(), Loc::encapsulate(0));
+/// ```
+/// The bound is injected for the type as a whole, so `skip_bound` cannot be given on variants.
 ///
 /// If you need other generic behaviour, you should implement `Located` yourself.
 /// ```
@@ -177,8 +251,11 @@ pub fn derive_located(item: TokenStream) -> TokenStream {
 
 /// A procedural macro for automatically deriving the `Node`-trait.
 ///
-/// For now, the `Node`-trait doesn't implement anything, so this macro just generates an empty
-/// implementation.
+/// This implements `Node::kind()` and `Node::walk()`. The kind is taken from the `Term`- or
+/// `NonTerm`-derive on the same type. Without either (e.g., if you implement those traits
+/// yourself), the node is a non-terminal if any field is marked as `#[child]`, and a terminal
+/// otherwise. Non-terminals are walked by recursing into their `#[child]`-fields in order (see
+/// the `NonTerm`-derive).
 ///
 /// # Usage
 /// To use this macro, add it to your struct with the `derive`-attribute:
@@ -195,11 +272,12 @@ pub fn derive_located(item: TokenStream) -> TokenStream {
 ///
 /// ## A note on generics
 /// Note that, instead of requiring `Node` on all generics, this macro instead will require
-/// `Located` on all generics.
+/// `Located` on all generics. The types of `#[child]`-fields additionally need to implement
+/// `Node`.
 ///
 /// If you need other generic behaviour, you should implement `Node` yourself.
 #[cfg(feature = "tree")]
-#[proc_macro_derive(Node)]
+#[proc_macro_derive(Node, attributes(child))]
 pub fn derive_node(item: TokenStream) -> TokenStream {
     match derive_node::handle(item.into()) {
         Ok(res) => res.into(),
@@ -209,29 +287,38 @@ pub fn derive_node(item: TokenStream) -> TokenStream {
 
 /// A procedural macro for automatically deriving the `NonTerm`-trait.
 ///
-/// For now, the `NonTerm`-trait doesn't implement anything, so this macro just generates an empty
-/// implementation.
+/// This implements `NonTerm::children()` by returning all fields marked as `#[child]`, in the
+/// order they are declared. Because the derive can't see what types are nodes, unmarked fields are
+/// never considered children.
 ///
 /// # Usage
 /// To use this macro, add it to your struct with the `derive`-attribute:
 /// ```ignore
-/// use ast_toolkit2::tree::NonTerm;
-///
-/// #[derive(NonTerm)]
-/// struct Foo;
+/// use ast_toolkit2::loc::{Loc, Located};
+/// use ast_toolkit2::tree::{Node, NonTerm};
 ///
-/// fn assert_nonterm<T: NonTerm>(_t: T) {}
+/// #[derive(Located, Node, NonTerm)]
+/// #[loc(all)]
+/// struct Foo {
+///     #[child]
+///     lhs: Lit,
+///     #[loc(skip)]
+///     op:  String,
+///     #[child]
+///     rhs: Lit,
+/// }
 ///
-/// assert_nonterm!(Foo);
+/// assert_eq!(foo.children().len(), 2);
 /// ```
 ///
 /// ## A note on generics
 /// Note that, instead of requiring `NonTerm` on all generics, this macro instead will require
-/// `Located` on all generics.
+/// `Located` on all generics. The types of `#[child]`-fields additionally need to implement
+/// `Node`.
 ///
 /// If you need other generic behaviour, you should implement `NonTerm` yourself.
 #[cfg(feature = "tree")]
-#[proc_macro_derive(NonTerm)]
+#[proc_macro_derive(NonTerm, attributes(child))]
 pub fn derive_nonterm(item: TokenStream) -> TokenStream {
     match derive_nonterm::handle(item.into()) {
         Ok(res) => res.into(),
@@ -239,6 +326,57 @@ pub fn derive_nonterm(item: TokenStream) -> TokenStream {
     }
 }
 
+/// A procedural macro for automatically deriving the `Parsable`-trait.
+///
+/// For structs, this parses every field in declaration order, threading the input through each of
+/// them. As such, every field's type must be `Parsable` itself. The resulting behaviour is that of
+/// a tuple of the fields: if the first field is unmatched, the struct is unmatched; if any later
+/// one is, it's an error.
+///
+/// By default, the struct will be parsable over any element type that its fields are parsable
+/// over. You can fix it to a specific one by giving the attribute:
+/// ```ignore
+/// #[parsable(elem = ELEM)]
+/// ```
+///
+/// # Usage
+/// ```ignore
+/// use ast_toolkit2::nibble::{Parsable, Slice};
+/// use ast_toolkit2::nibble::whitespace::Whitespace;
+///
+/// #[derive(Parsable)]
+/// #[parsable(elem = u8)]
+/// struct Greeting {
+///     hello: Hello,
+///     ws:    Whitespace,
+///     world: World,
+/// }
+///
+/// let (greeting, rem) = Greeting::parse(Slice::new(b"Hello World")).unwrap();
+/// ```
+///
+/// ## A note on generics
+/// Instead of requiring `Parsable` on all generics, this macro will require the sequence of
+/// fields to be `Parsable`.
+///
+/// # Enums
+/// For enums, every variant is an ordered alternative. They are tried in declaration order, and
+/// the first whose fields all parse (sequentially, as with structs) wins. If a variant's first
+/// field matches but a later one doesn't, this is an error and the next variants are _not_ tried.
+///
+/// The resulting behaviour is that of nested `Either`s of the variants. Variants without fields can't be parsed, and must be excluded with:
+/// ```ignore
+/// #[parsable(skip)]
+/// ```
+#[cfg(feature = "nibble")]
+#[proc_macro_derive(Parsable, attributes(parsable))]
+pub fn derive_parsable(item: TokenStream) -> TokenStream {
+    match derive_parsable::handle(item.into()) {
+        Ok(res) => res.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
 /// A procedural macro for automatically deriving the `Term`-trait.
 ///
 /// For now, the `Term`-trait doesn't implement anything, so this macro just generates an empty
@@ -278,12 +416,20 @@ pub fn derive_term(item: TokenStream) -> TokenStream {
 /// #[tag(ELEM, TAG)]
 /// ```
 ///
+/// For the common case of UTF-8 keywords or punctuation, you can also give a string literal, which
+/// derives `Tag<u8>` with the string's bytes as TAG:
+/// ```ignore
+/// #[tag("select")]
+/// ```
+///
 /// In the implementation, `Tag::new()` will create an instance created through
-/// [`Default`](trait@Default). `Tag::with_loc()` will take that instance and then replace every
-/// field marked as `#[loc]` with the given one.
+/// [`Default`](trait@Default). `Tag::with_loc()` will take that instance and then replace the
+/// field marked as `#[loc]` with the given one. Because a tag can only carry one location, it is
+/// an error to select more than one such field.
 ///
 /// Note that the exact same `loc`-algorithm is used as for [`Located`]. See it for more
-/// information on how to mark fields as loc.
+/// information on how to mark fields as loc. If your type has no field to store the loc in, add
+/// `no_loc` to the attribute (e.g., `#[tag("select", no_loc)]`) to discard it instead.
 ///
 /// # Usage
 /// ```ignore
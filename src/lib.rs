@@ -5,6 +5,10 @@
 //!   TODO
 //
 
+// Without `std`, the `loc`- and `tree`-modules still work with just `alloc`
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+extern crate alloc;
+
 // Declare the modules
 #[cfg(feature = "init")]
 pub mod init;
@@ -27,6 +31,7 @@ pub use nibble as parse;
 /// thus useful to "import by default".
 pub mod prelude {
     #[cfg(feature = "init")]
+    #[allow(unused_imports)]
     pub use super::init::prelude::*;
     #[cfg(feature = "loc")]
     pub use super::loc::prelude::*;
@@ -45,6 +50,8 @@ pub mod prelude {
 pub mod macros {
     #[cfg(all(feature = "proc-macros", feature = "loc"))]
     pub use ast_toolkit2_proc_macros::Located;
+    #[cfg(all(feature = "proc-macros", feature = "nibble"))]
+    pub use ast_toolkit2_proc_macros::Parsable;
     #[cfg(all(feature = "proc-macros", feature = "tree"))]
     pub use ast_toolkit2_proc_macros::{Node, NonTerm, Term};
 }
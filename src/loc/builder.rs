@@ -0,0 +1,104 @@
+//  BUILDER.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines the [`LocBuilder`], which accumulates a [`Loc`] from many
+//!   children.
+//
+
+use super::{Loc, Located};
+
+
+/***** LIBRARY *****/
+/// Builds a [`Loc`] by [`Loc::extend()`]ing it incrementally with those of children.
+///
+/// This is useful when walking over the children of some node and computing its span along the
+/// way, e.g., in semantic analysis.
+///
+/// Unlike [`Loc::merge_all()`], this skips children without any location. Those are empty
+/// [`Loc::new()`]s (i.e., with no source and an empty range) and [synthetic](Loc::synthetic())
+/// Locs. Otherwise, a leading child like that would decide the source of the result, and
+/// every child after it would be ignored.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LocBuilder {
+    /// The Loc built so far, if any.
+    loc: Option<Loc>,
+}
+impl LocBuilder {
+    /// Constructor for an empty LocBuilder.
+    ///
+    /// # Returns
+    /// A new LocBuilder that would build [`Loc::new()`] if nothing is pushed to it.
+    #[inline]
+    pub const fn new() -> Self { Self { loc: None } }
+
+    /// Extends the Loc being built with that of the given item.
+    ///
+    /// # Arguments
+    /// - `item`: Some [`Located`] thing to add.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    ///
+    /// Note that, like [`Loc::extend()`], the item is ignored if it's from another source than the
+    /// first item pushed. Items without a location are always ignored.
+    #[inline]
+    pub fn push(&mut self, item: &impl Located) -> &mut Self {
+        let loc: Loc = item.loc();
+        if (loc.source.is_none() && loc.range == ()) || loc.is_synthetic() {
+            return self;
+        }
+        self.loc.get_or_insert(loc).extend(loc);
+        self
+    }
+
+    /// Returns the Loc built so far.
+    ///
+    /// # Returns
+    /// A [`Loc`] spanning all pushed items, or [`Loc::new()`] if there weren't any (with a
+    /// location).
+    #[inline]
+    pub fn build(self) -> Loc { self.loc.unwrap_or_default() }
+}
+
+// Conversion
+impl FromIterator<Loc> for LocBuilder {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = Loc>>(iter: T) -> Self {
+        let mut res = Self::new();
+        for loc in iter {
+            res.push(&loc);
+        }
+        res
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::Range;
+
+    #[test]
+    fn test_loc_builder() {
+        let mut builder = LocBuilder::new();
+        builder.push(&Loc::new()).push(&Loc::synthetic()).push(&Loc::encapsulate_range(0, 5..10)).push(&Loc::new()).push(&Loc::encapsulate_range(0, 12..15));
+        let loc = builder.build();
+        assert_eq!((loc.source, loc.range), (Some(0), Range::from(5..15)));
+
+        // Other sources are still ignored after the first real one
+        let loc = [Loc::new(), Loc::encapsulate_range(1, 0..2), Loc::encapsulate_range(0, 5..10), Loc::encapsulate_range(1, 4..6)]
+            .into_iter()
+            .collect::<LocBuilder>()
+            .build();
+        assert_eq!((loc.source, loc.range), (Some(1), Range::from(0..6)));
+
+        // Nothing real yields an empty Loc
+        let loc = [Loc::new(), Loc::synthetic()].into_iter().collect::<LocBuilder>().build();
+        assert_eq!((loc.source, loc.range), (None, Range::empty()));
+    }
+}
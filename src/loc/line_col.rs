@@ -0,0 +1,127 @@
+//  LINE COL.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements functions for translating [`Loc`]s to line/column pairs in
+//!   some source text.
+//
+
+use super::Loc;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Computes the line and column of a byte offset in some source text.
+///
+/// # Arguments
+/// - `source`: The source text to find the position in.
+/// - `offset`: The byte offset to find. Clamped to the length of `source`.
+/// - `chars`: If true, columns count UTF-8 characters instead of bytes.
+///
+/// # Returns
+/// A tuple of the 1-based line and column.
+fn pos_at(source: &str, offset: u64, chars: bool) -> (usize, usize) {
+    let bytes: &[u8] = source.as_bytes();
+    let offset: usize = usize::try_from(offset).unwrap_or(usize::MAX).min(bytes.len());
+
+    // Find the line
+    let line_start: usize = bytes[..offset].iter().rposition(|b| *b == b'\n').map(|i| i + 1).unwrap_or(0);
+    let line: usize = bytes[..line_start].iter().filter(|b| **b == b'\n').count() + 1;
+
+    // Then the column. A `\r` part of a `\r\n` does not take up a column of its own.
+    let mut head: &[u8] = &bytes[line_start..offset];
+    if head.last() == Some(&b'\r') && bytes.get(offset) == Some(&b'\n') {
+        head = &head[..head.len() - 1];
+    }
+    let col: usize = if chars { head.iter().filter(|b| (**b & 0xC0) != 0x80).count() } else { head.len() };
+    (line, col + 1)
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Computes the line and column where a [`Loc`] starts in the given source text.
+///
+/// Lines are separated by `\n`, where a `\r\n` is treated as a single newline (i.e., a `Loc`
+/// pointing to its `\r` is at the same column as one pointing to its `\n`). Columns count
+/// [`char`]s; see [`line_col_bytes()`] to count bytes instead.
+///
+/// Note that this does not check whether `loc` actually comes from `source`.
+///
+/// # Arguments
+/// - `loc`: The [`Loc`] to find the start of. Positions beyond the end of `source` are clamped
+///   to it.
+/// - `source`: The source text that `loc` points into.
+///
+/// # Returns
+/// A tuple of the 1-based line and column of [`Loc::start()`].
+#[inline]
+pub fn line_col(loc: &Loc, source: &str) -> (usize, usize) { pos_at(source, loc.start(), true) }
+
+/// Computes the line and column where a [`Loc`] starts in the given source text, counting
+/// columns in bytes.
+///
+/// Works like [`line_col()`], except that columns count bytes instead of [`char`]s.
+///
+/// # Arguments
+/// - `loc`: The [`Loc`] to find the start of. Positions beyond the end of `source` are clamped
+///   to it.
+/// - `source`: The source text that `loc` points into.
+///
+/// # Returns
+/// A tuple of the 1-based line and column of [`Loc::start()`].
+#[inline]
+pub fn line_col_bytes(loc: &Loc, source: &str) -> (usize, usize) { pos_at(source, loc.start(), false) }
+
+/// Computes the lines and columns where a [`Loc`] starts and ends in the given source text.
+///
+/// Works like [`line_col()`], but also computes the (exclusive) end of the `Loc`.
+///
+/// # Arguments
+/// - `loc`: The [`Loc`] to find the start and end of. Positions beyond the end of `source` are
+///   clamped to it, which includes [`Length::Indefinite`](super::Length::Indefinite) ends.
+/// - `source`: The source text that `loc` points into.
+///
+/// # Returns
+/// A tuple of the 1-based line and column of [`Loc::start()`], and then that of
+/// [`Loc::end_in()`].
+#[inline]
+pub fn line_col_range(loc: &Loc, source: &str) -> ((usize, usize), (usize, usize)) {
+    (pos_at(source, loc.start(), true), pos_at(source, loc.end_in(source.len() as u64), true))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "fn main() {\r\n    println!(\"héllo\");\n}\n";
+
+    #[test]
+    fn test_line_col() {
+        assert_eq!(line_col(&Loc::encapsulate_range(0, 0..2), SOURCE), (1, 1));
+        assert_eq!(line_col(&Loc::encapsulate_range(0, 3..7), SOURCE), (1, 4));
+        // The `\r` and `\n` of a `\r\n` are at the same column
+        assert_eq!(line_col(&Loc::encapsulate_range(0, 11..12), SOURCE), (1, 12));
+        assert_eq!(line_col(&Loc::encapsulate_range(0, 12..13), SOURCE), (1, 12));
+        assert_eq!(line_col(&Loc::encapsulate_range(0, 13..14), SOURCE), (2, 1));
+        // Columns after the `é` differ between chars and bytes
+        assert_eq!(line_col(&Loc::encapsulate_range(0, 30..31), SOURCE), (2, 17));
+        assert_eq!(line_col_bytes(&Loc::encapsulate_range(0, 30..31), SOURCE), (2, 18));
+        // Right after a trailing newline, and beyond the end
+        assert_eq!(line_col(&Loc::encapsulate_range(0, 39..), SOURCE), (4, 1));
+        assert_eq!(line_col(&Loc::encapsulate_range(0, 100..), SOURCE), (4, 1));
+    }
+
+    #[test]
+    fn test_line_col_range() {
+        assert_eq!(line_col_range(&Loc::encapsulate_range(0, 3..7), SOURCE), ((1, 4), (1, 8)));
+        assert_eq!(line_col_range(&Loc::encapsulate_range(0, 10..37), SOURCE), ((1, 11), (3, 1)));
+        assert_eq!(line_col_range(&Loc::encapsulate(0), SOURCE), ((1, 1), (4, 1)));
+    }
+}
@@ -39,18 +39,27 @@
 //
 
 // Modules
+mod builder;
+mod line_col;
+mod packed;
 mod range;
+#[cfg(feature = "serde")]
+pub mod range_str;
 mod spec;
 pub mod test;
 
 // Imports
-use std::cmp::Ordering;
-use std::fmt::Debug;
-use std::hash::{Hash, Hasher};
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, AddAssign, BitAnd, BitAndAssign};
 
 #[cfg(feature = "proc-macros")]
 pub use ast_toolkit2_proc_macros::Located;
-pub use range::{Length, Range};
+pub use builder::LocBuilder;
+pub use line_col::{line_col, line_col_bytes, line_col_range};
+pub use packed::{PackError, PackedLoc};
+pub use range::{InvertedRangeError, Length, ParseRangeError, Range, ShrinkError};
 pub use spec::Located;
 
 /// Shorthand for including all the traits of this crate.
@@ -78,6 +87,11 @@ pub mod prelude {
 /// # Comparing Locs
 /// Note that [`Eq`], [`Hash`] and [`PartialEq`] are all implemented for Locs but do nothing (i.e.,
 /// all Locs are reported to be the same). This to make e.g. deriving them on parent structs much easier.
+///
+/// A consequence is that Locs (or types deriving these traits) collapse into a single element
+/// when put in e.g. a `HashSet` or used as map keys. If you want to
+/// actually compare Locs, use [`Loc::same_source()`] or [`Loc::overlaps()`] instead, or wrap
+/// them in a [`TestLoc`](test::TestLoc) for strict comparisons.
 #[derive(Clone, Copy, Debug)]
 pub struct Loc {
     /// Some unique ID (e.g., a hash) of the source text this was from.
@@ -96,8 +110,17 @@ impl Default for Loc {
     fn default() -> Self { Self::new() }
 }
 impl Loc {
+    /// The [`source`](Loc::source) identifier reserved for [synthetic](Loc::synthetic()) Locs.
+    ///
+    /// Don't use this identifier for actual sources, or their Locs will be mistaken for synthetic
+    /// ones.
+    pub const SYNTHETIC_ID: u64 = u64::MAX;
+
+
     /// Creates a new Loc that points to nothing.
     ///
+    /// See [`Loc::synthetic()`] instead for Locs of e.g. generated code.
+    ///
     /// # Returns
     /// A Loc that doesn't point to source text whatsoever.
     #[inline]
@@ -139,6 +162,19 @@ impl Loc {
     /// of it.
     #[inline]
     pub fn encapsulate_range(id: u64, range: impl Into<Range>) -> Self { Self { source: Some(id), range: range.into() } }
+
+    /// Creates a new Loc for something that didn't come from any source text (e.g., code
+    /// generated by the compiler).
+    ///
+    /// Unlike [`Loc::new()`], which merely points to nothing, this explicitly marks the Loc as
+    /// synthetic. It does so by setting its [`source`](Loc::source) to the reserved
+    /// [`Loc::SYNTHETIC_ID`]. Error renderers can check for this with [`Loc::is_synthetic()`] to
+    /// show e.g. `<generated>` instead of a snippet from the source.
+    ///
+    /// # Returns
+    /// A Loc that is synthetic and spans nothing.
+    #[inline]
+    pub const fn synthetic() -> Self { Self { source: Some(Self::SYNTHETIC_ID), range: Range::empty() } }
 }
 
 // Ops
@@ -163,6 +199,7 @@ impl PartialEq for Loc {
     ///
     /// It exists to make deriving this trait on a parent struct easier and harmless.
     #[inline]
+    #[allow(clippy::partialeq_ne_impl)]
     fn ne(&self, _other: &Self) -> bool { false }
 }
 impl PartialOrd for Loc {
@@ -171,7 +208,7 @@ impl PartialOrd for Loc {
     ///
     /// It exists to make deriving this trait on a parent struct easier and harmless.
     #[inline]
-    fn partial_cmp(&self, _other: &Self) -> Option<Ordering> { Some(Ordering::Equal) }
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 }
 impl Ord for Loc {
     /// WARNING: Note that this function **always** returns [`Ordering::Equal`], as it considers
@@ -201,7 +238,7 @@ impl<'de> serde::Deserialize<'de> for Loc {
             type Value = Loc;
 
             #[inline]
-            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { write!(f, "nothing") }
+            fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result { write!(f, "nothing") }
 
             #[inline]
             fn visit_unit<E>(self) -> Result<Self::Value, E>
@@ -230,6 +267,14 @@ impl serde::Serialize for Loc {
     }
 }
 
+// Arbitrary
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Loc {
+    /// Generates a Loc with any (or no) source and any [`Range`].
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> { Ok(Self { source: u.arbitrary()?, range: u.arbitrary()? }) }
+}
+
 // Range
 impl Loc {
     /// Returns a new Loc which is a subset of this one.
@@ -307,6 +352,23 @@ impl Loc {
     #[inline]
     pub fn shrink_range(&mut self, other: impl Into<Range>) -> &mut Self { self.shrink(other.into()) }
 
+    /// Widens this Loc by some number of elements on either side.
+    ///
+    /// See [`Range::grow()`] for more information.
+    ///
+    /// # Arguments
+    /// - `left`: The number of elements to grow to the left. Clamped to start at `0`.
+    /// - `right`: The number of elements to grow to the right. Ignored if the Loc has an
+    ///   [`Length::Indefinite`] length.
+    ///
+    /// # Returns
+    /// A new Loc that is grown on both ends, with the same source.
+    #[inline]
+    pub const fn grow(mut self, left: u64, right: u64) -> Self {
+        self.range = self.range.grow(left, right);
+        self
+    }
+
 
 
     /// Returns a new Loc that is the union of this and the given Loc.
@@ -371,6 +433,161 @@ impl Loc {
     }
 
 
+    /// Returns a new Loc that is the union of all the given Locs.
+    ///
+    /// This is like repeatedly calling [`Loc::extend()`]. As such, Locs with a different
+    /// [`source`](Loc::source) than the first one are ignored.
+    ///
+    /// # Arguments
+    /// - `locs`: Some iterator over Locs to union.
+    ///
+    /// # Returns
+    /// A new Loc representing the union of all of them, or [`Loc::new()`] if there are none.
+    #[inline]
+    pub fn merge_all(locs: impl IntoIterator<Item = Self>) -> Self { locs.into_iter().collect() }
+
+
+    /// Returns a new Loc that is the intersection of this and the given Loc.
+    ///
+    /// Visually, given two ranges:
+    /// ```plain
+    ///      A  <============>
+    ///      B         <=============>
+    /// result         ^^^^^^
+    /// ```
+    ///
+    /// If you want to do this in-place, see [`Loc::intersect()`] instead.
+    ///
+    /// # Arguments
+    /// - `other`: Some other Loc to intersect with this one.
+    ///
+    /// # Returns
+    /// A new range representing the intersection of the two. See [`Range::intersect()`] for what
+    /// happens if they don't overlap.
+    ///
+    /// Note that, if `self` and `other` have differing [`source`](Loc::source)-fields, this is a
+    /// no-op! `self` is returned in that case.
+    #[inline]
+    pub const fn intersection(mut self, other: Self) -> Self {
+        // Defer to the in-place variant
+        self.intersect(other);
+        self
+    }
+
+    /// Shrinks this Loc to only the part that overlaps with another given Loc.
+    ///
+    /// Visually, given two ranges:
+    /// ```plain
+    ///      A  <============>
+    ///      B         <=============>
+    /// result         ^^^^^^
+    /// ```
+    ///
+    /// If you don't need to mutate `self`, consider [`Loc::intersection()`] instead.
+    ///
+    /// # Arguments
+    /// - `other`: Some other Loc to intersect `self` with.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    ///
+    /// Note that, if `self` and `other` have differing [`source`](Loc::source)-fields, this is a
+    /// no-op! `self` is returned in that case.
+    #[inline]
+    pub const fn intersect(&mut self, other: Self) -> &mut Self {
+        match (self.source, other.source) {
+            (Some(lhs), Some(rhs)) if lhs == rhs => {
+                self.range.intersect(other.range);
+                self
+            },
+            (None, None) => {
+                self.range.intersect(other.range);
+                self
+            },
+            _ => self,
+        }
+    }
+
+
+    /// Moves this Loc to another source, keeping its range.
+    ///
+    /// This is useful when e.g. combining ASTs parsed from different buffers into one source.
+    ///
+    /// # WARNING
+    /// This bypasses the assumption that Locs with the same [`source`](Loc::source) share the
+    /// same "namespace" of ranges! Only use this if the range is actually valid in the new
+    /// source, or else merging it with other Locs (e.g., with [`Loc::extend()`]) will give
+    /// nonsensical results.
+    ///
+    /// # Arguments
+    /// - `id`: The identifier of the new source.
+    ///
+    /// # Returns
+    /// A new Loc with the same [`range`](Loc::range) but [`source`](Loc::source) `id`.
+    #[inline]
+    pub const fn with_source(mut self, id: u64) -> Self {
+        self.source = Some(id);
+        self
+    }
+
+    /// Moves this Loc to another source computed from its current one, keeping its range.
+    ///
+    /// This is useful for remapping the sources of many Locs at once (e.g., from a source map).
+    ///
+    /// # WARNING
+    /// Like [`Loc::with_source()`], this bypasses the assumption that Locs with the same
+    /// [`source`](Loc::source) share the same "namespace" of ranges! Only use this if the range
+    /// is actually valid in the new source.
+    ///
+    /// # Arguments
+    /// - `map`: Some closure that computes the new source from the old one.
+    ///
+    /// # Returns
+    /// A new Loc with the same [`range`](Loc::range) but the [`source`](Loc::source) returned
+    /// by `map`.
+    #[inline]
+    pub fn map_source(mut self, map: impl FnOnce(Option<u64>) -> Option<u64>) -> Self {
+        self.source = map(self.source);
+        self
+    }
+
+    /// Checks whether this Loc is [synthetic](Loc::synthetic()).
+    ///
+    /// # Returns
+    /// True if this Loc's [`source`](Loc::source) is [`Loc::SYNTHETIC_ID`], or false otherwise.
+    #[inline]
+    pub const fn is_synthetic(&self) -> bool { matches!(self.source, Some(Self::SYNTHETIC_ID)) }
+
+    /// Checks whether this Loc is from the same source as another.
+    ///
+    /// Unlike [`PartialEq`], this actually compares something. Two Locs without a source are
+    /// considered to be from the same source.
+    ///
+    /// # Arguments
+    /// - `other`: Some other Loc to compare with.
+    ///
+    /// # Returns
+    /// True if both [`source`](Loc::source)-fields are equal, or false otherwise.
+    #[inline]
+    pub const fn same_source(&self, other: &Self) -> bool {
+        match (self.source, other.source) {
+            (Some(lhs), Some(rhs)) => lhs == rhs,
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Checks whether this Loc shares any elements with another.
+    ///
+    /// # Arguments
+    /// - `other`: Some other Loc to compare with.
+    ///
+    /// # Returns
+    /// True if both Locs are from the [same source](Loc::same_source()) and their
+    /// [`Range::overlaps()`], or false otherwise.
+    #[inline]
+    pub const fn overlaps(&self, other: &Self) -> bool { self.same_source(other) && self.range.overlaps(&other.range) }
+
 
     /// Returns the starting position of this Loc.
     ///
@@ -410,6 +627,32 @@ impl Loc {
     pub const fn end_in(&self, max_len: u64) -> u64 { self.range.end_in(max_len) }
 }
 
+// Ops
+impl Add for Loc {
+    type Output = Self;
+
+    /// Alias for [`Loc::join()`].
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output { self.join(rhs) }
+}
+impl AddAssign for Loc {
+    /// Alias for [`Loc::extend()`].
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) { self.extend(rhs); }
+}
+impl BitAnd for Loc {
+    type Output = Self;
+
+    /// Alias for [`Loc::intersection()`].
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output { self.intersection(rhs) }
+}
+impl BitAndAssign for Loc {
+    /// Alias for [`Loc::intersect()`].
+    #[inline]
+    fn bitand_assign(&mut self, rhs: Self) { self.intersect(rhs); }
+}
+
 // Uniformity
 impl Located for Loc {
     #[inline(always)]
@@ -428,7 +671,7 @@ impl<A: Located> FromIterator<A> for Loc {
             let loc = <A as Located>::loc(&elem);
             res.get_or_insert(loc).extend(loc);
         }
-        res.unwrap_or_else(Loc::new)
+        res.unwrap_or_default()
     }
 }
 impl From<Range> for Loc {
@@ -439,3 +682,113 @@ impl From<Loc> for Range {
     #[inline]
     fn from(value: Loc) -> Self { value.range }
 }
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "arbitrary")]
+    use crate::loc::test::{TestLoc, for_all};
+
+    #[test]
+    fn test_with_source() {
+        let loc = Loc::encapsulate_range(0, 5..10).with_source(1);
+        assert_eq!((loc.source, loc.range), (Some(1), Range::from(5..10)));
+        let loc = Loc::from(Range::from(5..)).with_source(1);
+        assert_eq!((loc.source, loc.range), (Some(1), Range::from(5..)));
+
+        let loc = Loc::encapsulate_range(0, 5..10).map_source(|id| id.map(|id| id + 42));
+        assert_eq!((loc.source, loc.range), (Some(42), Range::from(5..10)));
+        let loc = Loc::encapsulate_range(0, 5..10).map_source(|_| None);
+        assert_eq!((loc.source, loc.range), (None, Range::from(5..10)));
+    }
+
+    #[test]
+    fn test_synthetic() {
+        assert!(Loc::synthetic().is_synthetic());
+        assert!(Loc::encapsulate(Loc::SYNTHETIC_ID).is_synthetic());
+        assert!(!Loc::new().is_synthetic());
+        assert!(!Loc::encapsulate_range(0, 5..10).is_synthetic());
+        // It survives composition with itself
+        assert!(Loc::synthetic().join(Loc::synthetic()).is_synthetic());
+        assert!(Loc::synthetic().grow(0, 2).is_synthetic());
+    }
+
+    #[test]
+    fn test_same_source() {
+        assert!(Loc::encapsulate(0).same_source(&Loc::encapsulate_range(0, 5..10)));
+        assert!(Loc::new().same_source(&Loc::from(Range::from(5..10))));
+        assert!(!Loc::encapsulate(0).same_source(&Loc::encapsulate(1)));
+        assert!(!Loc::encapsulate(0).same_source(&Loc::new()));
+        assert!(!Loc::new().same_source(&Loc::encapsulate(0)));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(Loc::encapsulate_range(0, 0..5).overlaps(&Loc::encapsulate_range(0, 4..8)));
+        assert!(Loc::encapsulate_range(0, 0..5).overlaps(&Loc::encapsulate_range(0, 1..2)));
+        assert!(Loc::encapsulate_range(0, 3..).overlaps(&Loc::encapsulate_range(0, 7..8)));
+        assert!(Loc::from(Range::from(0..5)).overlaps(&Loc::from(Range::from(4..8))));
+
+        // Adjacent or disjoint ranges don't
+        assert!(!Loc::encapsulate_range(0, 0..5).overlaps(&Loc::encapsulate_range(0, 5..8)));
+        assert!(!Loc::encapsulate_range(0, 6..8).overlaps(&Loc::encapsulate_range(0, 0..5)));
+        // And neither do differing sources
+        assert!(!Loc::encapsulate_range(0, 0..5).overlaps(&Loc::encapsulate_range(1, 0..5)));
+        assert!(!Loc::encapsulate_range(0, 0..5).overlaps(&Loc::from(Range::from(0..5))));
+    }
+
+    #[test]
+    fn test_grow() {
+        let loc = Loc::encapsulate_range(1, 5..10).grow(2, 3);
+        assert_eq!((loc.source, loc.range), (Some(1), Range::from(3..13)));
+        let loc = Loc::encapsulate_range(1, 1..).grow(2, 3);
+        assert_eq!((loc.source, loc.range), (Some(1), Range::from(0..)));
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = Loc::encapsulate_range(0, 0..5);
+        let b = Loc::encapsulate_range(0, 3..8);
+        let c = Loc::encapsulate_range(1, 10..12);
+
+        let loc = a + b;
+        assert_eq!((loc.source, loc.range), (Some(0), Range::from(0..8)));
+        assert_eq!(loc.range, a.join(b).range);
+        let loc = a & b;
+        assert_eq!((loc.source, loc.range), (Some(0), Range::from(3..5)));
+        assert_eq!(loc.range, a.intersection(b).range);
+
+        // Differing sources are a no-op
+        let loc = a + c;
+        assert_eq!((loc.source, loc.range), (Some(0), Range::from(0..5)));
+        let loc = a & c;
+        assert_eq!((loc.source, loc.range), (Some(0), Range::from(0..5)));
+
+        // In-place
+        let mut loc = a;
+        loc += b;
+        loc += c;
+        assert_eq!((loc.source, loc.range), (Some(0), Range::from(0..8)));
+        loc &= b;
+        assert_eq!((loc.source, loc.range), (Some(0), Range::from(3..8)));
+    }
+
+
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_extend_laws() {
+        for_all(|(a, b): (Loc, Loc)| {
+            let b: Loc = Loc { source: a.source, ..b };
+            assert_eq!(TestLoc(a.join(b)), TestLoc(b.join(a)), "join({a:?}, {b:?}) is not commutative");
+            // Locs from other sources are ignored
+            let c: Loc = Loc { source: Some(a.source.map_or(0, |s| s.wrapping_add(1))), ..b };
+            assert_eq!(TestLoc(a.join(c)), TestLoc(a), "join({a:?}, {c:?}) did not ignore the other source");
+        });
+    }
+}
@@ -0,0 +1,214 @@
+//  PACKED.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines the [`PackedLoc`], a compact representation of a [`Loc`] for
+//!   storing many of them.
+//
+
+use core::cmp::Ordering;
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result as FResult};
+use core::hash::{Hash, Hasher};
+
+use super::{Length, Loc, Located, Range};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted when packing a [`Loc`] into a [`PackedLoc`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum PackError {
+    /// The source ID is the one reserved for encoding [`None`].
+    ReservedSource,
+    /// The start of the range does not fit in a [`u32`].
+    StartTooLarge { start: u64 },
+    /// The (fixed) length of the range does not fit in a [`u32`] (minus the reserved value).
+    LengthTooLarge { len: u64 },
+}
+impl Display for PackError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::ReservedSource => write!(f, "Source ID {} is reserved for locs without a source", PackedLoc::NO_SOURCE),
+            Self::StartTooLarge { start } => write!(f, "Start {start} does not fit in 32 bits"),
+            Self::LengthTooLarge { len } => write!(f, "Length {len} does not fit in 32 bits (minus one reserved value)"),
+        }
+    }
+}
+impl Error for PackError {}
+
+
+
+
+
+/***** LIBRARY *****/
+/// A compact representation of a [`Loc`], taking 16 bytes instead of the [`Loc`]'s 40.
+///
+/// This is useful when storing very many of them, e.g., in the nodes of a large AST. Unpacking is
+/// always possible through [`From`], but packing is only possible through [`TryFrom`] for Locs
+/// that satisfy the following limits:
+/// - The source ID must not be [`PackedLoc::NO_SOURCE`], which encodes [`None`];
+/// - The start of the range must fit in a [`u32`]; and
+/// - A [`Length::Fixed`] length must be smaller than [`u32::MAX`], which encodes
+///   [`Length::Indefinite`].
+///
+/// Like the [`Loc`], this type implements [`Eq`], [`Hash`], [`Ord`] and friends such that all
+/// PackedLocs are the same. This makes deriving them on parent structs harmless. Unpack them
+/// into [`TestLoc`](super::test::TestLoc)s to actually compare them.
+#[derive(Clone, Copy, Debug)]
+pub struct PackedLoc {
+    /// The source ID, or [`PackedLoc::NO_SOURCE`].
+    source: u64,
+    /// The start of the range.
+    start:  u32,
+    /// The length of the range, or [`u32::MAX`] for [`Length::Indefinite`].
+    len:    u32,
+}
+impl PackedLoc {
+    /// The source ID used to encode [`Loc`]s without a [`source`](Loc::source).
+    ///
+    /// Note that this is one below [`Loc::SYNTHETIC_ID`], so that synthetic Locs can still be
+    /// packed.
+    pub const NO_SOURCE: u64 = u64::MAX - 1;
+}
+
+// Ops
+impl Eq for PackedLoc {}
+impl Hash for PackedLoc {
+    /// WARNING: Note that this function does nothing, as it considers all PackedLocs to be
+    /// equivalent from an AST perspective.
+    ///
+    /// It exists to make deriving this trait on a parent struct easier and harmless.
+    #[inline]
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
+}
+impl PartialEq for PackedLoc {
+    /// WARNING: Note that this function **always** returns true, as it considers all PackedLocs to
+    /// be equivalent from an AST perspective.
+    ///
+    /// It exists to make deriving this trait on a parent struct easier and harmless.
+    #[inline]
+    fn eq(&self, _other: &Self) -> bool { true }
+    /// WARNING: Note that this function **always** returns false, as it considers all PackedLocs
+    /// to be equivalent from an AST perspective.
+    ///
+    /// It exists to make deriving this trait on a parent struct easier and harmless.
+    #[inline]
+    #[allow(clippy::partialeq_ne_impl)]
+    fn ne(&self, _other: &Self) -> bool { false }
+}
+impl PartialOrd for PackedLoc {
+    /// WARNING: Note that this function **always** returns [`Ordering::Equal`], as it considers
+    /// all PackedLocs to be equivalent from an AST perspective.
+    ///
+    /// It exists to make deriving this trait on a parent struct easier and harmless.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for PackedLoc {
+    /// WARNING: Note that this function **always** returns [`Ordering::Equal`], as it considers
+    /// all PackedLocs to be equivalent from an AST perspective.
+    ///
+    /// It exists to make deriving this trait on a parent struct easier and harmless.
+    #[inline]
+    fn cmp(&self, _other: &Self) -> Ordering { Ordering::Equal }
+}
+
+// Uniformity
+impl Located for PackedLoc {
+    /// Unpacks this PackedLoc into a [`Loc`].
+    #[inline]
+    fn loc(&self) -> Loc { (*self).into() }
+}
+
+// Conversion
+impl TryFrom<Loc> for PackedLoc {
+    type Error = PackError;
+
+    #[inline]
+    fn try_from(value: Loc) -> Result<Self, Self::Error> {
+        let source: u64 = match value.source {
+            Some(Self::NO_SOURCE) => return Err(PackError::ReservedSource),
+            Some(source) => source,
+            None => Self::NO_SOURCE,
+        };
+        let start: u32 = u32::try_from(value.range.pos).map_err(|_| PackError::StartTooLarge { start: value.range.pos })?;
+        let len: u32 = match value.range.len {
+            Length::Fixed(len) if len < u32::MAX as u64 => len as u32,
+            Length::Fixed(len) => return Err(PackError::LengthTooLarge { len }),
+            Length::Indefinite => u32::MAX,
+        };
+        Ok(Self { source, start, len })
+    }
+}
+impl From<PackedLoc> for Loc {
+    #[inline]
+    fn from(value: PackedLoc) -> Self {
+        Self {
+            source: if value.source == PackedLoc::NO_SOURCE { None } else { Some(value.source) },
+            range:  Range {
+                pos: value.start as u64,
+                len: if value.len == u32::MAX { Length::Indefinite } else { Length::Fixed(value.len as u64) },
+            },
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+
+    /// Packs and unpacks the given Loc.
+    fn round_trip(loc: Loc) -> Result<TestLoc, PackError> { PackedLoc::try_from(loc).map(|loc| TestLoc(loc.into())) }
+
+    #[test]
+    fn test_packed_loc_size() {
+        assert_eq!(core::mem::size_of::<PackedLoc>(), 16);
+        assert!(core::mem::size_of::<PackedLoc>() < core::mem::size_of::<Loc>());
+    }
+
+    #[test]
+    fn test_packed_loc() {
+        for loc in [
+            Loc::new(),
+            Loc::encapsulate(0),
+            Loc::encapsulate_range(42, 5..10),
+            Loc::encapsulate_range(42, 5..),
+            Loc::encapsulate_range(u64::from(u32::MAX), ..0),
+            Loc::from(Range::from(7..8)),
+            Loc::synthetic(),
+        ] {
+            assert_eq!(round_trip(loc), Ok(TestLoc(loc)));
+        }
+    }
+
+    #[test]
+    fn test_packed_loc_limits() {
+        let max: u64 = u32::MAX as u64;
+        assert_eq!(round_trip(Loc::encapsulate_range(0, max - 1..max)), Ok(TestLoc(Loc::encapsulate_range(0, max - 1..max))));
+        assert_eq!(round_trip(Loc::encapsulate_range(0, max..)), Ok(TestLoc(Loc::encapsulate_range(0, max..))));
+        assert_eq!(round_trip(Loc::encapsulate_range(0, max + 1..)), Err(PackError::StartTooLarge { start: max + 1 }));
+        assert_eq!(round_trip(Loc::encapsulate_range(0, ..max - 1)), Ok(TestLoc(Loc::encapsulate_range(0, ..max - 1))));
+        assert_eq!(round_trip(Loc::encapsulate_range(0, ..max)), Err(PackError::LengthTooLarge { len: max }));
+        assert_eq!(round_trip(Loc::encapsulate(PackedLoc::NO_SOURCE)), Err(PackError::ReservedSource));
+    }
+
+    #[test]
+    fn test_packed_loc_ops() {
+        let lhs: PackedLoc = PackedLoc::try_from(Loc::encapsulate_range(0, 5..10)).unwrap();
+        let rhs: PackedLoc = PackedLoc::try_from(Loc::encapsulate_range(1, ..)).unwrap();
+
+        // All PackedLocs are the same, like Locs
+        assert_eq!(lhs, rhs);
+        assert_eq!(lhs.cmp(&rhs), Ordering::Equal);
+        // But they do remember where they are
+        assert_eq!(TestLoc(lhs.loc()), TestLoc(Loc::encapsulate_range(0, 5..10)));
+        assert_eq!(TestLoc(rhs.loc()), TestLoc(Loc::encapsulate_range(1, ..)));
+    }
+}
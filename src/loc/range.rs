@@ -5,9 +5,13 @@
 //!   Implements [`Range`], an abstraction of a slice of an array.
 //
 
-use std::fmt::{Debug, Display, Formatter, Result as FResult};
-use std::hash::{Hash, Hasher};
-use std::ops;
+use alloc::string::String;
+use core::error::Error;
+use core::fmt::{Debug, Display, Formatter, Result as FResult};
+use core::hash::{Hash, Hasher};
+use core::num::{NonZeroU8, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroUsize, ParseIntError};
+use core::ops;
+use core::str::FromStr;
 
 
 /***** HELPER FUNCTIONS *****/
@@ -37,6 +41,81 @@ pub const fn max(lhs: u64, rhs: u64) -> u64 { if lhs >= rhs { lhs } else { rhs }
 
 
 
+/***** ERRORS *****/
+/// Defines the errors emitted when parsing a [`Range`] from a string.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseRangeError {
+    /// The range was neither of the form `[START]..[END]` nor of the form `[START]!`.
+    MissingSeparator { raw: String },
+    /// The start of the range was not a valid number.
+    IllegalStart { raw: String, err: ParseIntError },
+    /// The end of the range was not a valid number.
+    IllegalEnd { raw: String, err: ParseIntError },
+    /// The end of the range was before its start.
+    EndBeforeStart { start: u64, end: u64 },
+}
+impl Display for ParseRangeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::MissingSeparator { raw } => write!(f, "Expected a range of the form \"[START]..[END]\" or \"[START]!\", got {raw:?}"),
+            Self::IllegalStart { raw, .. } => write!(f, "Illegal start of range {raw:?}"),
+            Self::IllegalEnd { raw, .. } => write!(f, "Illegal end of range {raw:?}"),
+            Self::EndBeforeStart { start, end } => write!(f, "End of range {end} is before its start {start}"),
+        }
+    }
+}
+impl Error for ParseRangeError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IllegalStart { err, .. } | Self::IllegalEnd { err, .. } => Some(err),
+            Self::MissingSeparator { .. } | Self::EndBeforeStart { .. } => None,
+        }
+    }
+}
+
+
+
+/// Defines the error emitted when converting a `(start, end)`-pair to a [`Range`] fails.
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvertedRangeError {
+    /// The start of the range.
+    pub start: u64,
+    /// The end of the range, which is before `start`.
+    pub end:   u64,
+}
+impl Display for InvertedRangeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "End of range {} is before its start {}", self.end, self.start) }
+}
+impl Error for InvertedRangeError {}
+
+
+
+/// Defines the errors emitted by [`Range::try_shrink()`].
+#[derive(Debug, Eq, PartialEq)]
+pub enum ShrinkError {
+    /// The subset started beyond the end of the Range.
+    StartOutOfBounds { start: u64, len: u64 },
+    /// The subset ended beyond the end of the Range.
+    EndOutOfBounds { end: u64, len: u64 },
+}
+impl Display for ShrinkError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::StartOutOfBounds { start, len } => write!(f, "Start {start} of subset is out-of-bounds for range of length {len}"),
+            Self::EndOutOfBounds { end, len } => write!(f, "End {end} of subset is out-of-bounds for range of length {len}"),
+        }
+    }
+}
+impl Error for ShrinkError {}
+
+
+
+
+
 /***** INTERFACES *****/
 /// Things that are 100% guaranteed to be convertible to [`u64`].
 ///
@@ -71,7 +150,7 @@ impl Index for u32 {
 }
 impl Index for u64 {
     #[inline]
-    fn as_u64(&self) -> u64 { *self as u64 }
+    fn as_u64(&self) -> u64 { *self }
 }
 #[cfg(any(target_pointer_width = "16", target_pointer_width = "32", target_pointer_width = "64"))]
 impl Index for usize {
@@ -129,8 +208,34 @@ impl Index for isize {
         *self as u64
     }
 }
+impl Index for NonZeroU8 {
+    #[inline]
+    fn as_u64(&self) -> u64 { self.get().as_u64() }
+}
+impl Index for NonZeroU16 {
+    #[inline]
+    fn as_u64(&self) -> u64 { self.get().as_u64() }
+}
+impl Index for NonZeroU32 {
+    #[inline]
+    fn as_u64(&self) -> u64 { self.get().as_u64() }
+}
+impl Index for NonZeroU64 {
+    #[inline]
+    fn as_u64(&self) -> u64 { self.get().as_u64() }
+}
+#[cfg(any(target_pointer_width = "16", target_pointer_width = "32", target_pointer_width = "64"))]
+impl Index for NonZeroUsize {
+    #[inline]
+    fn as_u64(&self) -> u64 { self.get().as_u64() }
+}
 
-
+// Pointer-like impls
+impl<T: ?Sized + Index> Index for &T {
+    #[inline]
+    #[track_caller]
+    fn as_u64(&self) -> u64 { <T as Index>::as_u64(self) }
+}
 
 
 
@@ -157,6 +262,16 @@ impl<T: Index> From<T> for Length {
     fn from(value: T) -> Self { Self::Fixed(value.as_u64()) }
 }
 
+// Arbitrary
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Length {
+    /// Generates either a [`Length::Fixed`] with any length, or a [`Length::Indefinite`].
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        if u.arbitrary()? { Ok(Self::Fixed(u.arbitrary()?)) } else { Ok(Self::Indefinite) }
+    }
+}
+
 
 
 
@@ -256,7 +371,7 @@ impl Range {
     /// A Range that starts at `start` and has length [`Length::Fixed`] such that it ends on the
     /// element before `end`.
     #[inline]
-    pub const fn bounded(start: u64, end: u64) -> Self { Self { pos: 0, len: Length::Fixed(end.saturating_sub(start)) } }
+    pub const fn bounded(start: u64, end: u64) -> Self { Self { pos: start, len: Length::Fixed(end.saturating_sub(start)) } }
 
     /// Constructor for a Range that is always empty and starts on 0.
     ///
@@ -298,8 +413,8 @@ impl Debug for Range {
 
         // Otherwise, write the range with blanks where it's unbounded
         write!(f, "..")?;
-        if let Length::Fixed(len) = self.len {
-            write!(f, "{}", self.pos + len)?;
+        if let Some(end) = self.end() {
+            write!(f, "{end}")?;
         }
         Ok(())
     }
@@ -337,39 +452,55 @@ impl PartialEq for Range {
         }
     }
 }
-impl<T: Index> PartialEq<std::ops::Range<T>> for Range {
+impl<T: Index> PartialEq<ops::Range<T>> for Range {
     #[inline]
     #[track_caller]
-    fn eq(&self, other: &std::ops::Range<T>) -> bool {
+    fn eq(&self, other: &ops::Range<T>) -> bool {
         match self.len {
             Length::Fixed(len) => self.pos == other.start.as_u64() && self.pos.saturating_add(len) == other.end.as_u64(),
             Length::Indefinite => false,
         }
     }
 }
-impl<T: Index> PartialEq<std::ops::RangeFrom<T>> for Range {
+impl<T: Index> PartialEq<ops::RangeFrom<T>> for Range {
     #[inline]
     #[track_caller]
-    fn eq(&self, other: &std::ops::RangeFrom<T>) -> bool {
+    fn eq(&self, other: &ops::RangeFrom<T>) -> bool {
         match self.len {
             Length::Indefinite => self.pos == other.start.as_u64(),
             Length::Fixed(_) => false,
         }
     }
 }
-impl<T: Index> PartialEq<std::ops::RangeTo<T>> for Range {
+impl<T: Index + PartialOrd> PartialEq<ops::RangeInclusive<T>> for Range {
+    /// NOTE: This compares as if `other` was converted with [`Range::from()`], so empty inclusive
+    /// ranges are equal to empty Ranges at the same start.
     #[inline]
     #[track_caller]
-    fn eq(&self, other: &std::ops::RangeTo<T>) -> bool {
+    fn eq(&self, other: &ops::RangeInclusive<T>) -> bool {
+        let start: u64 = other.start().as_u64();
+        let len: u64 = if !other.is_empty() { other.end().as_u64().saturating_sub(start).saturating_add(1) } else { 0 };
+        *self == Self { pos: start, len: Length::Fixed(len) }
+    }
+}
+impl<T: Index> PartialEq<ops::RangeTo<T>> for Range {
+    #[inline]
+    #[track_caller]
+    fn eq(&self, other: &ops::RangeTo<T>) -> bool {
         match self.len {
             Length::Fixed(len) => self.pos == 0 && len == other.end.as_u64(),
             _ => false,
         }
     }
 }
-impl PartialEq<std::ops::RangeFull> for Range {
+impl<T: Index> PartialEq<ops::RangeToInclusive<T>> for Range {
     #[inline]
-    fn eq(&self, _other: &std::ops::RangeFull) -> bool {
+    #[track_caller]
+    fn eq(&self, other: &ops::RangeToInclusive<T>) -> bool { *self == Self { pos: 0, len: Length::Fixed(other.end.as_u64().saturating_add(1)) } }
+}
+impl PartialEq<ops::RangeFull> for Range {
+    #[inline]
+    fn eq(&self, _other: &ops::RangeFull) -> bool {
         match self.len {
             Length::Indefinite => self.pos == 0,
             _ => false,
@@ -388,12 +519,7 @@ impl<T: Index> PartialEq<T> for Range {
 }
 impl PartialEq<()> for Range {
     #[inline]
-    fn eq(&self, _other: &()) -> bool {
-        match self.len {
-            Length::Fixed(0) => true,
-            _ => false,
-        }
-    }
+    fn eq(&self, _other: &()) -> bool { matches!(self.len, Length::Fixed(0)) }
 }
 
 // Range
@@ -478,6 +604,38 @@ impl Range {
         }
     }
 
+    /// Shrinks this Range to a specific subset of itself, failing if that subset is out-of-bounds.
+    ///
+    /// Unlike [`Range::shrink()`], which clamps the subset to this Range, this reports an error
+    /// instead. This is useful for catching invalid slicing.
+    ///
+    /// # Arguments
+    /// - `range`: Some other Range that defines a subset of self. Like with [`Range::shrink()`],
+    ///   it is relative to `self.pos`.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    ///
+    /// # Errors
+    /// This function errors if this Range has a [`Length::Fixed`] length, and `other` either
+    /// starts or (if it has a [`Length::Fixed`] length too) ends beyond it. In that case, self is
+    /// left untouched.
+    #[inline]
+    pub const fn try_shrink(&mut self, other: Self) -> Result<&mut Self, ShrinkError> {
+        if let Length::Fixed(len) = self.len {
+            if other.pos > len {
+                return Err(ShrinkError::StartOutOfBounds { start: other.pos, len });
+            }
+            if let Length::Fixed(olen) = other.len {
+                let end: u64 = other.pos.saturating_add(olen);
+                if end > len {
+                    return Err(ShrinkError::EndOutOfBounds { end, len });
+                }
+            }
+        }
+        Ok(self.shrink(other))
+    }
+
     /// Convenience alias for [`Range::shrink()`] that accepts anything converting into a Range,
     /// not just the Range itself.
     ///
@@ -556,6 +714,148 @@ impl Range {
 
 
 
+    /// Returns a new Range that is the intersection of this and the given Range.
+    ///
+    /// Visually, given two ranges:
+    /// ```plain
+    ///      A  <============>
+    ///      B         <=============>
+    /// result         ^^^^^^
+    /// ```
+    ///
+    /// If the ranges don't overlap, an empty range at the start of the rightmost one is returned.
+    ///
+    /// If you want to do this in-place, see [`Range::intersect()`] instead.
+    ///
+    /// # Arguments
+    /// - `other`: Some other Range to intersect with this one.
+    ///
+    /// # Returns
+    /// A new range representing the intersection of the two.
+    #[inline]
+    pub const fn intersection(mut self, other: Self) -> Self {
+        // Defer to the in-place variant
+        self.intersect(other);
+        self
+    }
+
+    /// Shrinks this Range to only the part that overlaps with another given Range.
+    ///
+    /// Visually, given two ranges:
+    /// ```plain
+    ///      A  <============>
+    ///      B         <=============>
+    /// result         ^^^^^^
+    /// ```
+    ///
+    /// If the ranges don't overlap, this becomes an empty range at the start of the rightmost one.
+    ///
+    /// If you don't need to mutate `self`, consider [`Range::intersection()`] instead.
+    ///
+    /// # Arguments
+    /// - `other`: Some other Range to intersect `self` with.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn intersect(&mut self, other: Self) -> &mut Self {
+        let end: Option<u64> = match (self.end(), other.end()) {
+            (Some(lhs), Some(rhs)) => Some(min(lhs, rhs)),
+            (Some(end), None) | (None, Some(end)) => Some(end),
+            (None, None) => None,
+        };
+        self.pos = max(self.pos, other.pos);
+        self.len = match end {
+            Some(end) => Length::Fixed(end.saturating_sub(self.pos)),
+            None => Length::Indefinite,
+        };
+        self
+    }
+
+    /// Checks whether this Range shares any elements with another.
+    ///
+    /// Note that empty Ranges never overlap with anything, and that adjacent ranges (e.g., `0..5`
+    /// and `5..10`) don't either.
+    ///
+    /// # Arguments
+    /// - `other`: Some other Range to compare with.
+    ///
+    /// # Returns
+    /// True if there is at least one element in both `self` and `other`, or false otherwise.
+    #[inline]
+    pub const fn overlaps(&self, other: &Self) -> bool {
+        let start: u64 = max(self.pos, other.pos);
+        let end: Option<u64> = match (self.end(), other.end()) {
+            (Some(lhs), Some(rhs)) => Some(min(lhs, rhs)),
+            (Some(end), None) | (None, Some(end)) => Some(end),
+            (None, None) => None,
+        };
+        match end {
+            Some(end) => start < end,
+            None => true,
+        }
+    }
+
+    /// Widens this Range such that both its ends are aligned to multiples of some stride.
+    ///
+    /// Visually, given a stride of `4`:
+    /// ```plain
+    /// stride |   |   |   |
+    ///      A    <==>
+    /// result <======>
+    /// ```
+    ///
+    /// This is useful when reconciling element ranges with fixed-width records.
+    ///
+    /// # Arguments
+    /// - `stride`: The stride to align to. If it's `0`, then the Range is returned as-is.
+    ///
+    /// # Returns
+    /// A new Range that starts at `self`'s start rounded down, and ends at `self`'s end rounded
+    /// up, to a multiple of `stride`. [`Length::Indefinite`] stays indefinite.
+    #[inline]
+    pub const fn align_to(self, stride: u64) -> Self {
+        if stride == 0 {
+            return self;
+        }
+        let pos: u64 = self.pos - self.pos % stride;
+        let len: Length = match self.end() {
+            Some(end) => {
+                let end: u64 = match end.div_ceil(stride).checked_mul(stride) {
+                    Some(end) => end,
+                    None => u64::MAX,
+                };
+                Length::Fixed(end - pos)
+            },
+            None => Length::Indefinite,
+        };
+        Self { pos, len }
+    }
+
+    /// Widens this Range by some number of elements on either side.
+    ///
+    /// This is the inverse of [`Range::shrink()`], and useful for e.g. including surrounding
+    /// delimiters or whitespace in a Range.
+    ///
+    /// # Arguments
+    /// - `left`: The number of elements to grow to the left. If this would move the Range before
+    ///   `0`, then it's clamped to start at `0` instead (keeping its end where it is).
+    /// - `right`: The number of elements to grow to the right. Ignored if [`Range::len`] is
+    ///   [`Length::Indefinite`].
+    ///
+    /// # Returns
+    /// A new Range that is grown on both ends.
+    #[inline]
+    pub const fn grow(self, left: u64, right: u64) -> Self {
+        let left: u64 = min(left, self.pos);
+        let len: Length = match self.len {
+            Length::Fixed(len) => Length::Fixed(len.saturating_add(left).saturating_add(right)),
+            Length::Indefinite => Length::Indefinite,
+        };
+        Self { pos: self.pos - left, len }
+    }
+
+
     /// Returns the starting position of this Range.
     ///
     /// Simply equal to [`Range::pos`].
@@ -602,6 +902,49 @@ impl Range {
             Length::Indefinite => max_len,
         }
     }
+
+    /// Translates an index in the sequence to an offset within this Range.
+    ///
+    /// This is the inverse of [`Range::to_absolute()`].
+    ///
+    /// # Arguments
+    /// - `seq_idx`: Some zero-indexed index in the sequence as a whole.
+    ///
+    /// # Returns
+    /// The offset of `seq_idx` from [`Range::pos`], or [`None`] if it's not part of this Range.
+    #[inline]
+    pub const fn to_relative(&self, seq_idx: u64) -> Option<u64> {
+        if seq_idx < self.pos {
+            return None;
+        }
+        match self.end() {
+            Some(end) if seq_idx >= end => None,
+            _ => Some(seq_idx - self.pos),
+        }
+    }
+
+    /// Translates an offset within this Range to an index in the sequence.
+    ///
+    /// This is the inverse of [`Range::to_relative()`]. Note that it does not check whether the
+    /// result is still part of this Range.
+    ///
+    /// # Arguments
+    /// - `rel_idx`: Some offset from [`Range::pos`].
+    ///
+    /// # Returns
+    /// The zero-indexed index in the sequence as a whole. Saturates at [`u64::MAX`].
+    #[inline]
+    pub const fn to_absolute(&self, rel_idx: u64) -> u64 { self.pos.saturating_add(rel_idx) }
+}
+
+// Arbitrary
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Range {
+    /// Generates a Range with any position and any [`Length`].
+    ///
+    /// Note that this includes ranges that run past [`u64::MAX`], which are saturated.
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> { Ok(Self { pos: u.arbitrary()?, len: u.arbitrary()? }) }
 }
 
 // Conversion
@@ -615,13 +958,15 @@ impl<T: Index> From<ops::Range<T>> for Range {
     }
 }
 impl<T: Index + PartialOrd> From<ops::RangeInclusive<T>> for Range {
+    /// NOTE: A Range cannot end beyond [`u64::MAX`], so ranges that include it lose their last
+    /// element (i.e., `0..=u64::MAX` becomes `0..u64::MAX`).
     #[inline]
     #[track_caller]
     fn from(value: ops::RangeInclusive<T>) -> Self {
         let start: u64 = value.start().as_u64();
         if !value.is_empty() {
             let end: u64 = value.end().as_u64();
-            Self { pos: start, len: Length::Fixed(1 + end.saturating_sub(start)) }
+            Self { pos: start, len: Length::Fixed(end.saturating_sub(start).saturating_add(1)) }
         } else {
             Self { pos: start, len: Length::Fixed(0) }
         }
@@ -633,10 +978,12 @@ impl<T: Index> From<ops::RangeTo<T>> for Range {
     fn from(value: ops::RangeTo<T>) -> Self { Self { pos: 0, len: Length::Fixed(value.end.as_u64()) } }
 }
 impl<T: Index + PartialOrd> From<ops::RangeToInclusive<T>> for Range {
-    /// NOTE: We are assuming that [`ops::RangeToInclusive]` cannot represent empty ranges.
+    /// NOTE: We are assuming that [`ops::RangeToInclusive`] cannot represent empty ranges.
+    /// Further, a Range cannot end beyond [`u64::MAX`], so `..=u64::MAX` loses its last element
+    /// (i.e., it becomes `..u64::MAX`).
     #[inline]
     #[track_caller]
-    fn from(value: ops::RangeToInclusive<T>) -> Self { Self { pos: 0, len: Length::Fixed(1 + value.end.as_u64()) } }
+    fn from(value: ops::RangeToInclusive<T>) -> Self { Self { pos: 0, len: Length::Fixed(value.end.as_u64().saturating_add(1)) } }
 }
 impl<T: Index> From<ops::RangeFrom<T>> for Range {
     #[inline]
@@ -651,6 +998,55 @@ impl From<()> for Range {
     #[inline]
     fn from(_value: ()) -> Self { Self { pos: 0, len: Length::Fixed(0) } }
 }
+impl From<(u64, u64)> for Range {
+    /// Interprets the pair as `start..end`, like [`Range::bounded()`].
+    #[inline]
+    fn from(value: (u64, u64)) -> Self { Self::bounded(value.0, value.1) }
+}
+impl TryFrom<(u64, Option<u64>)> for Range {
+    type Error = InvertedRangeError;
+
+    /// Interprets the pair as `start..end`, or `start..` if there is no end.
+    ///
+    /// Unlike `From<(u64, u64)>`, this fails if `end` is before `start`.
+    #[inline]
+    fn try_from(value: (u64, Option<u64>)) -> Result<Self, Self::Error> {
+        match value {
+            (start, Some(end)) if end < start => Err(InvertedRangeError { start, end }),
+            (start, Some(end)) => Ok(Self::bounded(start, end)),
+            (start, None) => Ok(Self::onwards(start)),
+        }
+    }
+}
+impl FromStr for Range {
+    type Err = ParseRangeError;
+
+    /// Parses a Range from the same syntax as it is [`Display`]ed in.
+    ///
+    /// Specifically, this accepts `START..END`, `START..`, `..END`, `..` and `START!` (an empty
+    /// range at `START`), where `START` may be omitted if it is `0`.
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Parses the start of the range, if any
+        let parse_start = |raw: &str| -> Result<u64, ParseRangeError> {
+            if raw.is_empty() { Ok(0) } else { raw.parse().map_err(|err| ParseRangeError::IllegalStart { raw: raw.into(), err }) }
+        };
+
+        if let Some(start) = s.strip_suffix('!') {
+            return Ok(Self::empty_at(parse_start(start)?));
+        }
+        let Some((start, end)) = s.split_once("..") else { return Err(ParseRangeError::MissingSeparator { raw: s.into() }) };
+        let start: u64 = parse_start(start)?;
+        if end.is_empty() {
+            return Ok(Self::onwards(start));
+        }
+        let end: u64 = end.parse().map_err(|err| ParseRangeError::IllegalEnd { raw: end.into(), err })?;
+        if end < start {
+            return Err(ParseRangeError::EndBeforeStart { start, end });
+        }
+        Ok(Self { pos: start, len: Length::Fixed(end - start) })
+    }
+}
 
 
 
@@ -660,8 +1056,11 @@ impl From<()> for Range {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "arbitrary")]
+    use crate::loc::test::for_all;
 
     #[test]
+    #[allow(clippy::reversed_empty_ranges)]
     fn test_slice() {
         // Some testcases; extend when more are known!
         assert_eq!(Range::from(0..10).slice_range(0..5), 0..5);
@@ -686,4 +1085,253 @@ mod tests {
 
         assert_eq!(Range::from(2..).slice_range(..1), 2..3);
     }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_slice_inclusive() {
+        // Like `test_slice()`, but comparing with inclusive bounds
+        assert_eq!(Range::from(0..10).slice_range(0..5), 0..=4);
+        assert_eq!(Range::from(..10).slice_range(..5), ..=4);
+        assert_eq!(Range::from(5..10).slice_range(..5), 5..=9);
+        assert_eq!(Range::from(10..5), 10..=9);
+        assert_eq!(Range::from(5..).slice_range(..5), 5..=9);
+        assert_eq!(Range::from(..).slice_range(5..10), 5..=9);
+        assert_eq!(Range::from(1..).slice_range(1..3), 2..=3);
+        assert_eq!(Range::from(1..4).slice_range(1..2), 2..=2);
+        assert_eq!(Range::from(1..4).slice_range(..2), 1..=2);
+        assert_eq!(Range::from(2..).slice_range(..1), 2..=2);
+
+        // Inequalities
+        assert_ne!(Range::from(2..6), 2..=6);
+        assert_ne!(Range::from(2..), 2..=5);
+        assert_ne!(Range::from(1..6), ..=5);
+        assert_eq!(Range::from(..6), ..=5);
+        assert_eq!(Range::from(0..u64::MAX), ..=u64::MAX);
+    }
+
+    #[test]
+    fn test_try_shrink() {
+        let mut range = Range::from(5..10);
+        assert_eq!(range.try_shrink(Range::from(1..3)).map(|r| *r), Ok(Range::from(6..8)));
+        let mut range = Range::from(5..10);
+        assert_eq!(range.try_shrink(Range::from(5..)).map(|r| *r), Ok(Range::empty_at(10)));
+        let mut range = Range::from(5..);
+        assert_eq!(range.try_shrink(Range::from(10..20)).map(|r| *r), Ok(Range::from(15..25)));
+
+        // Out-of-bounds leaves the Range untouched
+        let mut range = Range::from(5..10);
+        assert_eq!(range.try_shrink(Range::from(6..)), Err(ShrinkError::StartOutOfBounds { start: 6, len: 5 }));
+        assert_eq!(range.try_shrink(Range::from(3..7)), Err(ShrinkError::EndOutOfBounds { end: 7, len: 5 }));
+        assert_eq!(range, 5..10);
+    }
+
+    #[test]
+    fn test_intersection() {
+        assert_eq!(Range::from(0..10).intersection(Range::from(5..15)), 5..10);
+        assert_eq!(Range::from(5..15).intersection(Range::from(0..10)), 5..10);
+        assert_eq!(Range::from(0..10).intersection(Range::from(2..4)), 2..4);
+        assert_eq!(Range::from(0..10).intersection(Range::from(5..)), 5..10);
+        assert_eq!(Range::from(3..).intersection(Range::from(5..)), 5..);
+        assert_eq!(Range::from(..).intersection(Range::from(5..8)), 5..8);
+
+        // Disjoint ranges are empty
+        let res = Range::from(0..2).intersection(Range::from(5..8));
+        assert_eq!(res.pos, 5);
+        assert!(matches!(res.len, Length::Fixed(0)));
+    }
+
+    #[test]
+    fn test_join_laws() {
+        let max: u64 = u64::MAX;
+        let ranges: [Range; 10] = [
+            Range::from(0..0),
+            Range::from(5..10),
+            Range::from(8..20),
+            Range::from(5..),
+            Range::from(..),
+            Range { pos: max - 1, len: Length::Fixed(5) },
+            Range { pos: 3, len: Length::Fixed(max) },
+            Range { pos: max, len: Length::Fixed(0) },
+            Range::from(max - 1..max),
+            Range::from(max..),
+        ];
+        for a in ranges {
+            for b in ranges {
+                assert_eq!(a.join(b), b.join(a), "join({a:?}, {b:?}) is not commutative");
+                for c in ranges {
+                    assert_eq!(a.join(b).join(c), a.join(b.join(c)), "join({a:?}, {b:?}, {c:?}) is not associative");
+                }
+            }
+        }
+
+        // Saturated ranges are the same as ranges ending at the maximum, but not as indefinite ones
+        assert_eq!(Range { pos: max - 1, len: Length::Fixed(5) }.join(Range::from(0..1)), 0..max);
+        assert_eq!(Range { pos: 3, len: Length::Fixed(max) }.join(Range::from(5..)), 3..);
+        assert_eq!(format!("{:?}", Range { pos: max - 1, len: Length::Fixed(5) }), format!("{}..{max}", max - 1));
+    }
+
+    #[test]
+    fn test_from_str() {
+        // Everything that is displayed should be parsed back
+        for raw in ["5..10", "5..", "..10", "5!", "!", ".."] {
+            assert_eq!(raw.parse::<Range>().unwrap().to_string(), raw);
+        }
+        assert_eq!("5..10".parse::<Range>(), Ok(Range::from(5..10)));
+        assert_eq!("0..10".parse::<Range>(), Ok(Range::from(..10)));
+        assert_eq!("5!".parse::<Range>().map(|r| (r.pos, r.end())), Ok((5, Some(5))));
+
+        // Malformed ranges are rejected
+        assert!(matches!("5..3..".parse::<Range>(), Err(ParseRangeError::IllegalEnd { raw, .. }) if raw == "3.."));
+        assert!(matches!("a..3".parse::<Range>(), Err(ParseRangeError::IllegalStart { raw, .. }) if raw == "a"));
+        assert!(matches!("-1!".parse::<Range>(), Err(ParseRangeError::IllegalStart { .. })));
+        assert_eq!("5".parse::<Range>(), Err(ParseRangeError::MissingSeparator { raw: "5".into() }));
+        assert_eq!("5..3".parse::<Range>(), Err(ParseRangeError::EndBeforeStart { start: 5, end: 3 }));
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_from_inclusive() {
+        assert_eq!(Range::from(3..=7), 3..8);
+        assert_eq!(Range::from(7..=3), Range::empty_at(7));
+        assert_eq!(Range::from(..=7), ..8);
+        // Ranges including `u64::MAX` saturate
+        assert_eq!(Range::from(0..=u64::MAX), 0..u64::MAX);
+        assert_eq!(Range::from(5..=u64::MAX), 5..u64::MAX);
+        assert_eq!(Range::from(u64::MAX..=u64::MAX), Range::empty_at(u64::MAX));
+        assert_eq!(Range::from(..=u64::MAX), ..u64::MAX);
+        assert_eq!(Range::from(..=u64::MAX - 1), ..u64::MAX);
+    }
+
+    #[test]
+    fn test_bounded() {
+        assert_eq!(Range::bounded(5, 10), 5..10);
+        // Degenerate ranges are empty at their start
+        let range = Range::bounded(10, 5);
+        assert_eq!((range.start(), range.end()), (10, Some(10)));
+    }
+
+    #[test]
+    fn test_from_pair() {
+        assert_eq!(Range::bounded(3, 7).start(), 3);
+        assert_eq!(Range::from((3u64, 7u64)), 3..7);
+        assert_eq!(Range::from((7u64, 3u64)), Range::empty_at(7));
+        assert_eq!(Range::try_from((3u64, Some(7u64))), Ok(Range::from(3..7)));
+        assert_eq!(Range::try_from((3u64, None)), Ok(Range::from(3..)));
+        assert_eq!(Range::try_from((7u64, Some(3u64))), Err(InvertedRangeError { start: 7, end: 3 }));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(Range::from(0..5).overlaps(&Range::from(4..8)));
+        assert!(Range::from(4..8).overlaps(&Range::from(0..5)));
+        assert!(Range::from(0..10).overlaps(&Range::from(2..4)));
+        assert!(Range::from(5..).overlaps(&Range::from(..6)));
+        assert!(Range::from(..).overlaps(&Range::from(100..)));
+        assert!(!Range::from(0..5).overlaps(&Range::from(5..10)));
+        assert!(!Range::from(0..5).overlaps(&Range::from(7..)));
+        assert!(!Range::empty_at(3).overlaps(&Range::from(0..5)));
+    }
+
+    #[test]
+    fn test_align_to() {
+        assert_eq!(Range::from(3..7).align_to(4), 0..8);
+        assert_eq!(Range::from(4..8).align_to(4), 4..8);
+        assert_eq!(Range::from(5..6).align_to(4), 4..8);
+        assert_eq!(Range::from(5..).align_to(4), 4..);
+        assert_eq!(Range::from(3..7).align_to(1), 3..7);
+        assert_eq!(Range::from(3..7).align_to(0), 3..7);
+        assert_eq!(Range::from(9..u64::MAX).align_to(8), 8..u64::MAX);
+
+        // Empty ranges may become non-empty
+        let res = Range::empty_at(5).align_to(4);
+        assert_eq!((res.pos, res.end()), (4, Some(8)));
+        let res = Range::empty_at(4).align_to(4);
+        assert_eq!((res.pos, res.end()), (4, Some(4)));
+    }
+
+    #[test]
+    fn test_grow() {
+        assert_eq!(Range::from(5..10).grow(2, 3), 3..13);
+        assert_eq!(Range::from(5..10).grow(0, 0), 5..10);
+        // Growing past 0 keeps the right edge
+        assert_eq!(Range::from(1..4).grow(5, 1), 0..5);
+        // Indefinite ranges only move their start
+        assert_eq!(Range::from(5..).grow(2, 3), 3..);
+        assert_eq!(Range::full().grow(2, 3), ..);
+    }
+
+    #[test]
+    fn test_to_relative() {
+        let range = Range::from(5..10);
+        assert_eq!(range.to_relative(7), Some(2));
+        assert_eq!(range.to_relative(5), Some(0));
+        assert_eq!(range.to_relative(9), Some(4));
+        assert_eq!(range.to_relative(10), None);
+        assert_eq!(range.to_relative(4), None);
+        assert_eq!(Range::from(5..).to_relative(u64::MAX), Some(u64::MAX - 5));
+        assert_eq!(Range::empty_at(5).to_relative(5), None);
+
+        assert_eq!(range.to_absolute(2), 7);
+        assert_eq!(range.to_absolute(0), 5);
+        assert_eq!(range.to_absolute(10), 15);
+        assert_eq!(range.to_absolute(u64::MAX), u64::MAX);
+        assert_eq!(range.to_relative(range.to_absolute(3)), Some(3));
+    }
+
+    #[test]
+    fn test_index() {
+        // Non-zero types should be usable as-is
+        assert_eq!(Range::new(NonZeroU32::new(5).unwrap(), 3u32), 5..8);
+        assert_eq!(Range::new(NonZeroU8::new(1).unwrap(), NonZeroU64::new(2).unwrap()), 1..3);
+        assert_eq!(Range::new(NonZeroUsize::new(7).unwrap(), Length::Indefinite), 7..);
+
+        // References should be transparent
+        let starts: [u32; 2] = [5, 6];
+        let lens: [usize; 2] = [3, 4];
+        for (start, len) in starts.iter().zip(lens.iter()) {
+            assert_eq!(Range::new(start, len), *start..*start + *len as u32);
+        }
+        assert_eq!(Range::new(5, 1), &5u8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_negative_ref() { <&i32 as Index>::as_u64(&&-1); }
+
+
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_extend_laws() {
+        for_all(|(a, b): (Range, Range)| {
+            assert_eq!(a.join(b), b.join(a), "join({a:?}, {b:?}) is not commutative");
+            // Joining with either part again changes nothing
+            assert_eq!(a.join(b).join(a), a.join(b), "join({a:?}, {b:?}) does not absorb {a:?}");
+            assert_eq!(a.join(b).join(b), a.join(b), "join({a:?}, {b:?}) does not absorb {b:?}");
+        });
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_intersect_laws() {
+        for_all(|(a, b): (Range, Range)| {
+            assert_eq!(a.intersection(b), b.intersection(a), "intersection({a:?}, {b:?}) is not commutative");
+            assert_eq!(a.intersection(a), a, "intersection({a:?}, {a:?}) is not idempotent");
+        });
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_shrink_laws() {
+        for_all(|(a, b): (Range, Range)| {
+            let res: Range = a.slice(b);
+            assert!(res.pos >= a.pos, "slice({a:?}, {b:?}) = {res:?} starts before the original");
+            if let Length::Fixed(len) = a.len {
+                // Shrinking never grows a fixed range, nor makes it indefinite
+                let Length::Fixed(res_len) = res.len else { panic!("slice({a:?}, {b:?}) = {res:?} is indefinite") };
+                assert!(res_len <= len, "slice({a:?}, {b:?}) = {res:?} is longer than the original");
+                assert!(res.end() <= a.end(), "slice({a:?}, {b:?}) = {res:?} ends after the original");
+            }
+        });
+    }
 }
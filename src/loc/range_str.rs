@@ -0,0 +1,114 @@
+//  RANGE STR.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements (de)serializing [`Range`]s in their compact string form
+//!   (e.g., `"5..10"`) instead of as a struct.
+//!
+//!   Use it by annotating a field with
+//!   `#[serde(with = "ast_toolkit2::loc::range_str")]`.
+//
+
+use core::fmt::{Formatter, Result as FResult};
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+
+use super::Range;
+
+
+/***** HELPERS *****/
+/// [`Visitor`] for [`deserialize()`].
+struct RangeVisitor;
+impl<'de> Visitor<'de> for RangeVisitor {
+    type Value = Range;
+
+    #[inline]
+    fn expecting(&self, f: &mut Formatter) -> FResult { write!(f, "a range of the form \"[START]..[END]\" or \"[START]!\"") }
+
+    #[inline]
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map_err(E::custom)
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Serializes a [`Range`] as its [`Display`](core::fmt::Display) string, e.g., `"5..10"`, `"5.."`
+/// or `"5!"`.
+///
+/// # Arguments
+/// - `range`: The [`Range`] to serialize.
+/// - `serializer`: The [`Serializer`] to serialize to.
+///
+/// # Errors
+/// This function errors if the `serializer` does.
+#[inline]
+pub fn serialize<S>(range: &Range, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(range)
+}
+
+/// Deserializes a [`Range`] from its string form, as parsed by its [`FromStr`](core::str::FromStr)
+/// impl.
+///
+/// # Arguments
+/// - `deserializer`: The [`Deserializer`] to deserialize from.
+///
+/// # Returns
+/// The deserialized [`Range`].
+///
+/// # Errors
+/// This function errors if the `deserializer` does, or if the string is not a valid range.
+#[inline]
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Range, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_str(RangeVisitor)
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::loc::Length;
+
+    #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+    struct Config {
+        #[serde(with = "crate::loc::range_str")]
+        range: Range,
+    }
+
+    #[test]
+    fn test_range_str() {
+        for (range, raw) in [
+            (Range::from(5..10), r#"{"range":"5..10"}"#),
+            (Range::from(5..), r#"{"range":"5.."}"#),
+            (Range::from(..10), r#"{"range":"..10"}"#),
+            (Range::from(..), r#"{"range":".."}"#),
+            (Range { pos: 5, len: Length::Fixed(0) }, r#"{"range":"5!"}"#),
+        ] {
+            assert_eq!(serde_json::to_string(&Config { range }).unwrap(), raw);
+            assert_eq!(serde_json::from_str::<Config>(raw).unwrap(), Config { range });
+        }
+
+        // Invalid ranges are reported
+        assert!(serde_json::from_str::<Config>(r#"{"range":"10..5"}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"range":{"pos":5,"len":"Indefinite"}}"#).is_err());
+    }
+}
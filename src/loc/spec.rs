@@ -5,14 +5,19 @@
 //!   Defines auxillary interfaces useful with [`Loc`]s.
 //
 
-use std::cell::{Ref, RefMut};
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::{Ref, RefMut};
+use core::convert::Infallible;
+use core::hint::unreachable_unchecked;
+#[cfg(feature = "std")]
 use std::collections::{HashMap, HashSet};
-use std::convert::Infallible;
-use std::hint::unreachable_unchecked;
-use std::rc::Rc;
-use std::sync::{Arc, MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+#[cfg(feature = "std")]
+use std::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
 
-use super::Loc;
+use super::{Loc, LocBuilder};
 
 
 /***** HELPER MACROS *****/
@@ -25,14 +30,17 @@ macro_rules! located_tuple_impl {
     };
     (__impl($fty:ident $(, $rty:ident)*)) => {
         impl<$fty: Located $(,$rty: Located)*> Located for ($fty, $($rty,)*) {
+            /// Returns the union of the [`Loc`]s of all elements, as built by a [`LocBuilder`].
+            ///
+            /// This means that elements without a location (e.g., [`None`]s) are skipped, and that
+            /// elements from another source than the first are ignored.
             #[inline]
             fn loc(&self) -> Loc {
                 #[allow(non_snake_case)]
                 let ($fty, $($rty,)*) = self;
-                #[allow(unused_mut)]
-                let mut res: Loc = <$fty as Located>::loc($fty);
-                $(res.extend(<$rty as Located>::loc($rty));)*
-                res
+                let mut res: LocBuilder = LocBuilder::new();
+                res.push($fty)$(.push($rty))*;
+                res.build()
             }
         }
     };
@@ -51,20 +59,20 @@ macro_rules! located_collection_impl {
             ///
             /// If there are none, then [`Loc::new()`] is returned.
             #[inline]
-            fn loc(&self) -> Loc { self.into_iter().map(Located::loc).collect() }
+            fn loc(&self) -> Loc { Loc::merge_all(self.into_iter().map(Located::loc)) }
         }
     };
 }
 
 macro_rules! located_ptr_impl {
     ('a, $ty:ty) => {
-        impl<'a, T: Located> Located for $ty {
+        impl<'a, T: ?Sized + Located> Located for $ty {
             #[inline(always)]
             fn loc(&self) -> Loc { <T as Located>::loc(self) }
         }
     };
     ($ty:ty) => {
-        impl<T: Located> Located for $ty {
+        impl<T: ?Sized + Located> Located for $ty {
             #[inline(always)]
             fn loc(&self) -> Loc { <T as Located>::loc(self) }
         }
@@ -115,7 +123,7 @@ impl<T: Located> Located for Option<T> {
     /// Returns [`Located::loc()`] of the internal element if it's [`Some`], else returns
     /// [`Loc::new()`].
     #[inline(always)]
-    fn loc(&self) -> Loc { self.as_ref().map(Located::loc).unwrap_or_else(Loc::new) }
+    fn loc(&self) -> Loc { self.as_ref().map(Located::loc).unwrap_or_default() }
 }
 located_collection_impl!([T]);
 impl<const LEN: usize, T: Located> Located for [T; LEN] {
@@ -129,7 +137,9 @@ impl<const LEN: usize, T: Located> Located for [T; LEN] {
     fn loc(&self) -> Loc { <[T] as Located>::loc(self.as_slice()) }
 }
 located_collection_impl!(Vec<T>);
+#[cfg(feature = "std")]
 located_collection_impl!(HashSet<T>);
+#[cfg(feature = "std")]
 impl<K, V: Located> Located for HashMap<K, V> {
     /// Iterates over the values in this type to create one [`Loc`] [`Loc::extend()`]ed
     /// over all of the elements.
@@ -142,7 +152,7 @@ impl<K, V: Located> Located for HashMap<K, V> {
             let loc = <V as Located>::loc(elem);
             res.get_or_insert(loc).extend(loc);
         }
-        res.unwrap_or_else(Loc::new)
+        res.unwrap_or_default()
     }
 }
 
@@ -157,6 +167,69 @@ located_ptr_impl!(Rc<T>);
 located_ptr_impl!(Arc<T>);
 located_ptr_impl!('a, Ref<'a, T>);
 located_ptr_impl!('a, RefMut<'a, T>);
+#[cfg(feature = "std")]
 located_ptr_impl!('a, RwLockReadGuard<'a, T>);
+#[cfg(feature = "std")]
 located_ptr_impl!('a, RwLockWriteGuard<'a, T>);
+#[cfg(feature = "std")]
 located_ptr_impl!('a, MutexGuard<'a, T>);
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+
+    #[test]
+    fn test_located_option() {
+        assert_eq!(TestLoc(Some(Loc::encapsulate(0)).loc()), TestLoc(Loc::encapsulate(0)));
+        assert_eq!(TestLoc(None::<Loc>.loc()), TestLoc(Loc::new()));
+    }
+
+    #[test]
+    fn test_located_collections() {
+        let locs: Vec<Loc> = vec![Loc::encapsulate_range(0, ..2), Loc::encapsulate_range(0, 2..4), Loc::encapsulate_range(0, 4..6)];
+        assert_eq!(TestLoc(locs.loc()), TestLoc(Loc::encapsulate_range(0, ..6)));
+        assert_eq!(TestLoc(locs.as_slice().loc()), TestLoc(Loc::encapsulate_range(0, ..6)));
+        assert_eq!(TestLoc(Vec::<Loc>::new().loc()), TestLoc(Loc::new()));
+        assert_eq!(TestLoc(<[Loc] as Located>::loc(&[])), TestLoc(Loc::new()));
+
+        // Other sources are ignored
+        assert_eq!(TestLoc(Loc::merge_all([Loc::encapsulate_range(0, ..2), Loc::encapsulate_range(1, 4..6)])), TestLoc(Loc::encapsulate_range(0, ..2)));
+    }
+
+    #[test]
+    fn test_located_tuple() {
+        let a: Loc = Loc::encapsulate_range(0, 2..4);
+        let b: Loc = Loc::encapsulate_range(0, 6..8);
+        assert_eq!(TestLoc((a, b).loc()), TestLoc(a.join(b)));
+        assert_eq!(TestLoc((b, a).loc()), TestLoc(a.join(b)));
+        assert_eq!(TestLoc((a, Loc::encapsulate_range(0, ..1), b, a).loc()), TestLoc(Loc::encapsulate_range(0, ..8)));
+        assert_eq!(TestLoc((a,).loc()), TestLoc(a));
+
+        // Elements without a location are skipped...
+        assert_eq!(TestLoc((None::<Loc>, a, Loc::synthetic(), b).loc()), TestLoc(a.join(b)));
+        assert_eq!(TestLoc((None::<Loc>, Loc::new()).loc()), TestLoc(Loc::new()));
+        // ...and those from other sources ignored
+        assert_eq!(TestLoc((a, Loc::encapsulate_range(1, 6..8)).loc()), TestLoc(a));
+    }
+
+    #[test]
+    fn test_located_ptr() {
+        let loc: Loc = Loc::encapsulate_range(0, 2..4);
+        assert_eq!(TestLoc(<&Loc as Located>::loc(&&loc)), TestLoc(loc));
+        assert_eq!(TestLoc(Box::new(loc).loc()), TestLoc(loc));
+        assert_eq!(TestLoc(Rc::new(loc).loc()), TestLoc(loc));
+        assert_eq!(TestLoc(Arc::new(loc).loc()), TestLoc(loc));
+
+        // Also for unsized things
+        let locs: Box<[Loc]> = vec![Loc::encapsulate_range(0, ..2), loc].into_boxed_slice();
+        assert_eq!(TestLoc(locs.loc()), TestLoc(Loc::encapsulate_range(0, ..4)));
+        let dynloc: Rc<dyn Located> = Rc::new(loc);
+        assert_eq!(TestLoc(dynloc.loc()), TestLoc(loc));
+    }
+}
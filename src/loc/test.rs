@@ -8,8 +8,8 @@
 //!   implements `eq` etc strictly.
 //
 
-use std::hash::{Hash, Hasher};
-use std::ops::{Deref, DerefMut};
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
 
 use super::{Loc, Located};
 
@@ -85,3 +85,30 @@ impl From<TestLoc> for Loc {
     #[inline]
     fn from(value: TestLoc) -> Self { value.0 }
 }
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Runs a property test on many [arbitrary](arbitrary::Arbitrary) `T`s.
+///
+/// The `T`s are generated from a fixed pseudo-random seed, so failures are reproducible.
+///
+/// # Arguments
+/// - `prop`: Some closure asserting the property for the given `T`.
+#[cfg(all(test, feature = "arbitrary"))]
+pub(crate) fn for_all<T: for<'a> arbitrary::Arbitrary<'a>>(mut prop: impl FnMut(T)) {
+    // A simple xorshift generator is more than enough to feed `Unstructured`
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut bytes: [u8; 64] = [0; 64];
+    for _ in 0..1024 {
+        for chunk in bytes.chunks_mut(8) {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            chunk.copy_from_slice(&state.to_le_bytes());
+        }
+        prop(T::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap());
+    }
+}
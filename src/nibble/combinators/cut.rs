@@ -0,0 +1,127 @@
+//  CUT.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`Cut`], which prevents backtracking out of a parser.
+//
+
+use std::fmt::Debug;
+
+use thiserror::Error;
+
+use super::super::{NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted when parsing a [`Cut`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum CutError<F, E> {
+    /// The nested parser was not matched.
+    #[error("Expected {0}")]
+    Unmatched(F),
+    /// The nested parser failed.
+    #[error(transparent)]
+    Inner(E),
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a `T`, but turns any [`NibbleError::Unmatched`] into a [`NibbleError::Error`].
+///
+/// This commits to the current branch: surrounding combinators will not try an alternative, but
+/// report that `T` was expected instead. Use it for the parts of a construct that are mandatory
+/// once its start has been seen, e.g., the expression after a `return`-keyword.
+///
+/// Note that this also means that `Option<Cut<T>>` never yields [`None`], as [`Option`] only
+/// swallows [`NibbleError::Unmatched`]. If a part is optional, wrap it like `Cut<Option<T>>`
+/// instead (which never fails to match in the first place) or leave the cut out.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Cut<T>(pub T);
+impl<T> Cut<T> {
+    /// Returns the parsed value.
+    ///
+    /// # Returns
+    /// The `T` that was parsed.
+    #[inline]
+    pub fn into_inner(self) -> T { self.0 }
+}
+
+// Loc
+impl<T: Located> Located for Cut<T> {
+    #[inline]
+    fn loc(&self) -> Loc { self.0.loc() }
+}
+
+// Parsing
+impl<E, T: Parsable<E>> Parsable<E> for Cut<T>
+where
+    T::Formatter: 'static + Debug,
+{
+    type Formatter = T::Formatter;
+    type Error = CutError<T::Formatter, T::Error>;
+
+    #[inline]
+    fn expects() -> Self::Formatter { T::expects() }
+
+    #[inline]
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        match T::parse(input) {
+            Ok((value, rem)) => Ok((Self(value), rem)),
+            Err(NibbleError::Unmatched(fmt, _)) => Err(NibbleError::Error(CutError::Unmatched(fmt))),
+            Err(NibbleError::Error(err)) => Err(NibbleError::Error(CutError::Inner(err))),
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::combinators::Either;
+    use crate::nibble::test::tag;
+
+    tag!(Open, b"(");
+    tag!(Foo, b"foo");
+    tag!(Close, b")");
+
+    #[test]
+    fn test_cut() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b")".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"foo".as_slice());
+
+        assert_eq!(Cut::<Close>::parse(input1), Ok((Cut(Close(TestLoc(Loc::encapsulate_range(ID, ..1)))), input1.slice(1..))));
+        assert_eq!(Cut::<Close>::parse(input2), Err(NibbleError::Error(CutError::Unmatched(Close::expects()))));
+        assert_eq!(Cut::<Close>::parse(input2).unwrap_err().to_string(), "Expected [41]");
+    }
+
+    #[test]
+    fn test_cut_backtracking() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"(foo".as_slice());
+
+        // Normally, a missing close means we backtrack to the next alternative...
+        assert_eq!(
+            <Either<(Open, Foo, Option<Close>), Open>>::parse(input).map(|(res, rem)| (matches!(res, Either::Left((_, _, None))), rem)),
+            Ok((true, input.slice(4..)))
+        );
+        // ...but with a cut, it's reported instead
+        assert_eq!(
+            <Option<Cut<Close>>>::parse(input.slice(4..)).map(|(res, _)| res.is_none()),
+            Err(NibbleError::Error(CutError::Unmatched(Close::expects())))
+        );
+        match <Either<(Open, Foo, Option<Cut<Close>>), Open>>::parse(input) {
+            Err(NibbleError::Error(Either::Left(err))) => assert_eq!(err.to_string(), "Expected [41]"),
+            res => panic!("Expected error, got {:?}", res.map(|(_, rem)| rem)),
+        }
+    }
+}
@@ -0,0 +1,315 @@
+//  DELIMITED.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for parsing values that are preceded and/or
+//!   terminated by some other node, e.g., parenthesis.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use thiserror::Error;
+
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted by [`delimited()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum DelimitedError<F2, F3, E1, E2, E3> {
+    /// The value was not found after the opening delimiter.
+    #[error("Expected {0} after opening delimiter")]
+    UnmatchedValue(F2),
+    /// The closing delimiter was not found after the value.
+    #[error("Expected closing {0}")]
+    UnmatchedClose(F3),
+    /// The opening delimiter failed to parse.
+    #[error(transparent)]
+    Open(E1),
+    /// The value failed to parse.
+    #[error(transparent)]
+    Value(E2),
+    /// The closing delimiter failed to parse.
+    #[error(transparent)]
+    Close(E3),
+}
+
+/// Defines the errors emitted by [`preceded()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum PrecededError<F2, E1, E2> {
+    /// The value was not found after the prefix.
+    #[error("Expected {0} after prefix")]
+    UnmatchedValue(F2),
+    /// The prefix failed to parse.
+    #[error(transparent)]
+    Prefix(E1),
+    /// The value failed to parse.
+    #[error(transparent)]
+    Value(E2),
+}
+
+/// Defines the errors emitted by [`terminated()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum TerminatedError<F2, E1, E2> {
+    /// The suffix was not found after the value.
+    #[error("Expected terminating {0}")]
+    UnmatchedSuffix(F2),
+    /// The value failed to parse.
+    #[error(transparent)]
+    Value(E1),
+    /// The suffix failed to parse.
+    #[error(transparent)]
+    Suffix(E2),
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`delimited()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct DelimitedFormatter<F1, F2, F3> {
+    /// The formatter of the opening delimiter.
+    open:  F1,
+    /// The formatter of the value.
+    value: F2,
+    /// The formatter of the closing delimiter.
+    close: F3,
+}
+impl<F1: Display, F2: Display, F3: Display> Display for DelimitedFormatter<F1, F2, F3> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        Display::fmt(&self.value, f)?;
+        write!(f, " delimited by ")?;
+        Display::fmt(&self.open, f)?;
+        write!(f, " and ")?;
+        Display::fmt(&self.close, f)
+    }
+}
+
+/// Formatter for [`preceded()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct PrecededFormatter<F1, F2> {
+    /// The formatter of the prefix.
+    prefix: F1,
+    /// The formatter of the value.
+    value:  F2,
+}
+impl<F1: Display, F2: Display> Display for PrecededFormatter<F1, F2> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        Display::fmt(&self.value, f)?;
+        write!(f, " preceded by ")?;
+        Display::fmt(&self.prefix, f)
+    }
+}
+
+/// Formatter for [`terminated()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct TerminatedFormatter<F1, F2> {
+    /// The formatter of the value.
+    value:  F1,
+    /// The formatter of the suffix.
+    suffix: F2,
+}
+impl<F1: Display, F2: Display> Display for TerminatedFormatter<F1, F2> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        Display::fmt(&self.value, f)?;
+        write!(f, " terminated by ")?;
+        Display::fmt(&self.suffix, f)
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a `T` that is enclosed by an `O`pening and `C`losing node, e.g., parenthesis.
+///
+/// Only the value is returned; the delimiters are discarded.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input, positioned after the closing
+/// delimiter.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] only if the opening delimiter was not matched.
+/// Once it is, the construct is considered committed, and failing to match the value or the
+/// closing delimiter results in a [`NibbleError::Error`] instead.
+///
+/// It also returns a [`NibbleError::Error`] if any of the nested parsers does so.
+#[allow(clippy::type_complexity)]
+pub fn delimited<'a, O, T, C, E>(
+    input: Slice<'a, E>,
+) -> Result<
+    (T, Slice<'a, E>),
+    NibbleError<DelimitedFormatter<O::Formatter, T::Formatter, C::Formatter>, DelimitedError<T::Formatter, C::Formatter, O::Error, T::Error, C::Error>>,
+>
+where
+    O: Parsable<E>,
+    T: Parsable<E>,
+    C: Parsable<E>,
+{
+    let rem: Slice<E> = match O::parse(input) {
+        Ok((_, rem)) => rem,
+        Err(NibbleError::Unmatched(_, needed)) => {
+            return Err(NibbleError::Unmatched(DelimitedFormatter { open: O::expects(), value: T::expects(), close: C::expects() }, needed));
+        },
+        Err(NibbleError::Error(err)) => return Err(NibbleError::Error(DelimitedError::Open(err))),
+    };
+    let (value, rem): (T, Slice<E>) = match T::parse(rem) {
+        Ok(res) => res,
+        Err(NibbleError::Unmatched(fmt, _)) => return Err(NibbleError::Error(DelimitedError::UnmatchedValue(fmt))),
+        Err(NibbleError::Error(err)) => return Err(NibbleError::Error(DelimitedError::Value(err))),
+    };
+    match C::parse(rem) {
+        Ok((_, rem)) => Ok((value, rem)),
+        Err(NibbleError::Unmatched(fmt, _)) => Err(NibbleError::Error(DelimitedError::UnmatchedClose(fmt))),
+        Err(NibbleError::Error(err)) => Err(NibbleError::Error(DelimitedError::Close(err))),
+    }
+}
+
+/// Parses a `T` that is preceded by a `P`.
+///
+/// Only the value is returned; the prefix is discarded.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input, positioned after the value.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] only if the prefix was not matched. Once it
+/// is, failing to match the value results in a [`NibbleError::Error`] instead.
+///
+/// It also returns a [`NibbleError::Error`] if any of the nested parsers does so.
+#[allow(clippy::type_complexity)]
+pub fn preceded<'a, P, T, E>(
+    input: Slice<'a, E>,
+) -> Result<(T, Slice<'a, E>), NibbleError<PrecededFormatter<P::Formatter, T::Formatter>, PrecededError<T::Formatter, P::Error, T::Error>>>
+where
+    P: Parsable<E>,
+    T: Parsable<E>,
+{
+    let rem: Slice<E> = match P::parse(input) {
+        Ok((_, rem)) => rem,
+        Err(NibbleError::Unmatched(_, needed)) => {
+            return Err(NibbleError::Unmatched(PrecededFormatter { prefix: P::expects(), value: T::expects() }, needed));
+        },
+        Err(NibbleError::Error(err)) => return Err(NibbleError::Error(PrecededError::Prefix(err))),
+    };
+    match T::parse(rem) {
+        Ok(res) => Ok(res),
+        Err(NibbleError::Unmatched(fmt, _)) => Err(NibbleError::Error(PrecededError::UnmatchedValue(fmt))),
+        Err(NibbleError::Error(err)) => Err(NibbleError::Error(PrecededError::Value(err))),
+    }
+}
+
+/// Parses a `T` that is terminated by an `S`.
+///
+/// Only the value is returned; the suffix is discarded.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input, positioned after the suffix.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] only if the value was not matched. Once it
+/// is, failing to match the suffix results in a [`NibbleError::Error`] instead.
+///
+/// It also returns a [`NibbleError::Error`] if any of the nested parsers does so.
+#[allow(clippy::type_complexity)]
+pub fn terminated<'a, T, S, E>(
+    input: Slice<'a, E>,
+) -> Result<(T, Slice<'a, E>), NibbleError<TerminatedFormatter<T::Formatter, S::Formatter>, TerminatedError<S::Formatter, T::Error, S::Error>>>
+where
+    T: Parsable<E>,
+    S: Parsable<E>,
+{
+    let (value, rem): (T, Slice<E>) = match T::parse(input) {
+        Ok(res) => res,
+        Err(NibbleError::Unmatched(_, needed)) => {
+            return Err(NibbleError::Unmatched(TerminatedFormatter { value: T::expects(), suffix: S::expects() }, needed));
+        },
+        Err(NibbleError::Error(err)) => return Err(NibbleError::Error(TerminatedError::Value(err))),
+    };
+    match S::parse(rem) {
+        Ok((_, rem)) => Ok((value, rem)),
+        Err(NibbleError::Unmatched(fmt, _)) => Err(NibbleError::Error(TerminatedError::UnmatchedSuffix(fmt))),
+        Err(NibbleError::Error(err)) => Err(NibbleError::Error(TerminatedError::Suffix(err))),
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::tag;
+
+    tag!(Open, b"(");
+    tag!(Foo, b"foo");
+    tag!(Close, b")");
+
+    #[test]
+    fn test_delimited() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"(foo)!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"foo)".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"(foo".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"()".as_slice());
+
+        assert_eq!(delimited::<Open, Foo, Close, _>(input1), Ok((Foo(TestLoc(Loc::encapsulate_range(ID, 1..4))), input1.slice(5..))));
+        assert_eq!(
+            delimited::<Open, Foo, Close, _>(input2),
+            Err(NibbleError::Unmatched(DelimitedFormatter { open: Open::expects(), value: Foo::expects(), close: Close::expects() }, None))
+        );
+        assert_eq!(delimited::<Open, Foo, Close, _>(input3), Err(NibbleError::Error(DelimitedError::UnmatchedClose(Close::expects()))));
+        assert_eq!(delimited::<Open, Foo, Close, _>(input3).unwrap_err().to_string(), "Expected closing [41]");
+        assert_eq!(delimited::<Open, Foo, Close, _>(input4), Err(NibbleError::Error(DelimitedError::UnmatchedValue(Foo::expects()))));
+    }
+
+    #[test]
+    fn test_preceded() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"(foo".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"foo".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"()".as_slice());
+
+        assert_eq!(preceded::<Open, Foo, _>(input1), Ok((Foo(TestLoc(Loc::encapsulate_range(ID, 1..4))), input1.slice(4..))));
+        assert_eq!(
+            preceded::<Open, Foo, _>(input2),
+            Err(NibbleError::Unmatched(PrecededFormatter { prefix: Open::expects(), value: Foo::expects() }, None))
+        );
+        assert_eq!(preceded::<Open, Foo, _>(input3), Err(NibbleError::Error(PrecededError::UnmatchedValue(Foo::expects()))));
+    }
+
+    #[test]
+    fn test_terminated() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"foo)".as_slice());
+        let input2 = Slice::with_raw_id(ID, b")".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"foo(".as_slice());
+
+        assert_eq!(terminated::<Foo, Close, _>(input1), Ok((Foo(TestLoc(Loc::encapsulate_range(ID, ..3))), input1.slice(4..))));
+        assert_eq!(
+            terminated::<Foo, Close, _>(input2),
+            Err(NibbleError::Unmatched(TerminatedFormatter { value: Foo::expects(), suffix: Close::expects() }, None))
+        );
+        assert_eq!(terminated::<Foo, Close, _>(input3), Err(NibbleError::Error(TerminatedError::UnmatchedSuffix(Close::expects()))));
+    }
+}
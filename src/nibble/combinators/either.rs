@@ -0,0 +1,182 @@
+//  EITHER.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`Either`], a combinator for ordered choice between two
+//!   parsers.
+//
+
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use super::super::error::Needed;
+use super::super::{NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`Either::expects()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct EitherFormatter<F1, F2> {
+    /// The formatter of the left parser.
+    left:  F1,
+    /// The formatter of the right parser.
+    right: F2,
+}
+impl<F1: Display, F2: Display> Display for EitherFormatter<F1, F2> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        Display::fmt(&self.left, f)?;
+        write!(f, " or ")?;
+        Display::fmt(&self.right, f)
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Represents an ordered choice between two nodes.
+///
+/// When parsed, it will first attempt to parse `A`. Only if that is [`NibbleError::Unmatched`] will
+/// it try `B` on the same input. If `A` returns a [`NibbleError::Error`], then that is returned
+/// instead, as it means that the input was recognized as `A` but illegal.
+///
+/// You can nest this type to choose between more than two nodes, e.g., `Either<A, Either<B, C>>`.
+///
+/// Note that this type doubles as the [`Parsable::Error`] of itself, being either the error of `A`
+/// or that of `B`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Either<A, B> {
+    /// The first alternative was parsed.
+    Left(A),
+    /// The second alternative was parsed.
+    Right(B),
+}
+
+// Ops
+impl<A: Display, B: Display> Display for Either<A, B> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        match self {
+            Self::Left(a) => a.fmt(f),
+            Self::Right(b) => b.fmt(f),
+        }
+    }
+}
+impl<A: Error, B: Error> Error for Either<A, B> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Left(a) => a.source(),
+            Self::Right(b) => b.source(),
+        }
+    }
+}
+
+// Loc
+impl<A: Located, B: Located> Located for Either<A, B> {
+    #[inline]
+    fn loc(&self) -> Loc {
+        match self {
+            Self::Left(a) => a.loc(),
+            Self::Right(b) => b.loc(),
+        }
+    }
+}
+
+// Parsing
+impl<E, A: Parsable<E>, B: Parsable<E>> Parsable<E> for Either<A, B> {
+    type Formatter = EitherFormatter<A::Formatter, B::Formatter>;
+    type Error = Either<A::Error, B::Error>;
+
+    #[inline]
+    fn expects() -> Self::Formatter { EitherFormatter { left: A::expects(), right: B::expects() } }
+
+    #[inline]
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        let lneeded = match A::parse(input) {
+            Ok((res, rem)) => return Ok((Self::Left(res), rem)),
+            Err(NibbleError::Unmatched(_, needed)) => needed,
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(Either::Left(err))),
+        };
+        match B::parse(input) {
+            Ok((res, rem)) => Ok((Self::Right(res), rem)),
+            Err(NibbleError::Unmatched(_, rneeded)) => Err(NibbleError::Unmatched(Self::expects(), Needed::combine_opt(lneeded, rneeded))),
+            Err(NibbleError::Error(err)) => Err(NibbleError::Error(Either::Right(err))),
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Foo, b"foo");
+    tag!(Bar, b"bar");
+    tag!(Baz, b"baz");
+    tag!(Hello, b"Hello");
+    illegal!(Bad, b"foo");
+
+    #[test]
+    fn test_either() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"foo!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"bar!".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"quz".as_slice());
+
+        assert_eq!(Either::<Foo, Bar>::parse(input1), Ok((Either::Left(Foo(TestLoc(Loc::encapsulate_range(ID, ..3)))), input1.slice(3..))));
+        assert_eq!(Either::<Foo, Bar>::parse(input2), Ok((Either::Right(Bar(TestLoc(Loc::encapsulate_range(ID, ..3)))), input2.slice(3..))));
+        assert_eq!(Either::<Foo, Bar>::parse(input3), Err(NibbleError::Unmatched(Either::<Foo, Bar>::expects(), None)));
+        assert_eq!(Either::<Foo, Bar>::expects().to_string(), "[102, 111, 111] or [98, 97, 114]");
+    }
+
+    #[test]
+    fn test_either_needed() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"f".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"".as_slice());
+
+        // Only the alternatives that may still match contribute
+        assert_eq!(Either::<Foo, Bar>::parse(input1), Err(NibbleError::Unmatched(Either::<Foo, Bar>::expects(), Some(Needed::Bounded(2, 2)))));
+        assert_eq!(
+            Either::<Foo, Either<Bar, Hello>>::parse(input2),
+            Err(NibbleError::Unmatched(Either::<Foo, Either<Bar, Hello>>::expects(), Some(Needed::Bounded(3, 5))))
+        );
+    }
+
+    #[test]
+    fn test_either_nested() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"baz".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"bar".as_slice());
+
+        assert_eq!(
+            Either::<Foo, Either<Bar, Baz>>::parse(input1),
+            Ok((Either::Right(Either::Right(Baz(TestLoc(Loc::encapsulate_range(ID, ..3))))), input1.slice(3..)))
+        );
+        assert_eq!(
+            Either::<Foo, Either<Bar, Baz>>::parse(input2),
+            Ok((Either::Right(Either::Left(Bar(TestLoc(Loc::encapsulate_range(ID, ..3))))), input2.slice(3..)))
+        );
+        assert_eq!(Either::<Foo, Either<Bar, Baz>>::expects().to_string(), "[102, 111, 111] or [98, 97, 114] or [98, 97, 122]");
+    }
+
+    #[test]
+    fn test_either_error() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"foo".as_slice());
+
+        // A hard error never falls through to the next alternative
+        assert_eq!(Either::<Bad, Foo>::parse(input), Err(NibbleError::Error(Either::Left(TestError("Bad")))));
+        assert_eq!(Either::<Bar, Bad>::parse(input), Err(NibbleError::Error(Either::Right(TestError("Bad")))));
+    }
+}
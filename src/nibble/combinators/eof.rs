@@ -0,0 +1,146 @@
+//  EOF.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements parsers for asserting the end of the input.
+//
+
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use thiserror::Error;
+
+use super::super::error::ResultExt;
+use super::super::{NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted by [`parse_all()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum ParseAllError<E> {
+    /// The nested parser failed.
+    #[error(transparent)]
+    Inner(E),
+    /// The nested parser succeeded, but did not consume all input.
+    #[error("Expected end of input")]
+    TrailingInput(Loc),
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`Eof::expects()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct EofFormatter;
+impl Display for EofFormatter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "end of input") }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Matches only if there is no input left.
+///
+/// It does not consume anything, and its [`Loc`] is thus always empty.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Eof {
+    /// The (empty) location of the end of the input.
+    pub loc: Loc,
+}
+
+// Loc
+impl Located for Eof {
+    #[inline]
+    fn loc(&self) -> Loc { self.loc }
+}
+
+// Parsing
+impl<E> Parsable<E> for Eof {
+    type Formatter = EofFormatter;
+    type Error = Infallible;
+
+    #[inline]
+    fn expects() -> Self::Formatter { EofFormatter }
+
+    #[inline]
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        if input.is_empty() {
+            let ((_, loc), rem) = input.head_slice_loc(0);
+            Ok((Self { loc }, rem))
+        } else {
+            Err(NibbleError::Unmatched(EofFormatter, None))
+        }
+    }
+}
+
+
+
+/// Parses a `T` and asserts that it consumed the whole input.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// The parsed value.
+///
+/// # Errors
+/// This function errors whenever `T` does. In addition, if there is input left after `T`, then a
+/// [`NibbleError::Error`] with [`ParseAllError::TrailingInput`] is returned, carrying the location
+/// of the trailing input.
+#[inline]
+pub fn parse_all<T, E>(input: Slice<E>) -> Result<T, NibbleError<T::Formatter, ParseAllError<T::Error>>>
+where
+    T: Parsable<E>,
+{
+    let (value, rem): (T, Slice<E>) = T::parse(input).map_nerr(ParseAllError::Inner)?;
+    if rem.is_empty() { Ok(value) } else { Err(NibbleError::Error(ParseAllError::TrailingInput(rem.loc()))) }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::tag;
+
+    tag!(Hello, b"Hello");
+
+    #[test]
+    fn test_eof() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Hello".as_slice());
+
+        let (eof, rem) = Eof::parse(input1).unwrap();
+        assert_eq!((TestLoc(eof.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..0)), input1));
+        let (eof, rem) = Eof::parse(input2.slice(5..)).unwrap();
+        assert_eq!((TestLoc(eof.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, 5..5)), input2.slice(5..)));
+        assert_eq!(Eof::parse(input2), Err(NibbleError::Unmatched(EofFormatter, None)));
+    }
+
+    #[test]
+    fn test_parse_all() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"Hello".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Hello!".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"Goodbye".as_slice());
+
+        assert_eq!(parse_all::<Hello, _>(input1), Ok(Hello(TestLoc(Loc::encapsulate_range(ID, ..5)))));
+        match parse_all::<Hello, _>(input2) {
+            Err(NibbleError::Error(ParseAllError::TrailingInput(loc))) => assert_eq!(TestLoc(loc), TestLoc(Loc::encapsulate_range(ID, 5..6))),
+            res => panic!("Expected trailing input error, got {res:?}"),
+        }
+        assert_eq!(parse_all::<Hello, _>(input2).unwrap_err().to_string(), "Expected end of input");
+        assert_eq!(parse_all::<Hello, _>(input3), Err(NibbleError::Unmatched(Hello::expects(), None)));
+    }
+}
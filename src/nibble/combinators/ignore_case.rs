@@ -0,0 +1,126 @@
+//  IGNORE CASE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`IgnoreCase`], which parses [`Tag`]s case-insensitively.
+//
+
+use std::convert::Infallible;
+use std::fmt::{Debug, Display, Formatter, Result as FResult};
+use std::marker::PhantomData;
+
+use super::super::error::Needed;
+use super::super::{NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+use crate::tree::Tag;
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`IgnoreCase::expects()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct IgnoreCaseFormatter<T> {
+    /// The tag to find.
+    _t: PhantomData<T>,
+}
+impl<T: Tag<u8>> Display for IgnoreCaseFormatter<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "{:?} (ignoring case)", T::TAG) }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a byte [`Tag`] `T`, but ignores the case of ASCII characters while doing so.
+///
+/// Non-ASCII bytes are still compared exactly; i.e., no multibyte case folding is performed.
+///
+/// The [`Loc`] of the resulting `T` points to the actually matched input, whatever its case.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct IgnoreCase<T>(pub T);
+impl<T> IgnoreCase<T> {
+    /// Returns the parsed tag.
+    ///
+    /// # Returns
+    /// The `T` that was parsed.
+    #[inline]
+    pub fn into_inner(self) -> T { self.0 }
+}
+
+// Loc
+impl<T: Located> Located for IgnoreCase<T> {
+    #[inline]
+    fn loc(&self) -> Loc { self.0.loc() }
+}
+
+// Parsing
+impl<T: Tag<u8>> Parsable<u8> for IgnoreCase<T> {
+    type Formatter = IgnoreCaseFormatter<T>;
+    type Error = Infallible;
+
+    #[inline]
+    fn expects() -> Self::Formatter { IgnoreCaseFormatter { _t: PhantomData } }
+
+    #[inline]
+    fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+        // Get a slice of bytes equal to (at most) the tag size
+        let ((head, loc), rem) = input.head_slice_loc(T::TAG.len());
+        for (h, t) in head.iter().zip(T::TAG.iter()) {
+            if !h.eq_ignore_ascii_case(t) {
+                // Divirging bytes. More input can never fix this!
+                return Err(NibbleError::Unmatched(Self::expects(), None));
+            }
+        }
+
+        // Now it depends on whether the head is _all_ of TAG or whether it is a prefix of it
+        if head.len() >= T::TAG.len() {
+            Ok((Self(T::with_loc(loc)), rem))
+        } else {
+            let needed: usize = T::TAG.len() - head.len();
+            Err(NibbleError::Unmatched(Self::expects(), Some(Needed::Bounded(needed, needed))))
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::tag;
+
+    tag!(Select, b"select");
+    tag!(Cafe, b"caf\xC3\xA9");
+
+    #[test]
+    fn test_ignore_case() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"SELECT *".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"select".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"SeLeCt".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"SEL".as_slice());
+        let input5 = Slice::with_raw_id(ID, b"insert".as_slice());
+
+        assert_eq!(IgnoreCase::<Select>::parse(input1), Ok((IgnoreCase(Select(TestLoc(Loc::encapsulate_range(ID, ..6)))), input1.slice(6..))));
+        assert_eq!(IgnoreCase::<Select>::parse(input2), Ok((IgnoreCase(Select(TestLoc(Loc::encapsulate_range(ID, ..6)))), input2.slice(6..))));
+        assert_eq!(IgnoreCase::<Select>::parse(input3), Ok((IgnoreCase(Select(TestLoc(Loc::encapsulate_range(ID, ..6)))), input3.slice(6..))));
+        assert_eq!(IgnoreCase::<Select>::parse(input4), Err(NibbleError::Unmatched(IgnoreCase::<Select>::expects(), Some(Needed::Bounded(3, 3)))));
+        assert_eq!(IgnoreCase::<Select>::parse(input5), Err(NibbleError::Unmatched(IgnoreCase::<Select>::expects(), None)));
+    }
+
+    #[test]
+    fn test_ignore_case_non_ascii() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, "CAFé".as_bytes());
+        let input2 = Slice::with_raw_id(ID, "CAFÉ".as_bytes());
+
+        // Non-ASCII bytes are compared exactly
+        assert_eq!(IgnoreCase::<Cafe>::parse(input1), Ok((IgnoreCase(Cafe(TestLoc(Loc::encapsulate_range(ID, ..5)))), input1.slice(5..))));
+        assert_eq!(IgnoreCase::<Cafe>::parse(input2), Err(NibbleError::Unmatched(IgnoreCase::<Cafe>::expects(), None)));
+    }
+}
@@ -0,0 +1,90 @@
+//  LABELLED.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`labelled()`], which overrides what a parser says it
+//!   expects.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use super::super::error::ResultExt as _;
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`labelled()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct LabelFormatter {
+    /// The label to show.
+    label: &'static str,
+}
+impl Display for LabelFormatter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "{}", self.label) }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a `T`, but describes what was expected with the given label instead of with `T`'s
+/// formatter.
+///
+/// This is useful for giving errors a higher-level meaning, e.g., reporting `a block` instead of
+/// `"{"`.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `label`: The description of what was expected if `T` is unmatched.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input.
+///
+/// # Errors
+/// This function returns a [`NibbleError::Unmatched`] with the `label` (and `T`'s [`Needed`]-hint)
+/// if `T` is unmatched. [`NibbleError::Error`]s are returned as-is.
+///
+/// [`Needed`]: super::super::Needed
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn labelled<'a, T, E>(input: Slice<'a, E>, label: &'static str) -> Result<(T, Slice<'a, E>), NibbleError<LabelFormatter, T::Error>>
+where
+    T: Parsable<E>,
+{
+    T::parse(input).map_unmatched(|_, needed| (LabelFormatter { label }, needed))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::Needed;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Open, b"{");
+    illegal!(Bad, b"{");
+
+    #[test]
+    fn test_labelled() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"{}".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"()".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"".as_slice());
+
+        assert_eq!(labelled::<Open, _>(input1, "a block"), Ok((Open(TestLoc(Loc::encapsulate_range(ID, ..1))), input1.slice(1..))));
+        let err = labelled::<Open, _>(input2, "a block").unwrap_err();
+        assert_eq!(err, NibbleError::Unmatched(LabelFormatter { label: "a block" }, None));
+        assert_eq!(err.to_string(), "a block");
+        assert_eq!(labelled::<Open, _>(input3, "a block"), Err(NibbleError::Unmatched(LabelFormatter { label: "a block" }, Some(Needed::Bounded(1, 1)))));
+        // Errors are left alone
+        assert_eq!(labelled::<Bad, _>(input1, "a block").map(|(_, rem)| rem), Err(NibbleError::Error(TestError("Bad"))));
+    }
+}
@@ -0,0 +1,321 @@
+//  MANY.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for parsing a bounded number of repetitions of
+//!   a node, or for folding repetitions without collecting them.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use super::super::error::Needed;
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`count()`] and [`many_m_n()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct ManyFormatter<F> {
+    /// The formatter of the repeated node.
+    fmt: F,
+    /// The minimum number of occurrences.
+    min: usize,
+    /// The maximum number of occurrences.
+    max: usize,
+}
+impl<F: Display> Display for ManyFormatter<F> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        if self.min == self.max {
+            write!(f, "exactly {} occurrences of ", self.min)?;
+        } else {
+            write!(f, "between {} and {} occurrences of ", self.min, self.max)?;
+        }
+        Display::fmt(&self.fmt, f)
+    }
+}
+
+
+/// Formatter for [`fold_many()`] and [`fold_many1()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct FoldFormatter<F> {
+    /// The formatter of the repeated node.
+    fmt: F,
+}
+impl<F: Display> Display for FoldFormatter<F> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        write!(f, "one or more occurrences of ")?;
+        Display::fmt(&self.fmt, f)
+    }
+}
+
+
+
+
+/***** LIBRARY *****/
+/// Parses exactly `n` occurrences of `T`.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `n`: The number of occurrences to parse. If this is `0`, then an empty list is returned
+///   without consuming any input.
+///
+/// # Returns
+/// A tuple with the parsed values and the remainder of the input.
+///
+/// # Errors
+/// See [`many_m_n()`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn count<'a, T, E>(input: Slice<'a, E>, n: usize) -> Result<(Vec<T>, Slice<'a, E>), NibbleError<ManyFormatter<T::Formatter>, T::Error>>
+where
+    T: Parsable<E>,
+{
+    many_m_n(input, n, n)
+}
+
+/// Parses between `min` and `max` (both inclusive) occurrences of `T`.
+///
+/// Parsing stops as soon as `max` occurrences have been found, even if more would match.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `min`: The minimum number of occurrences to parse.
+/// - `max`: The maximum number of occurrences to parse.
+///
+/// # Returns
+/// A tuple with the parsed values and the remainder of the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if less than `min` occurrences were found. If
+/// this happened because `T` ran out of input, then its [`Needed`] reflects that; if more than
+/// one occurrence is missing, then only a lower bound is known.
+///
+/// It returns a [`NibbleError::Error`] if any `T` does.
+///
+/// # Panics
+/// This function panics if `min` is larger than `max`.
+#[allow(clippy::type_complexity)]
+pub fn many_m_n<'a, T, E>(
+    mut input: Slice<'a, E>,
+    min: usize,
+    max: usize,
+) -> Result<(Vec<T>, Slice<'a, E>), NibbleError<ManyFormatter<T::Formatter>, T::Error>>
+where
+    T: Parsable<E>,
+{
+    assert!(min <= max, "Given minimum {min} is larger than given maximum {max}");
+
+    // NOTE: `min` may come from the input (e.g., a length prefix), so don't trust it for
+    // allocating. Assuming every element consumes input, there can't be more than there's input.
+    let mut res: Vec<T> = Vec::with_capacity(min.min(input.len()));
+    while res.len() < max {
+        match T::parse(input) {
+            Ok((value, rem)) => {
+                res.push(value);
+                input = rem;
+            },
+            Err(NibbleError::Unmatched(_, needed)) => {
+                if res.len() >= min {
+                    break;
+                }
+
+                // If we're missing more than one, we only know the lower bound
+                let needed: Option<Needed> = if min - res.len() > 1 {
+                    needed.map(|needed| match needed {
+                        Needed::Bounded(min, _) | Needed::AtLeast(min) => Needed::AtLeast(min),
+                        Needed::Unknown => Needed::Unknown,
+                    })
+                } else {
+                    needed
+                };
+                return Err(NibbleError::Unmatched(ManyFormatter { fmt: T::expects(), min, max }, needed));
+            },
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(err)),
+        }
+    }
+    Ok((res, input))
+}
+
+
+/// Parses zero or more `T`s, folding each of them into an accumulator.
+///
+/// This is like parsing a [`Vec<T>`] and then folding it, except that no [`Vec`] is allocated.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `init`: The initial value of the accumulator.
+/// - `f`: Some function that folds a parsed `T` into the accumulator.
+///
+/// # Returns
+/// A tuple with the final accumulator and the remainder of the input. Parsing stops at the first
+/// `T` that is unmatched, or after a `T` that consumed no input (as it would match forever).
+///
+/// # Errors
+/// This function returns a [`NibbleError::Error`] if any `T` does. It never returns
+/// [`NibbleError::Unmatched`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn fold_many<'a, T, E, A>(
+    mut input: Slice<'a, E>,
+    init: A,
+    mut f: impl FnMut(A, T) -> A,
+) -> Result<(A, Slice<'a, E>), NibbleError<FoldFormatter<T::Formatter>, T::Error>>
+where
+    T: Parsable<E>,
+{
+    let mut acc: A = init;
+    loop {
+        match T::parse(input) {
+            Ok((value, rem)) => {
+                acc = f(acc, value);
+                if rem.offset() == input.offset() {
+                    return Ok((acc, rem));
+                }
+                input = rem;
+            },
+            Err(NibbleError::Unmatched(_, _)) => return Ok((acc, input)),
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(err)),
+        }
+    }
+}
+
+/// Parses one or more `T`s, folding each of them into an accumulator.
+///
+/// See [`fold_many()`] for more information.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `init`: The initial value of the accumulator.
+/// - `f`: Some function that folds a parsed `T` into the accumulator.
+///
+/// # Returns
+/// A tuple with the final accumulator and the remainder of the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if not even the first `T` matched, with its
+/// [`Needed`]-hint. It returns a [`NibbleError::Error`] if any `T` does.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn fold_many1<'a, T, E, A>(
+    input: Slice<'a, E>,
+    init: A,
+    mut f: impl FnMut(A, T) -> A,
+) -> Result<(A, Slice<'a, E>), NibbleError<FoldFormatter<T::Formatter>, T::Error>>
+where
+    T: Parsable<E>,
+{
+    let (value, rem): (T, Slice<E>) = match T::parse(input) {
+        Ok(res) => res,
+        Err(NibbleError::Unmatched(fmt, needed)) => return Err(NibbleError::Unmatched(FoldFormatter { fmt }, needed)),
+        Err(NibbleError::Error(err)) => return Err(NibbleError::Error(err)),
+    };
+    let acc: A = f(init, value);
+    if rem.offset() == input.offset() {
+        return Ok((acc, rem));
+    }
+    fold_many(rem, acc, f)
+}
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::combinators::Either;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Foo, b"foo");
+    tag!(One, b"1");
+    tag!(Two, b"2");
+    illegal!(Bad, b"foo");
+
+    #[test]
+    fn test_count() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"foofoofoo".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"foofo".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"foobar".as_slice());
+
+        assert_eq!(count::<Foo, _>(input1, 0), Ok((vec![], input1)));
+        assert_eq!(
+            count::<Foo, _>(input1, 2),
+            Ok((vec![Foo(TestLoc(Loc::encapsulate_range(ID, 0..3))), Foo(TestLoc(Loc::encapsulate_range(ID, 3..6)))], input1.slice(6..)))
+        );
+        // Not enough input
+        assert_eq!(
+            count::<Foo, _>(input2, 2),
+            Err(NibbleError::Unmatched(ManyFormatter { fmt: Foo::expects(), min: 2, max: 2 }, Some(Needed::Bounded(1, 1))))
+        );
+        assert_eq!(
+            count::<Foo, _>(input2, 3),
+            Err(NibbleError::Unmatched(ManyFormatter { fmt: Foo::expects(), min: 3, max: 3 }, Some(Needed::AtLeast(1))))
+        );
+        // Diverging input
+        assert_eq!(count::<Foo, _>(input3, 2), Err(NibbleError::Unmatched(ManyFormatter { fmt: Foo::expects(), min: 2, max: 2 }, None)));
+        assert_eq!(count::<Foo, _>(input3, 2).unwrap_err().to_string(), "exactly 2 occurrences of [102, 111, 111]");
+        // Errors propagate
+        assert_eq!(count::<Bad, _>(input1, 2).map(|(v, _)| v.len()), Err(NibbleError::Error(TestError("Bad"))));
+    }
+
+    #[test]
+    fn test_many_m_n() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"foofoofoo".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"foo".as_slice());
+
+        // The upper bound stops consumption
+        let (res, rem) = many_m_n::<Foo, _>(input1, 1, 2).unwrap();
+        assert_eq!((res.len(), rem), (2, input1.slice(6..)));
+        // But fewer is fine, too
+        let (res, rem) = many_m_n::<Foo, _>(input2, 1, 2).unwrap();
+        assert_eq!((res.len(), rem), (1, input2.slice(3..)));
+        let (res, rem) = many_m_n::<Foo, _>(input2, 0, 2).unwrap();
+        assert_eq!((res.len(), rem), (1, input2.slice(3..)));
+        // Unless it's too few
+        assert_eq!(
+            many_m_n::<Foo, _>(input2, 2, 3).map(|(v, _)| v.len()),
+            Err(NibbleError::Unmatched(ManyFormatter { fmt: Foo::expects(), min: 2, max: 3 }, Some(Needed::Bounded(3, 3))))
+        );
+        assert_eq!(many_m_n::<Foo, _>(input2, 2, 3).unwrap_err().to_string(), "between 2 and 3 occurrences of [102, 111, 111]");
+        // Huge minimums don't allocate beforehand
+        assert_eq!(
+            many_m_n::<Foo, _>(input2, usize::MAX, usize::MAX).map(|(v, _)| v.len()),
+            Err(NibbleError::Unmatched(ManyFormatter { fmt: Foo::expects(), min: usize::MAX, max: usize::MAX }, Some(Needed::AtLeast(3))))
+        );
+    }
+
+    #[test]
+    fn test_fold_many() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"1221;".as_slice());
+        let input2 = Slice::with_raw_id(ID, b";".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"12foo".as_slice());
+
+        // Sum the digits
+        let digit = |acc: u32, d: Either<One, Two>| acc + if matches!(d, Either::Left(_)) { 1 } else { 2 };
+        assert_eq!(fold_many::<Either<One, Two>, _, _>(input1, 0, digit), Ok((6, input1.slice(4..))));
+        assert_eq!(fold_many::<Either<One, Two>, _, _>(input2, 0, digit), Ok((0, input2)));
+        assert_eq!(fold_many1::<Either<One, Two>, _, _>(input1, 0, digit), Ok((6, input1.slice(4..))));
+        assert_eq!(
+            fold_many1::<Either<One, Two>, _, _>(input2, 0, digit),
+            Err(NibbleError::Unmatched(FoldFormatter { fmt: Either::<One, Two>::expects() }, None))
+        );
+        assert_eq!(fold_many1::<One, _, _>(input2, 0, |acc, _| acc).unwrap_err().to_string(), "one or more occurrences of [49]");
+        // Errors propagate
+        assert_eq!(fold_many::<Either<One, Either<Two, Bad>>, _, _>(input3, 0, |acc, _| acc + 1), Err(NibbleError::Error(Either::Right(Either::Right(TestError("Bad"))))));
+        // Nodes that don't consume input are folded only once
+        assert_eq!(fold_many::<Option<One>, _, _>(input2, 0, |acc, _| acc + 1), Ok((1, input2)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_many_m_n_invalid() {
+        let _ = many_m_n::<Foo, _>(Slice::with_raw_id(0, b"".as_slice()), 2, 1);
+    }
+}
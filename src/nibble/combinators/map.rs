@@ -0,0 +1,257 @@
+//  MAP.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for transforming the result of a parser into
+//!   something else.
+//!
+//!   These come in both flavours. The [`map()`] and [`map_res()`] functions
+//!   take a closure, and are the most convenient if you're writing a parser
+//!   by hand. The [`Map`] and [`MapRes`] types are instead parameterized by a
+//!   (marker) type implementing [`MapFn`] or [`TryMapFn`], respectively, and
+//!   can be used anywhere a [`Parsable`] node can.
+//
+
+use std::error::Error;
+use std::marker::PhantomData;
+
+use thiserror::Error;
+
+use super::super::error::ResultExt;
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted by [`map_res()`] and [`MapRes`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum MapResError<E1, E2> {
+    /// The nested parser failed.
+    #[error(transparent)]
+    Inner(E1),
+    /// The mapping function failed.
+    #[error(transparent)]
+    Map(E2),
+}
+
+
+
+
+
+/***** INTERFACES *****/
+/// Defines a (marker) type that can map some `I`nput to some `O`utput.
+///
+/// This is used by [`Map`] to carry the mapping function in its type.
+pub trait MapFn<I, O> {
+    /// Performs the mapping.
+    ///
+    /// # Arguments
+    /// - `value`: The value to map.
+    ///
+    /// # Returns
+    /// The mapped value.
+    fn map(value: I) -> O;
+}
+
+/// Defines a (marker) type that can map some `I`nput to some `O`utput, but which may fail.
+///
+/// This is used by [`MapRes`] to carry the mapping function in its type.
+pub trait TryMapFn<I, O> {
+    /// The error returned when mapping fails.
+    type Error: 'static + Error;
+
+    /// Performs the mapping.
+    ///
+    /// # Arguments
+    /// - `value`: The value to map.
+    ///
+    /// # Returns
+    /// The mapped value.
+    ///
+    /// # Errors
+    /// This function errors if the value could not be mapped.
+    fn try_map(value: I) -> Result<O, Self::Error>;
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a `T` and then maps it to something else using the given closure.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `f`: The closure that maps the parsed `T` to an `O`.
+///
+/// # Returns
+/// A tuple with the mapped value and the remainder of the input.
+///
+/// # Errors
+/// This function errors whenever `T` does.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn map<'a, T, O, E>(input: Slice<'a, E>, f: impl FnOnce(T) -> O) -> Result<(O, Slice<'a, E>), NibbleError<T::Formatter, T::Error>>
+where
+    T: Parsable<E>,
+{
+    let (value, rem): (T, Slice<E>) = T::parse(input)?;
+    Ok((f(value), rem))
+}
+
+/// Parses a `T` and then maps it to something else using the given fallible closure.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `f`: The closure that maps the parsed `T` to an `O`.
+///
+/// # Returns
+/// A tuple with the mapped value and the remainder of the input.
+///
+/// # Errors
+/// This function errors whenever `T` does. In addition, if `f` fails, its error is returned as a
+/// [`NibbleError::Error`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn map_res<'a, T, O, E, E2>(
+    input: Slice<'a, E>,
+    f: impl FnOnce(T) -> Result<O, E2>,
+) -> Result<(O, Slice<'a, E>), NibbleError<T::Formatter, MapResError<T::Error, E2>>>
+where
+    T: Parsable<E>,
+{
+    let (value, rem): (T, Slice<E>) = T::parse(input).map_nerr(MapResError::Inner)?;
+    match f(value) {
+        Ok(value) => Ok((value, rem)),
+        Err(err) => Err(NibbleError::Error(MapResError::Map(err))),
+    }
+}
+
+
+
+/// Parses a `P` and then maps it to an `O` using the [`MapFn`] `F`.
+///
+/// See [`map()`] for a version that accepts a closure instead.
+pub struct Map<P, F, O> {
+    /// The mapped value.
+    pub value: O,
+    /// Remembers the parser and the mapping function.
+    _f:        PhantomData<fn(P) -> F>,
+}
+impl<P, F, O> Map<P, F, O> {
+    /// Returns the mapped value.
+    ///
+    /// # Returns
+    /// The `O` that was produced by `F`.
+    #[inline]
+    pub fn into_inner(self) -> O { self.value }
+}
+impl<E, P: Parsable<E>, F: MapFn<P, O>, O> Parsable<E> for Map<P, F, O> {
+    type Formatter = P::Formatter;
+    type Error = P::Error;
+
+    #[inline]
+    fn expects() -> Self::Formatter { P::expects() }
+
+    #[inline]
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        map(input, |value: P| Self { value: F::map(value), _f: PhantomData })
+    }
+}
+
+/// Parses a `P` and then maps it to an `O` using the [`TryMapFn`] `F`.
+///
+/// See [`map_res()`] for a version that accepts a closure instead.
+pub struct MapRes<P, F, O> {
+    /// The mapped value.
+    pub value: O,
+    /// Remembers the parser and the mapping function.
+    _f:        PhantomData<fn(P) -> F>,
+}
+impl<P, F, O> MapRes<P, F, O> {
+    /// Returns the mapped value.
+    ///
+    /// # Returns
+    /// The `O` that was produced by `F`.
+    #[inline]
+    pub fn into_inner(self) -> O { self.value }
+}
+impl<E, P: Parsable<E>, F: TryMapFn<P, O>, O> Parsable<E> for MapRes<P, F, O> {
+    type Formatter = P::Formatter;
+    type Error = MapResError<P::Error, F::Error>;
+
+    #[inline]
+    fn expects() -> Self::Formatter { P::expects() }
+
+    #[inline]
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        map_res(input, |value: P| F::try_map(value).map(|value| Self { value, _f: PhantomData }))
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::{Length, Located};
+    use crate::nibble::test::{TestError, tag};
+
+    tag!(Hello, b"Hello");
+
+    /// Computes the length of a [`Hello`].
+    #[inline]
+    fn len(value: Hello) -> usize {
+        match value.loc().range.len {
+            Length::Fixed(len) => len as usize,
+            Length::Indefinite => unreachable!(),
+        }
+    }
+
+    /// Maps a [`Hello`] to its length.
+    struct HelloLen;
+    impl MapFn<Hello, usize> for HelloLen {
+        #[inline]
+        fn map(value: Hello) -> usize { len(value) }
+    }
+
+    /// Refuses to map a [`Hello`].
+    struct HelloNo;
+    impl TryMapFn<Hello, usize> for HelloNo {
+        type Error = TestError;
+
+        #[inline]
+        fn try_map(_value: Hello) -> Result<usize, Self::Error> { Err(TestError("HelloNo")) }
+    }
+
+    #[test]
+    fn test_map() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"Hello, world!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Goodbye".as_slice());
+
+        assert_eq!(map(input1, len), Ok((5, input1.slice(5..))));
+        assert_eq!(map(input2, len), Err(NibbleError::Unmatched(Hello::expects(), None)));
+
+        let (res, rem) = Map::<Hello, HelloLen, usize>::parse(input1).unwrap();
+        assert_eq!((res.into_inner(), rem), (5, input1.slice(5..)));
+    }
+
+    #[test]
+    fn test_map_res() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"Hello, world!".as_slice());
+
+        assert_eq!(map_res(input, |h: Hello| Ok::<usize, TestError>(len(h))), Ok((5, input.slice(5..))));
+        assert_eq!(
+            map_res(input, |_: Hello| Err::<usize, TestError>(TestError("Map"))),
+            Err(NibbleError::Error(MapResError::Map(TestError("Map"))))
+        );
+        assert_eq!(
+            MapRes::<Hello, HelloNo, usize>::parse(input).map(|(res, _)| res.into_inner()),
+            Err(NibbleError::Error(MapResError::Map(TestError("HelloNo"))))
+        );
+    }
+}
@@ -0,0 +1,57 @@
+//  MOD.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines combinators for building parsers out of other parsers.
+//!
+//!   Because [`Parsable`](super::Parsable) is a static trait (i.e., it has
+//!   no `self`), these come in two flavours:
+//!   - Wrapper types that implement [`Parsable`](super::Parsable)
+//!     themselves. These are fully determined by their type parameters, and
+//!     can thus be used anywhere a node can.
+//!   - Functions over a [`Slice`](super::Slice) that take additional runtime
+//!     arguments (e.g., closures), and which return the same kind of result
+//!     as [`Parsable::parse()`](super::Parsable::parse()).
+//
+
+// Modules
+mod cut;
+mod delimited;
+mod either;
+mod eof;
+#[cfg(feature = "tree")]
+mod ignore_case;
+mod labelled;
+mod many;
+mod map;
+mod one_of;
+mod peek;
+mod probe;
+mod recognize;
+mod recover;
+mod separated;
+mod streaming;
+mod take;
+mod verify;
+mod word;
+
+// Imports
+pub use cut::*;
+pub use delimited::*;
+pub use either::*;
+pub use eof::*;
+#[cfg(feature = "tree")]
+pub use ignore_case::*;
+pub use labelled::*;
+pub use many::*;
+pub use map::*;
+pub use one_of::*;
+pub use peek::*;
+pub use probe::*;
+pub use recognize::*;
+pub use recover::*;
+pub use separated::*;
+pub use streaming::*;
+pub use take::*;
+pub use verify::*;
+pub use word::*;
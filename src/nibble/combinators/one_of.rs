@@ -0,0 +1,195 @@
+//  ONE OF.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for parsing single elements that are (not) in
+//!   some set.
+//
+
+use std::convert::Infallible;
+use std::fmt::{Debug, Display, Formatter, Result as FResult};
+
+use super::super::error::Needed;
+use super::super::{NibbleError, Slice};
+use crate::loc::Loc;
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`one_of()`] and [`none_of()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct OneOfFormatter<E: 'static> {
+    /// The set of elements that were (not) allowed.
+    set:  &'static [E],
+    /// Whether this was [`none_of()`] rather than [`one_of()`].
+    none: bool,
+}
+impl<E: 'static> Display for OneOfFormatter<E>
+where
+    &'static [E]: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "{} of {:?}", if self.none { "none" } else { "one" }, self.set) }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Implements both [`one_of()`] and [`none_of()`].
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `set`: The set of elements to compare with.
+/// - `none`: Whether the head must _not_ be in the set.
+///
+/// # Returns
+/// The matched head element, its [`Loc`] and the remainder of the input.
+///
+/// # Errors
+/// See [`one_of()`] and [`none_of()`].
+#[inline]
+#[allow(clippy::type_complexity)]
+fn parse_one_of<'a, E: PartialEq>(
+    input: Slice<'a, E>,
+    set: &'static [E],
+    none: bool,
+) -> Result<((&'a E, Loc), Slice<'a, E>), NibbleError<OneOfFormatter<E>, Infallible>> {
+    match input.head_ref_loc() {
+        (Some((head, loc)), rem) if set.contains(head) != none => Ok(((head, loc), rem)),
+        (Some(_), _) => Err(NibbleError::Unmatched(OneOfFormatter { set, none }, None)),
+        (None, _) => Err(NibbleError::Unmatched(OneOfFormatter { set, none }, Some(Needed::Bounded(1, 1)))),
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a single element that is in the given set.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `set`: The set of elements, any of which is accepted.
+///
+/// # Returns
+/// A tuple with the matched element and its [`Loc`], and the remainder of the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if the head of the input is not in `set`, or
+/// if the input is empty (in which case it needs exactly one more element).
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn one_of<'a, E: PartialEq>(
+    input: Slice<'a, E>,
+    set: &'static [E],
+) -> Result<((&'a E, Loc), Slice<'a, E>), NibbleError<OneOfFormatter<E>, Infallible>> {
+    parse_one_of(input, set, false)
+}
+
+/// Parses a single element that is _not_ in the given set.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `set`: The set of elements, none of which is accepted.
+///
+/// # Returns
+/// A tuple with the matched element and its [`Loc`], and the remainder of the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if the head of the input is in `set`, or if
+/// the input is empty (in which case it needs exactly one more element).
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn none_of<'a, E: PartialEq>(
+    input: Slice<'a, E>,
+    set: &'static [E],
+) -> Result<((&'a E, Loc), Slice<'a, E>), NibbleError<OneOfFormatter<E>, Infallible>> {
+    parse_one_of(input, set, true)
+}
+
+/// Convenience version of [`one_of()`] for bytes that returns the matched byte by value.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `set`: The set of bytes, any of which is accepted.
+///
+/// # Returns
+/// A tuple with the matched byte and its [`Loc`], and the remainder of the input.
+///
+/// # Errors
+/// See [`one_of()`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn one_of_bytes<'a>(
+    input: Slice<'a, u8>,
+    set: &'static [u8],
+) -> Result<((u8, Loc), Slice<'a, u8>), NibbleError<OneOfFormatter<u8>, Infallible>> {
+    parse_one_of(input, set, false).map(|((head, loc), rem)| ((*head, loc), rem))
+}
+
+/// Convenience version of [`none_of()`] for bytes that returns the matched byte by value.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `set`: The set of bytes, none of which is accepted.
+///
+/// # Returns
+/// A tuple with the matched byte and its [`Loc`], and the remainder of the input.
+///
+/// # Errors
+/// See [`none_of()`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn none_of_bytes<'a>(
+    input: Slice<'a, u8>,
+    set: &'static [u8],
+) -> Result<((u8, Loc), Slice<'a, u8>), NibbleError<OneOfFormatter<u8>, Infallible>> {
+    parse_one_of(input, set, true).map(|((head, loc), rem)| ((*head, loc), rem))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+
+    #[test]
+    fn test_one_of() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"*x".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"x".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"".as_slice());
+
+        let ((head, loc), rem) = one_of(input1, b"+-*/").unwrap();
+        assert_eq!((head, TestLoc(loc), rem), (&b'*', TestLoc(Loc::encapsulate_range(ID, 0..1)), input1.slice(1..)));
+        assert_eq!(one_of(input2, b"+-*/"), Err(NibbleError::Unmatched(OneOfFormatter { set: b"+-*/", none: false }, None)));
+        assert_eq!(one_of(input3, b"+-*/"), Err(NibbleError::Unmatched(OneOfFormatter { set: b"+-*/", none: false }, Some(Needed::Bounded(1, 1)))));
+
+        let ((head, loc), rem) = one_of_bytes(input1, b"+-*/").unwrap();
+        assert_eq!((head, TestLoc(loc), rem), (b'*', TestLoc(Loc::encapsulate_range(ID, 0..1)), input1.slice(1..)));
+        assert_eq!(one_of_bytes(input2, b"+-").unwrap_err().to_string(), "one of [43, 45]");
+    }
+
+    #[test]
+    fn test_none_of() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"x*".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"*".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"".as_slice());
+
+        let ((head, loc), rem) = none_of(input1, b"+-*/").unwrap();
+        assert_eq!((head, TestLoc(loc), rem), (&b'x', TestLoc(Loc::encapsulate_range(ID, 0..1)), input1.slice(1..)));
+        assert_eq!(none_of(input2, b"+-*/"), Err(NibbleError::Unmatched(OneOfFormatter { set: b"+-*/", none: true }, None)));
+        assert_eq!(none_of(input3, b"+-*/"), Err(NibbleError::Unmatched(OneOfFormatter { set: b"+-*/", none: true }, Some(Needed::Bounded(1, 1)))));
+
+        let ((head, _), _) = none_of_bytes(input1, b"+-*/").unwrap();
+        assert_eq!(head, b'x');
+        assert_eq!(none_of_bytes(input2, b"*").unwrap_err().to_string(), "none of [42]");
+    }
+}
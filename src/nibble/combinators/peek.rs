@@ -0,0 +1,267 @@
+//  PEEK.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for looking ahead in the input without
+//!   consuming it.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::marker::PhantomData;
+
+use super::super::{NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+#[cfg(feature = "tree")]
+use crate::tree::Tag;
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`Not::expects()`] and [`not()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct NotFormatter<F> {
+    /// The formatter of the node that may not follow.
+    fmt: F,
+}
+impl<F: Display> Display for NotFormatter<F> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        write!(f, "not ")?;
+        Display::fmt(&self.fmt, f)
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Checks whether the input starts with a `T`, without consuming anything.
+///
+/// Unlike [`Parsable::parse_peek()`] or `Option<T>`, this never advances the input, not even if
+/// `T` matched. Use it to disambiguate between alternatives before committing to one.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to look into.
+///
+/// # Returns
+/// True if `T` matched, or false if it was [`NibbleError::Unmatched`] (for whatever reason).
+///
+/// # Errors
+/// This function errors if `T::parse()` returned a [`NibbleError::Error`].
+#[inline]
+pub fn peek<T: Parsable<E>, E>(input: &Slice<E>) -> Result<bool, NibbleError<T::Formatter, T::Error>> {
+    match T::parse(*input) {
+        Ok(_) => Ok(true),
+        Err(NibbleError::Unmatched(_, _)) => Ok(false),
+        Err(NibbleError::Error(err)) => Err(NibbleError::Error(err)),
+    }
+}
+
+/// Checks whether the input starts with the [`Tag`] of `T`, without consuming anything.
+///
+/// This is a specialized version of [`peek()`] that simply compares the input with
+/// [`Tag::TAG`].
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to look into.
+///
+/// # Returns
+/// True if the input starts with `T::TAG`. Note that this is false if the input is shorter than
+/// the tag, even if it could still match with more input.
+#[cfg(feature = "tree")]
+#[inline]
+pub fn peek_tag<T: Tag<E>, E: 'static + PartialEq>(input: &Slice<E>) -> bool { input.starts_with(T::TAG) }
+
+
+
+
+
+/// Matches only if the input does _not_ start with a `T`, without consuming anything.
+///
+/// This is a negative lookahead, and composes with sequencing to build guards. For example,
+/// `(Not<Word<If>>, Ident)` parses an identifier that isn't the keyword `if`. See [`not()`] for
+/// the function version.
+///
+/// Note that the guard needs a boundary-checked keyword (see [`Word`]) for this. A plain
+/// `Not<If>` also rejects any identifier that merely starts with `if`, such as `iffy`.
+///
+/// If `T` is unmatched only because the input ended (i.e., with a [`Needed`]-hint), then it's not
+/// yet known whether `T` is absent. As such, this is unmatched too, with the same hint.
+///
+/// Its [`Loc`] is always empty, positioned where `T` was absent.
+///
+/// [`Needed`]: super::super::Needed
+/// [`Word`]: super::Word
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Not<T> {
+    /// The (empty) location where `T` was absent.
+    pub loc: Loc,
+    /// Remembers the node that was absent.
+    _t:      PhantomData<T>,
+}
+impl<T> Not<T> {
+    /// Constructor for the Not.
+    ///
+    /// # Arguments
+    /// - `loc`: The (empty) location where `T` was absent.
+    ///
+    /// # Returns
+    /// A new Not.
+    #[inline]
+    pub const fn new(loc: Loc) -> Self { Self { loc, _t: PhantomData } }
+}
+
+// Loc
+impl<T> Located for Not<T> {
+    #[inline]
+    fn loc(&self) -> Loc { self.loc }
+}
+
+// Parsing
+impl<E, T: Parsable<E>> Parsable<E> for Not<T> {
+    type Formatter = NotFormatter<T::Formatter>;
+    type Error = T::Error;
+
+    #[inline]
+    fn expects() -> Self::Formatter { NotFormatter { fmt: T::expects() } }
+
+    #[inline]
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        let ((), rem) = not::<T, E>(input)?;
+        let ((_, loc), _) = input.head_slice_loc(0);
+        Ok((Self::new(loc), rem))
+    }
+}
+
+/// Matches only if the input does _not_ start with a `T`, without consuming anything.
+///
+/// This is the function version of [`Not`]; see it for more information.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to look into.
+///
+/// # Returns
+/// A tuple with a unit and the input, untouched.
+///
+/// # Errors
+/// This function returns a [`NibbleError::Unmatched`] if `T` matched, or if `T` was unmatched
+/// with a [`Needed`]-hint (which is then passed on). It returns a [`NibbleError::Error`] if
+/// `T::parse()` did.
+///
+/// [`Needed`]: super::super::Needed
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn not<'a, T, E>(input: Slice<'a, E>) -> Result<((), Slice<'a, E>), NibbleError<NotFormatter<T::Formatter>, T::Error>>
+where
+    T: Parsable<E>,
+{
+    match T::parse(input) {
+        Ok(_) => Err(NibbleError::Unmatched(NotFormatter { fmt: T::expects() }, None)),
+        Err(NibbleError::Unmatched(fmt, Some(needed))) => Err(NibbleError::Unmatched(NotFormatter { fmt }, Some(needed))),
+        Err(NibbleError::Unmatched(_, None)) => Ok(((), input)),
+        Err(NibbleError::Error(err)) => Err(NibbleError::Error(err)),
+    }
+}
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::Needed;
+    use crate::nibble::combinators::Word;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Hello, b"Hello");
+    tag!(World, b"World");
+    tag!(If, b"if");
+    illegal!(Bad, b"Hello");
+
+    /// Parses one or more lowercase ASCII letters as an identifier.
+    #[derive(Debug, Eq, PartialEq)]
+    struct Ident(TestLoc);
+    impl Parsable<u8> for Ident {
+        type Formatter = &'static str;
+        type Error = TestError;
+
+        #[inline]
+        fn expects() -> Self::Formatter { "an identifier" }
+
+        #[inline]
+        fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+            let len: usize = input.iter().take_while(|b| b.is_ascii_lowercase()).count();
+            if len == 0 {
+                return Err(NibbleError::Unmatched(Self::expects(), None));
+            }
+            let ((_, loc), rem) = input.head_slice_loc(len);
+            Ok((Self(TestLoc(loc)), rem))
+        }
+    }
+
+    #[test]
+    fn test_peek() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"Hello".as_slice());
+
+        assert_eq!(peek::<Hello, u8>(&input), Ok(true));
+        assert_eq!(peek::<World, u8>(&input), Ok(false));
+        assert_eq!(peek::<Bad, u8>(&input), Err(NibbleError::Error(TestError("Bad"))));
+    }
+
+    #[test]
+    fn test_peek_tag() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"Hello".as_slice());
+
+        assert!(peek_tag::<Hello, u8>(&input));
+        assert!(!peek_tag::<World, u8>(&input));
+        assert!(!peek_tag::<Hello, u8>(&input.slice(1..)));
+
+        // The input is still fully available afterwards
+        assert_eq!(Hello::parse(input), Ok((Hello(TestLoc(Loc::encapsulate_range(ID, ..5))), input.slice(5..))));
+    }
+
+    #[test]
+    fn test_not() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"foo".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"if".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"Hello".as_slice());
+
+        assert_eq!(not::<If, u8>(input1), Ok(((), input1)));
+        assert_eq!(not::<If, u8>(input2), Err(NibbleError::Unmatched(NotFormatter { fmt: If::expects() }, None)));
+        assert_eq!(not::<If, u8>(input2).unwrap_err().to_string(), "not [105, 102]");
+        assert_eq!(not::<Bad, u8>(input3), Err(NibbleError::Error(TestError("Bad"))));
+
+        // As a guard
+        let ((not, ident), rem) = <(Not<If>, Ident)>::parse(input1).unwrap();
+        assert_eq!((TestLoc(not.loc()), ident, rem), (TestLoc(Loc::encapsulate_range(ID, ..0)), Ident(TestLoc(Loc::encapsulate_range(ID, ..3))), input1.slice(3..)));
+        assert!(matches!(<(Not<If>, Ident)>::parse(input2), Err(NibbleError::Unmatched(_, _))));
+    }
+
+    #[test]
+    fn test_not_word() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"iffy".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"if x".as_slice());
+
+        // A plain tag also rejects identifiers starting with the keyword...
+        assert!(matches!(<(Not<If>, Ident)>::parse(input1), Err(NibbleError::Unmatched(_, _))));
+        // ...but a word-bounded one doesn't
+        let ((_, ident), rem) = <(Not<Word<If>>, Ident)>::parse(input1).unwrap();
+        assert_eq!((ident, rem), (Ident(TestLoc(Loc::encapsulate_range(ID, ..4))), input1.slice(4..)));
+        assert!(matches!(<(Not<Word<If>>, Ident)>::parse(input2), Err(NibbleError::Unmatched(_, None))));
+    }
+
+    #[test]
+    fn test_not_needed() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"i".as_slice());
+
+        // We can't know yet whether it's absent
+        assert_eq!(not::<If, u8>(input), Err(NibbleError::Unmatched(NotFormatter { fmt: If::expects() }, Some(Needed::Bounded(1, 1)))));
+    }
+}
@@ -0,0 +1,89 @@
+//  PROBE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`Probe`], an optional parser that remembers why it didn't
+//!   match.
+//
+
+use super::super::error::Needed;
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** LIBRARY *****/
+/// Parses a `T` optionally, like [`Option`], but retains why it wasn't matched.
+///
+/// When parsed, this is `Ok(...)` if `T` matched, or `Err(...)` with the formatter and
+/// [`Needed`]-hint of `T`'s [`NibbleError::Unmatched`] if not. In the latter case, no input is
+/// consumed. [`NibbleError::Error`]s are propagated as usual.
+///
+/// `F` is the formatter of `T`, and is only a separate parameter to avoid having to name the
+/// element type. I.e., use it as `Probe<T, <T as Parsable<E>>::Formatter>`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Probe<T, F>(pub Result<T, (F, Option<Needed>)>);
+impl<T, F> Probe<T, F> {
+    /// Returns the result of the parse.
+    ///
+    /// # Returns
+    /// The parsed `T`, or the reason why it wasn't matched.
+    #[inline]
+    pub fn into_inner(self) -> Result<T, (F, Option<Needed>)> { self.0 }
+}
+
+// Parsing
+impl<E, T: Parsable<E>> Parsable<E> for Probe<T, T::Formatter> {
+    type Formatter = T::Formatter;
+    type Error = T::Error;
+
+    #[inline]
+    fn expects() -> Self::Formatter { T::expects() }
+
+    /// NOTE: This parser can never be [`NibbleError::Unmatched`] (as it will simply return
+    /// [`Err`] then).
+    #[inline]
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        match T::parse(input) {
+            Ok((value, rem)) => Ok((Self(Ok(value)), rem)),
+            Err(NibbleError::Unmatched(fmt, needed)) => Ok((Self(Err((fmt, needed))), input)),
+            Err(NibbleError::Error(err)) => Err(NibbleError::Error(err)),
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Hello, b"Hello");
+    illegal!(Bad, b"Hello");
+
+    type HelloProbe = Probe<Hello, <Hello as Parsable<u8>>::Formatter>;
+
+    #[test]
+    fn test_probe() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"Hello!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"World".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"Hel".as_slice());
+
+        assert_eq!(HelloProbe::parse(input1), Ok((Probe(Ok(Hello(TestLoc(Loc::encapsulate_range(ID, ..5))))), input1.slice(5..))));
+        assert_eq!(HelloProbe::parse(input2), Ok((Probe(Err((Hello::expects(), None))), input2)));
+        assert_eq!(HelloProbe::parse(input3), Ok((Probe(Err((Hello::expects(), Some(Needed::Bounded(2, 2))))), input3)));
+    }
+
+    #[test]
+    fn test_probe_error() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"Hello".as_slice());
+
+        assert_eq!(<Probe<Bad, <Bad as Parsable<u8>>::Formatter>>::parse(input).map(|(res, _)| res.0.is_ok()), Err(NibbleError::Error(TestError("Bad"))));
+    }
+}
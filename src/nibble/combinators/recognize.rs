@@ -0,0 +1,59 @@
+//  RECOGNIZE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`recognize()`], which parses something only for its
+//!   location.
+//
+
+use super::super::{NibbleError, Parsable, Slice};
+use crate::loc::Loc;
+
+
+/***** LIBRARY *****/
+/// Parses a `T`, but discards its value and only returns the [`Loc`] of what it consumed.
+///
+/// This is useful for tokens of which only the source text is interesting, e.g., identifiers or
+/// literals that are interpreted later.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the [`Loc`] spanning everything `T` consumed, and the remainder of the input.
+///
+/// # Errors
+/// This function errors exactly when `T::parse()` does, with the same error.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn recognize<'a, T: Parsable<E>, E>(input: Slice<'a, E>) -> Result<(Loc, Slice<'a, E>), NibbleError<T::Formatter, T::Error>> {
+    let (_, rem): (T, Slice<E>) = T::parse(input)?;
+    Ok((input.loc_until(&rem), rem))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Hello, b"Hello");
+    illegal!(Bad, b"Hello");
+
+    #[test]
+    fn test_recognize() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"Hello!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Goodbye".as_slice());
+
+        let (loc, rem) = recognize::<Hello, u8>(input1).unwrap();
+        assert_eq!((TestLoc(loc), rem), (TestLoc(Loc::encapsulate_range(ID, ..5)), input1.slice(5..)));
+        assert_eq!(recognize::<Hello, u8>(input2), Err(NibbleError::Unmatched(Hello::expects(), None)));
+        assert_eq!(recognize::<Bad, u8>(input1), Err(NibbleError::Error(TestError("Bad"))));
+    }
+}
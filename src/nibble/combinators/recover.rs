@@ -0,0 +1,132 @@
+//  RECOVER.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for recovering from errors, such that multiple
+//!   errors can be reported in one go.
+//
+
+use std::convert::Infallible;
+
+use super::super::{Errors, NibbleError, Parsable, Slice};
+
+
+/***** LIBRARY *****/
+/// Parses a `T`, but recovers from any [`NibbleError::Error`] it produces.
+///
+/// Note that this deliberately turns a fatal outcome into a non-fatal one: when `T` fails, the
+/// error is pushed to `errors` instead, and the input is skipped until the first element
+/// satisfying `sync` (e.g., a `;`). That element itself is not consumed, so that the surrounding
+/// grammar can parse it as usual. To guarantee progress, at least one element is always skipped.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `errors`: An [`Errors`] to which to push any recovered errors.
+/// - `sync`: Some predicate deciding which elements are synchronization points.
+///
+/// # Returns
+/// A tuple with the parsed value (or [`None`] if we recovered from an error) and the remainder of
+/// the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if `T` does. It never returns a
+/// [`NibbleError::Error`].
+#[allow(clippy::type_complexity)]
+pub fn recover_with<'a, T, E>(
+    input: Slice<'a, E>,
+    errors: &mut Errors<T::Error>,
+    sync: impl Fn(&E) -> bool,
+) -> Result<(Option<T>, Slice<'a, E>), NibbleError<T::Formatter, Infallible>>
+where
+    T: Parsable<E>,
+{
+    match T::parse(input) {
+        Ok((value, rem)) => Ok((Some(value), rem)),
+        Err(NibbleError::Unmatched(fmt, needed)) => Err(NibbleError::Unmatched(fmt, needed)),
+        Err(NibbleError::Error(err)) => {
+            errors.push(err);
+            let skip: usize = input.iter().skip(1).position(sync).map(|i| 1 + i).unwrap_or(input.len());
+            Ok((None, input.slice(skip..)))
+        },
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::combinators::Either;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Foo, b"foo");
+    tag!(Semi, b";");
+    illegal!(Bad, b"bad");
+
+    #[test]
+    fn test_recover_with() {
+        const ID: u64 = 0;
+        let mut input = Slice::with_raw_id(ID, b"foo;bad;foo;".as_slice());
+
+        // Parse the statements
+        let mut values: Vec<Option<Either<Bad, Foo>>> = Vec::new();
+        let mut errors: Errors<Either<TestError, _>> = Errors::new();
+        while !input.is_empty() {
+            let (value, rem) = recover_with::<Either<Bad, Foo>, _>(input, &mut errors, |b| *b == b';').unwrap();
+            let (_, rem) = Semi::parse(rem).unwrap();
+            values.push(value);
+            input = rem;
+        }
+
+        // Both the error and the subsequent statement are produced
+        assert_eq!(values, vec![
+            Some(Either::Right(Foo(TestLoc(Loc::encapsulate_range(ID, 0..3))))),
+            None,
+            Some(Either::Right(Foo(TestLoc(Loc::encapsulate_range(ID, 8..11)))))
+        ]);
+        assert_eq!(errors.into_iter().collect::<Vec<_>>(), vec![Either::Left(TestError("Bad"))]);
+    }
+
+    #[test]
+    fn test_recover_with_edges() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"bad".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"baz;".as_slice());
+
+        // Without a sync point, all input is skipped
+        let mut errors: Errors<TestError> = Errors::new();
+        let (value, rem) = recover_with::<Bad, _>(input1, &mut errors, |b| *b == b';').unwrap();
+        assert_eq!((value.is_none(), rem, errors.into_iter().collect::<Vec<_>>()), (true, input1.slice(3..), vec![TestError("Bad")]));
+        // Unmatched is passed as-is
+        let mut errors: Errors<TestError> = Errors::new();
+        assert_eq!(recover_with::<Bad, _>(input2, &mut errors, |b| *b == b';').map(|(v, r)| (v.is_none(), r)), Err(NibbleError::Unmatched("Bad", None)));
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn test_recover_with_errors() {
+        const ID: u64 = 0;
+        let mut input = Slice::with_raw_id(ID, b"bad;foo;bad;".as_slice());
+
+        // Parse the list, collecting all errors
+        let mut values: Vec<Option<Either<Bad, Foo>>> = Vec::new();
+        let mut errors: Errors<Either<TestError, _>> = Errors::new();
+        while !input.is_empty() {
+            let (value, rem) = recover_with::<Either<Bad, Foo>, _>(input, &mut errors, |b| *b == b';').unwrap();
+            let (_, rem) = Semi::parse(rem).unwrap();
+            values.push(value);
+            input = rem;
+        }
+
+        // The partial list is still there, but the errors win
+        assert_eq!(values, vec![None, Some(Either::Right(Foo(TestLoc(Loc::encapsulate_range(ID, 4..7))))), None]);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors.to_string(), "Illegal Bad\nIllegal Bad");
+        assert_eq!(errors.into_result(values).map(|v| v.len()).map_err(|e| e.len()), Err(2));
+    }
+}
@@ -0,0 +1,696 @@
+//  SEPARATED.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for parsing lists of values separated by some
+//!   other node.
+//
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, Formatter, Result as FResult};
+use std::hash::Hash;
+
+use thiserror::Error;
+
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted by [`separated_list()`] and [`separated_list1()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum SeparatedListError<E1, E2> {
+    /// One of the values failed to parse.
+    #[error(transparent)]
+    Value(E1),
+    /// One of the separators failed to parse.
+    #[error(transparent)]
+    Separator(E2),
+}
+
+
+
+/// Defines the errors emitted by [`separated_terminated()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum SeparatedTerminatedError<F1, F2, F3, E1, E2, E3> {
+    /// A value was followed by neither a separator nor the terminator.
+    #[error("Expected {sep} or {end}")]
+    Unterminated { sep: F2, end: F3 },
+    /// A separator was followed by neither a value nor (if allowed) the terminator.
+    #[error("Expected {value}")]
+    MissingValue { value: F1 },
+    /// A separator was followed by the terminator, but trailing separators are not allowed.
+    #[error("Unexpected trailing {sep} before {end}")]
+    Trailing { sep: F2, end: F3 },
+    /// One of the values failed to parse.
+    #[error(transparent)]
+    Value(E1),
+    /// One of the separators failed to parse.
+    #[error(transparent)]
+    Separator(E2),
+    /// The terminator failed to parse.
+    #[error(transparent)]
+    End(E3),
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`separated_list()`] and [`separated_list1()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct SeparatedListFormatter<F1, F2> {
+    /// The formatter of the values.
+    value: F1,
+    /// The formatter of the separators.
+    sep:   F2,
+    /// Whether at least one value was required.
+    one:   bool,
+}
+impl<F1: Display, F2: Display> Display for SeparatedListFormatter<F1, F2> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        write!(f, "{} or more occurrences of ", if self.one { "one" } else { "zero" })?;
+        Display::fmt(&self.value, f)?;
+        write!(f, " separated by ")?;
+        Display::fmt(&self.sep, f)
+    }
+}
+
+
+
+/// Formatter for [`separated_terminated()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct SeparatedTerminatedFormatter<F1, F2, F3> {
+    /// The formatter of the values.
+    value: F1,
+    /// The formatter of the separators.
+    sep:   F2,
+    /// The formatter of the terminator.
+    end:   F3,
+}
+impl<F1: Display, F2: Display, F3: Display> Display for SeparatedTerminatedFormatter<F1, F2, F3> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        write!(f, "zero or more occurrences of ")?;
+        Display::fmt(&self.value, f)?;
+        write!(f, " separated by ")?;
+        Display::fmt(&self.sep, f)?;
+        write!(f, " and terminated by ")?;
+        Display::fmt(&self.end, f)
+    }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Implements both [`separated_list()`] and [`separated_list1()`].
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `one`: Whether to require at least one value.
+///
+/// # Returns
+/// The parsed values and the remainder of the input.
+///
+/// # Errors
+/// See [`separated_list()`] and [`separated_list1()`].
+#[allow(clippy::type_complexity)]
+fn parse_separated<'a, T, S, E>(
+    input: Slice<'a, E>,
+    one: bool,
+) -> Result<(Vec<T>, Slice<'a, E>), NibbleError<SeparatedListFormatter<T::Formatter, S::Formatter>, SeparatedListError<T::Error, S::Error>>>
+where
+    T: Parsable<E>,
+    S: Parsable<E>,
+{
+    // Parse the first value, which decides if there's any list at all
+    let mut res: Vec<T> = Vec::new();
+    let mut input: Slice<E> = match T::parse(input) {
+        Ok((value, rem)) => {
+            res.push(value);
+            rem
+        },
+        Err(NibbleError::Unmatched(_, needed)) => {
+            if one {
+                return Err(NibbleError::Unmatched(SeparatedListFormatter { value: T::expects(), sep: S::expects(), one }, needed));
+            } else {
+                return Ok((res, input));
+            }
+        },
+        Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedListError::Value(err))),
+    };
+
+    // Then parse pairs of separators and values
+    loop {
+        let rem: Slice<E> = match S::parse(input) {
+            Ok((_, rem)) => rem,
+            Err(NibbleError::Unmatched(_, _)) => break,
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedListError::Separator(err))),
+        };
+        match T::parse(rem) {
+            Ok((value, rem)) => {
+                res.push(value);
+                input = rem;
+            },
+            // NOTE: We don't update `input`, so that the separator is not consumed
+            Err(NibbleError::Unmatched(_, _)) => break,
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedListError::Value(err))),
+        }
+    }
+    Ok((res, input))
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses zero or more `T`s that are separated by `S`s.
+///
+/// A trailing separator is not allowed. Rather, if a separator is not followed by a value, then
+/// the list ends _before_ that separator (i.e., it isn't consumed).
+///
+/// The separators themselves are discarded. See [`separated_list1()`] if you need at least one
+/// value.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed values and the remainder of the input, positioned after the last parsed
+/// value.
+///
+/// # Errors
+/// This function never returns [`NibbleError::Unmatched`]; if not even one value is found, an
+/// empty list is returned instead.
+///
+/// It returns a [`NibbleError::Error`] if any value or separator does so.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn separated_list<'a, T, S, E>(
+    input: Slice<'a, E>,
+) -> Result<(Vec<T>, Slice<'a, E>), NibbleError<SeparatedListFormatter<T::Formatter, S::Formatter>, SeparatedListError<T::Error, S::Error>>>
+where
+    T: Parsable<E>,
+    S: Parsable<E>,
+{
+    parse_separated::<T, S, E>(input, false)
+}
+
+/// Parses one or more `T`s that are separated by `S`s.
+///
+/// Like [`separated_list()`], a trailing separator is not allowed and won't be consumed.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed values and the remainder of the input, positioned after the last parsed
+/// value. The list is guaranteed to be non-empty.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if the first value was not matched, and a
+/// [`NibbleError::Error`] if any value or separator fails.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn separated_list1<'a, T, S, E>(
+    input: Slice<'a, E>,
+) -> Result<(Vec<T>, Slice<'a, E>), NibbleError<SeparatedListFormatter<T::Formatter, S::Formatter>, SeparatedListError<T::Error, S::Error>>>
+where
+    T: Parsable<E>,
+    S: Parsable<E>,
+{
+    parse_separated::<T, S, E>(input, true)
+}
+
+
+/// Parses zero or more key/value-pairs that are separated by `S`s into a [`BTreeMap`].
+///
+/// Every pair is a `K`, then a `KvSep` and then a `V`, e.g., `a = 1`. Like with
+/// [`separated_list()`], a trailing separator is not consumed. Once a key is matched, the rest of
+/// its pair is mandatory (see the tuple impl of [`Parsable`]).
+///
+/// See [`separated_hash_map()`] to parse into a [`HashMap`] instead.
+///
+/// # Duplicate keys
+/// If a key occurs multiple times, the last value wins. The key itself is kept from its first
+/// occurrence, however (which matters if it carries e.g. a [`Loc`](crate::loc::Loc)). This is
+/// what [`BTreeMap::insert()`] does.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed map and the remainder of the input, positioned after the last parsed
+/// pair.
+///
+/// # Errors
+/// This function never returns [`NibbleError::Unmatched`]; if not even one pair is found, an empty
+/// map is returned instead.
+///
+/// It returns a [`NibbleError::Error`] if any key, value or separator does so, or if a key is not
+/// followed by the rest of its pair.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn separated_map<'a, K, KvSep, V, S, E>(
+    input: Slice<'a, E>,
+) -> Result<
+    (BTreeMap<K, V>, Slice<'a, E>),
+    NibbleError<SeparatedListFormatter<<(K, KvSep, V) as Parsable<E>>::Formatter, S::Formatter>, SeparatedListError<<(K, KvSep, V) as Parsable<E>>::Error, S::Error>>,
+>
+where
+    K: Ord + Parsable<E>,
+    KvSep: Parsable<E>,
+    V: Parsable<E>,
+    S: Parsable<E>,
+    KvSep::Formatter: 'static + Debug,
+    V::Formatter: 'static + Debug,
+{
+    let (pairs, rem) = parse_separated::<(K, KvSep, V), S, E>(input, false)?;
+    // NOTE: We insert explicitly instead of collecting, which keeps the first key (`from_iter()`
+    // keeps the last one for BTreeMaps)
+    let mut res: BTreeMap<K, V> = BTreeMap::new();
+    for (k, _, v) in pairs {
+        res.insert(k, v);
+    }
+    Ok((res, rem))
+}
+
+/// Parses zero or more key/value-pairs that are separated by `S`s into a [`HashMap`].
+///
+/// This is exactly like [`separated_map()`], except that it builds a [`HashMap`]. Duplicate keys
+/// are handled the same: the last value wins, but the key of the first occurrence is kept.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed map and the remainder of the input, positioned after the last parsed
+/// pair.
+///
+/// # Errors
+/// See [`separated_map()`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn separated_hash_map<'a, K, KvSep, V, S, E>(
+    input: Slice<'a, E>,
+) -> Result<
+    (HashMap<K, V>, Slice<'a, E>),
+    NibbleError<SeparatedListFormatter<<(K, KvSep, V) as Parsable<E>>::Formatter, S::Formatter>, SeparatedListError<<(K, KvSep, V) as Parsable<E>>::Error, S::Error>>,
+>
+where
+    K: Eq + Hash + Parsable<E>,
+    KvSep: Parsable<E>,
+    V: Parsable<E>,
+    S: Parsable<E>,
+    KvSep::Formatter: 'static + Debug,
+    V::Formatter: 'static + Debug,
+{
+    let (pairs, rem) = parse_separated::<(K, KvSep, V), S, E>(input, false)?;
+    let mut res: HashMap<K, V> = HashMap::with_capacity(pairs.len());
+    for (k, _, v) in pairs {
+        res.insert(k, v);
+    }
+    Ok((res, rem))
+}
+
+/// Parses zero or more `T`s that are separated by `S`s, up to and including a terminating `End`.
+///
+/// This is useful for lists that are closed by some token, like the `a, b, c]` after the `[` in
+/// `[a, b, c]`. Unlike [`separated_list()`], once a value is parsed, the list must be properly
+/// terminated: it's a [`NibbleError::Error`] if it isn't.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `trailing`: Whether to allow a separator right before the `End`, e.g., `[a, b, c,]`.
+///
+/// # Returns
+/// A tuple with the parsed values and terminator, and the remainder of the input after the
+/// terminator.
+///
+/// # Errors
+/// This function returns a [`NibbleError::Unmatched`] if neither a value nor the `End` is found at
+/// the start of the input.
+///
+/// It returns a [`NibbleError::Error`] if any value, separator or terminator does so, or if the list
+/// is not properly terminated. See [`SeparatedTerminatedError`] for the cases.
+#[allow(clippy::type_complexity)]
+pub fn separated_terminated<'a, T, S, End, E>(
+    input: Slice<'a, E>,
+    trailing: bool,
+) -> Result<
+    ((Vec<T>, End), Slice<'a, E>),
+    NibbleError<
+        SeparatedTerminatedFormatter<T::Formatter, S::Formatter, End::Formatter>,
+        SeparatedTerminatedError<T::Formatter, S::Formatter, End::Formatter, T::Error, S::Error, End::Error>,
+    >,
+>
+where
+    T: Parsable<E>,
+    S: Parsable<E>,
+    End: Parsable<E>,
+{
+    // An immediate terminator means an empty list
+    match End::parse(input) {
+        Ok((end, rem)) => return Ok(((Vec::new(), end), rem)),
+        Err(NibbleError::Unmatched(_, _)) => {},
+        Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedTerminatedError::End(err))),
+    }
+
+    // Otherwise, there must be at least one value
+    let mut res: Vec<T> = Vec::new();
+    let mut input: Slice<E> = match T::parse(input) {
+        Ok((value, rem)) => {
+            res.push(value);
+            rem
+        },
+        Err(NibbleError::Unmatched(_, needed)) => {
+            return Err(NibbleError::Unmatched(SeparatedTerminatedFormatter { value: T::expects(), sep: S::expects(), end: End::expects() }, needed));
+        },
+        Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedTerminatedError::Value(err))),
+    };
+
+    // Then parse separators and values until we find the terminator
+    loop {
+        match End::parse(input) {
+            Ok((end, rem)) => return Ok(((res, end), rem)),
+            Err(NibbleError::Unmatched(_, _)) => {},
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedTerminatedError::End(err))),
+        }
+        input = match S::parse(input) {
+            Ok((_, rem)) => rem,
+            Err(NibbleError::Unmatched(_, _)) => {
+                return Err(NibbleError::Error(SeparatedTerminatedError::Unterminated { sep: S::expects(), end: End::expects() }));
+            },
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedTerminatedError::Separator(err))),
+        };
+        match T::parse(input) {
+            Ok((value, rem)) => {
+                res.push(value);
+                input = rem;
+                continue;
+            },
+            Err(NibbleError::Unmatched(_, _)) => {},
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedTerminatedError::Value(err))),
+        }
+
+        // The separator is not followed by a value; it's only OK if it's trailing
+        match End::parse(input) {
+            Ok((end, rem)) if trailing => return Ok(((res, end), rem)),
+            Ok(_) => return Err(NibbleError::Error(SeparatedTerminatedError::Trailing { sep: S::expects(), end: End::expects() })),
+            Err(NibbleError::Unmatched(_, _)) => return Err(NibbleError::Error(SeparatedTerminatedError::MissingValue { value: T::expects() })),
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(SeparatedTerminatedError::End(err))),
+        }
+    }
+}
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::combinators::Either;
+    use crate::nibble::error::Needed;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Foo, b"foo");
+    tag!(Comma, b",");
+    tag!(A, b"a");
+    tag!(B, b"b");
+    tag!(C, b"c");
+    tag!(Open, b"[");
+    tag!(Close, b"]");
+    tag!(Eq, b"=");
+    tag!(Semi, b";");
+    illegal!(Bad, b"bad");
+    illegal!(BadComma, b";");
+
+    /// Parses [`Foo`] but errors on [`Bad`].
+    struct FooOrBad;
+    impl Parsable<u8> for FooOrBad {
+        type Formatter = &'static str;
+        type Error = TestError;
+
+        #[inline]
+        fn expects() -> Self::Formatter { "foo or bad" }
+
+        #[inline]
+        fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+            match Bad::parse(input) {
+                Ok(_) => unreachable!(),
+                Err(NibbleError::Unmatched(_, _)) => match Foo::parse(input) {
+                    Ok((_, rem)) => Ok((Self, rem)),
+                    Err(NibbleError::Unmatched(_, needed)) => Err(NibbleError::Unmatched(Self::expects(), needed)),
+                },
+                Err(NibbleError::Error(err)) => Err(NibbleError::Error(err)),
+            }
+        }
+    }
+
+    /// Parses a single lowercase ASCII letter as a map key.
+    ///
+    /// Only the letter is compared, so that we can check which of two equal keys is kept.
+    #[derive(Debug)]
+    struct Key(u8, TestLoc);
+    impl std::cmp::Eq for Key {}
+    impl Hash for Key {
+        #[inline]
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) { self.0.hash(state) }
+    }
+    impl Ord for Key {
+        #[inline]
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering { self.0.cmp(&other.0) }
+    }
+    impl PartialEq for Key {
+        #[inline]
+        fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+    }
+    impl PartialOrd for Key {
+        #[inline]
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+    }
+    impl Parsable<u8> for Key {
+        type Formatter = &'static str;
+        type Error = TestError;
+
+        #[inline]
+        fn expects() -> Self::Formatter { "a key" }
+
+        #[inline]
+        fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+            match input.head() {
+                (Some(b), rem) if b.is_ascii_lowercase() => Ok((Self(b, TestLoc(input.loc_until(&rem))), rem)),
+                _ => Err(NibbleError::Unmatched(Self::expects(), None)),
+            }
+        }
+    }
+
+    /// Parses a single ASCII digit as a map value, but errors on `!`.
+    #[derive(Debug, Eq, PartialEq)]
+    struct Value(u8);
+    impl Parsable<u8> for Value {
+        type Formatter = &'static str;
+        type Error = TestError;
+
+        #[inline]
+        fn expects() -> Self::Formatter { "a value" }
+
+        #[inline]
+        fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+            match input.head() {
+                (Some(b), rem) if b.is_ascii_digit() => Ok((Self(b - b'0'), rem)),
+                (Some(b'!'), _) => Err(NibbleError::Error(TestError("Value"))),
+                _ => Err(NibbleError::Unmatched(Self::expects(), None)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_separated_list() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"foo".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"foo,foo,foo".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"foo,foo,".as_slice());
+        let input5 = Slice::with_raw_id(ID, b"foo,bar".as_slice());
+        let input6 = Slice::with_raw_id(ID, b"bar".as_slice());
+
+        assert_eq!(separated_list::<Foo, Comma, _>(input1), Ok((vec![], input1)));
+        assert_eq!(separated_list::<Foo, Comma, _>(input2), Ok((vec![Foo(TestLoc(Loc::encapsulate_range(ID, ..3)))], input2.slice(3..))));
+        assert_eq!(
+            separated_list::<Foo, Comma, _>(input3),
+            Ok((
+                vec![
+                    Foo(TestLoc(Loc::encapsulate_range(ID, ..3))),
+                    Foo(TestLoc(Loc::encapsulate_range(ID, 4..7))),
+                    Foo(TestLoc(Loc::encapsulate_range(ID, 8..11)))
+                ],
+                input3.slice(11..)
+            ))
+        );
+        // Trailing separators are not consumed
+        assert_eq!(
+            separated_list::<Foo, Comma, _>(input4),
+            Ok((vec![Foo(TestLoc(Loc::encapsulate_range(ID, ..3))), Foo(TestLoc(Loc::encapsulate_range(ID, 4..7)))], input4.slice(7..)))
+        );
+        assert_eq!(separated_list::<Foo, Comma, _>(input5), Ok((vec![Foo(TestLoc(Loc::encapsulate_range(ID, ..3)))], input5.slice(3..))));
+        assert_eq!(separated_list::<Foo, Comma, _>(input6), Ok((vec![], input6)));
+    }
+
+    #[test]
+    fn test_separated_list_error() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"bad".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"foo,bad".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"foo;foo".as_slice());
+
+        // Errors in values always propagate, also after separators
+        assert_eq!(separated_list::<Bad, Comma, _>(input1), Err(NibbleError::Error(SeparatedListError::Value(TestError("Bad")))));
+        assert_eq!(
+            separated_list::<FooOrBad, Comma, _>(input2).map(|(v, _)| v.len()),
+            Err(NibbleError::Error(SeparatedListError::Value(TestError("Bad"))))
+        );
+        // Errors in separators too
+        assert_eq!(separated_list::<Foo, BadComma, _>(input3).map(|(v, _)| v.len()), Err(NibbleError::Error(SeparatedListError::Separator(TestError("BadComma")))));
+    }
+
+    #[test]
+    fn test_separated_list1() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"foo,foo".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"bar".as_slice());
+
+        assert_eq!(
+            separated_list1::<Foo, Comma, _>(input1),
+            Err(NibbleError::Unmatched(SeparatedListFormatter { value: Foo::expects(), sep: Comma::expects(), one: true }, Some(Needed::Bounded(3, 3))))
+        );
+        assert_eq!(
+            separated_list1::<Foo, Comma, _>(input2),
+            Ok((vec![Foo(TestLoc(Loc::encapsulate_range(ID, ..3))), Foo(TestLoc(Loc::encapsulate_range(ID, 4..7)))], input2.slice(7..)))
+        );
+        assert_eq!(
+            separated_list1::<Foo, Comma, _>(input3),
+            Err(NibbleError::Unmatched(SeparatedListFormatter { value: Foo::expects(), sep: Comma::expects(), one: true }, None))
+        );
+        assert_eq!(
+            separated_list1::<Foo, Comma, _>(input3).unwrap_err().to_string(),
+            "one or more occurrences of [102, 111, 111] separated by [44]"
+        );
+    }
+
+    #[test]
+    fn test_separated_terminated() {
+        type Letter = Either<A, Either<B, C>>;
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"[a,b,c]".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"[]!".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"[a,b,]".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"x]".as_slice());
+
+        let (_, rem) = Open::parse(input1).unwrap();
+        let ((values, end), rem) = separated_terminated::<Letter, Comma, Close, _>(rem, false).unwrap();
+        assert_eq!(values, vec![
+            Either::Left(A(TestLoc(Loc::encapsulate_range(ID, 1..2)))),
+            Either::Right(Either::Left(B(TestLoc(Loc::encapsulate_range(ID, 3..4))))),
+            Either::Right(Either::Right(C(TestLoc(Loc::encapsulate_range(ID, 5..6))))),
+        ]);
+        assert_eq!((end, rem), (Close(TestLoc(Loc::encapsulate_range(ID, 6..7))), input1.slice(7..)));
+
+        // Empty lists
+        assert_eq!(
+            separated_terminated::<Letter, Comma, Close, _>(input2.slice(1..), false),
+            Ok(((vec![], Close(TestLoc(Loc::encapsulate_range(ID, 1..2)))), input2.slice(2..)))
+        );
+
+        // Trailing separators
+        assert_eq!(
+            separated_terminated::<Letter, Comma, Close, _>(input3.slice(1..), true).map(|((values, _), rem)| (values.len(), rem)),
+            Ok((2, input3.slice(6..)))
+        );
+        assert_eq!(
+            separated_terminated::<Letter, Comma, Close, _>(input3.slice(1..), false).map(|((values, _), _)| values.len()),
+            Err(NibbleError::Error(SeparatedTerminatedError::Trailing { sep: Comma::expects(), end: Close::expects() }))
+        );
+
+        // Not a list at all
+        assert_eq!(
+            separated_terminated::<Letter, Comma, Close, _>(input4, false),
+            Err(NibbleError::Unmatched(SeparatedTerminatedFormatter { value: Letter::expects(), sep: Comma::expects(), end: Close::expects() }, None))
+        );
+    }
+
+    #[test]
+    fn test_separated_terminated_error() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"a,a".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"a,]".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"a,x]".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"foo,bad]".as_slice());
+
+        // A missing terminator is a hard error
+        let err = separated_terminated::<A, Comma, Close, _>(input1, false).map(|((values, _), _)| values.len()).unwrap_err();
+        assert_eq!(err, NibbleError::Error(SeparatedTerminatedError::Unterminated { sep: Comma::expects(), end: Close::expects() }));
+        assert_eq!(err.to_string(), "Expected [44] or [93]");
+        assert_eq!(
+            separated_terminated::<A, Comma, Close, _>(input2, false).map(|((values, _), _)| values.len()),
+            Err(NibbleError::Error(SeparatedTerminatedError::Trailing { sep: Comma::expects(), end: Close::expects() }))
+        );
+        assert_eq!(
+            separated_terminated::<A, Comma, Close, _>(input3, true).map(|((values, _), _)| values.len()),
+            Err(NibbleError::Error(SeparatedTerminatedError::MissingValue { value: A::expects() }))
+        );
+        assert_eq!(
+            separated_terminated::<FooOrBad, Comma, Close, _>(input4, false).map(|((values, _), _)| values.len()),
+            Err(NibbleError::Error(SeparatedTerminatedError::Value(TestError("Bad"))))
+        );
+    }
+
+    #[test]
+    fn test_separated_map() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"a=1;b=2".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"b=1;a=2;b=3;".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"".as_slice());
+
+        // Flattens the maps such that the keys' Locs are compared too
+        let at = |pos: usize| TestLoc(Loc::encapsulate_range(ID, pos..pos + 1));
+        let flat = |map: BTreeMap<Key, Value>| map.into_iter().map(|(k, v)| (k.0, k.1, v.0)).collect::<Vec<_>>();
+
+        let (map, rem) = separated_map::<Key, Eq, Value, Semi, _>(input1).unwrap();
+        assert_eq!((flat(map), rem), (vec![(b'a', at(0), 1), (b'b', at(4), 2)], input1.slice(7..)));
+        // The last value wins but the first key is kept; the trailing separator is left alone
+        let (map, rem) = separated_map::<Key, Eq, Value, Semi, _>(input2).unwrap();
+        assert_eq!((flat(map), rem), (vec![(b'a', at(4), 2), (b'b', at(0), 3)], input2.slice(11..)));
+        assert_eq!(separated_map::<Key, Eq, Value, Semi, _>(input3), Ok((BTreeMap::new(), input3)));
+
+        // The same goes for hash maps
+        let (map, rem) = separated_hash_map::<Key, Eq, Value, Semi, _>(input2).unwrap();
+        assert_eq!((flat(map.into_iter().collect()), rem), (vec![(b'a', at(4), 2), (b'b', at(0), 3)], input2.slice(11..)));
+    }
+
+    #[test]
+    fn test_separated_map_error() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"a=1;b=!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"a=1;b:2".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"a=1;b=x".as_slice());
+
+        // Errors of values (or anything else in the pairs) are propagated
+        assert!(matches!(separated_map::<Key, Eq, Value, Semi, _>(input1), Err(NibbleError::Error(SeparatedListError::Value(_)))));
+        assert_eq!(
+            separated_map::<Key, Eq, Value, Semi, _>(input1).unwrap_err().to_string(),
+            "Illegal Value"
+        );
+        // As are incomplete pairs
+        assert_eq!(separated_map::<Key, Eq, Value, Semi, _>(input2).unwrap_err().to_string(), "Expected [61]");
+        assert_eq!(separated_map::<Key, Eq, Value, Semi, _>(input3).unwrap_err().to_string(), "Expected a value");
+    }
+}
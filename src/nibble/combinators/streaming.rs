@@ -0,0 +1,159 @@
+//  STREAMING.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements variants of the [`Option`]- and [`Vec`]-parsers that report
+//!   whether more input may have given a different result.
+//
+
+use super::super::error::Needed;
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** LIBRARY *****/
+/// Parses an optional `T`, reporting whether it was absent only because the input ended.
+///
+/// This is like parsing an [`Option<T>`], except that it surfaces the [`Needed`]-hint of the
+/// unmatched `T`. This allows callers parsing in chunks to distinguish "definitely none" from
+/// "none yet".
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed value and a [`Needed`]-hint, and the remainder of the input. The hint
+/// is only [`Some`] if `T` was unmatched but might have matched with more input.
+///
+/// # Errors
+/// This function returns a [`NibbleError::Error`] if `T` does. It never returns
+/// [`NibbleError::Unmatched`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn opt_with_needed<'a, T, E>(
+    input: Slice<'a, E>,
+) -> Result<((Option<T>, Option<Needed>), Slice<'a, E>), NibbleError<<Option<T> as Parsable<E>>::Formatter, T::Error>>
+where
+    T: Parsable<E>,
+{
+    match T::parse(input) {
+        Ok((value, rem)) => Ok(((Some(value), None), rem)),
+        Err(NibbleError::Unmatched(_, needed)) => Ok(((None, needed), input)),
+        Err(NibbleError::Error(err)) => Err(NibbleError::Error(err)),
+    }
+}
+
+/// Parses zero or more `T`s, reporting whether parsing stopped only because the input ended.
+///
+/// This is like parsing a [`Vec<T>`], except that it surfaces the [`Needed`]-hint of the first
+/// `T` that was unmatched.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed values and a [`Needed`]-hint, and the remainder of the input. The hint
+/// is only [`Some`] if the last `T` was unmatched but might have matched with more input.
+///
+/// # Errors
+/// This function returns a [`NibbleError::Error`] if any `T` does. It never returns
+/// [`NibbleError::Unmatched`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn many_with_needed<'a, T, E>(
+    mut input: Slice<'a, E>,
+) -> Result<((Vec<T>, Option<Needed>), Slice<'a, E>), NibbleError<<Vec<T> as Parsable<E>>::Formatter, T::Error>>
+where
+    T: Parsable<E>,
+{
+    // NOTE: I suspect it's quite optimal to avoid allocating until the first element. This
+    // because of the brute-force nature of the parser, and we'll probably see more failing calls
+    // then successful calls.
+    let mut res = Vec::new();
+    loop {
+        let (item, rem): (T, Slice<E>) = match T::parse(input) {
+            Ok(res) => res,
+            Err(NibbleError::Unmatched(_, needed)) => {
+                // Shrink to something efficient
+                res.shrink_to_fit();
+                return Ok(((res, needed), input));
+            },
+            Err(NibbleError::Error(err)) => return Err(NibbleError::Error(err)),
+        };
+
+        // Pre-size the list at the first element. Assuming every element consumes input, there can
+        // be at most as many as there is input left; any excess is shrunk away at the end.
+        if res.is_empty() {
+            res.reserve(1 + rem.len());
+        }
+
+        // Then push the item
+        res.push(item);
+        input = rem;
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Hello, b"Hello");
+    tag!(A, b"a");
+    illegal!(Bad, b"Hello");
+
+    #[test]
+    fn test_opt_with_needed() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"Hello!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Hel".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"World".as_slice());
+
+        assert_eq!(opt_with_needed::<Hello, _>(input1), Ok(((Some(Hello(TestLoc(Loc::encapsulate_range(ID, ..5)))), None), input1.slice(5..))));
+        // A truncated tag may still match
+        assert_eq!(opt_with_needed::<Hello, _>(input2), Ok(((None, Some(Needed::Bounded(2, 2))), input2)));
+        // But a diverging one never does
+        assert_eq!(opt_with_needed::<Hello, _>(input3), Ok(((None, None), input3)));
+        assert_eq!(opt_with_needed::<Bad, _>(input1).map(|_| ()), Err(NibbleError::Error(TestError("Bad"))));
+    }
+
+    #[test]
+    fn test_many_with_needed() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"HelloHelloHel".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"HelloHello!".as_slice());
+
+        assert_eq!(
+            many_with_needed::<Hello, _>(input1),
+            Ok((
+                (vec![Hello(TestLoc(Loc::encapsulate_range(ID, ..5))), Hello(TestLoc(Loc::encapsulate_range(ID, 5..10)))], Some(Needed::Bounded(2, 2))),
+                input1.slice(10..)
+            ))
+        );
+        assert_eq!(
+            many_with_needed::<Hello, _>(input2),
+            Ok(((vec![Hello(TestLoc(Loc::encapsulate_range(ID, ..5))), Hello(TestLoc(Loc::encapsulate_range(ID, 5..10)))], None), input2.slice(10..)))
+        );
+        assert_eq!(many_with_needed::<Bad, _>(input2).map(|_| ()), Err(NibbleError::Error(TestError("Bad"))));
+    }
+
+    #[test]
+    fn test_many_with_needed_capacity() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, [b'a'; 1000].as_slice());
+        let input2 = Slice::with_raw_id(ID, b"HelloHelloHelloHello!".as_slice());
+
+        // Elements consuming one input element fill the pre-sized list exactly
+        let (res, rem) = Vec::<A>::parse(input1).unwrap();
+        assert_eq!((res.len(), res.capacity(), rem.len()), (1000, 1000, 0));
+        // And for longer ones, the excess is shrunk away
+        let (res, rem) = Vec::<Hello>::parse(input2).unwrap();
+        assert_eq!((res.len(), res.capacity(), rem.len()), (4, 4, 1));
+    }
+}
@@ -0,0 +1,243 @@
+//  TAKE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for consuming elements in bulk based on a
+//!   predicate.
+//
+
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use super::super::error::Needed;
+use super::super::{NibbleError, Slice};
+use crate::loc::Loc;
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`take_while()`], [`take_while1()`] and [`take_until()`].
+///
+/// Because predicates are opaque, it can only describe that some elements matching it were
+/// expected.
+#[derive(Debug, Eq, PartialEq)]
+pub struct TakeFormatter;
+impl Display for TakeFormatter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "one or more matching elements") }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Consumes the longest prefix of the input for which all elements satisfy `pred`.
+///
+/// This may match zero elements, in which case an empty slice is returned. See [`take_while1()`]
+/// if you need at least one.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `pred`: Some predicate deciding whether to consume an element.
+///
+/// # Returns
+/// A tuple with the consumed elements and their [`Loc`], and the remainder of the input.
+///
+/// # Errors
+/// This function never errors.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn take_while<'a, E>(
+    input: Slice<'a, E>,
+    pred: impl Fn(&E) -> bool,
+) -> Result<((&'a [E], Loc), Slice<'a, E>), NibbleError<TakeFormatter, Infallible>> {
+    let len: usize = input.iter().take_while(|e| pred(e)).count();
+    Ok(input.head_slice_loc(len))
+}
+
+/// Consumes the longest non-empty prefix of the input for which all elements satisfy `pred`.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `pred`: Some predicate deciding whether to consume an element.
+///
+/// # Returns
+/// A tuple with the consumed elements and their [`Loc`], and the remainder of the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if the first element does not satisfy `pred`,
+/// or if the input is empty (in which case it needs at least one more element).
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn take_while1<'a, E>(
+    input: Slice<'a, E>,
+    pred: impl Fn(&E) -> bool,
+) -> Result<((&'a [E], Loc), Slice<'a, E>), NibbleError<TakeFormatter, Infallible>> {
+    match take_while(input, pred)? {
+        (([], _), _) => Err(NibbleError::Unmatched(TakeFormatter, if input.is_empty() { Some(Needed::AtLeast(1)) } else { None })),
+        res => Ok(res),
+    }
+}
+
+/// Consumes elements from the input until `pred` is first true.
+///
+/// The element satisfying `pred` itself is not consumed. If no such element is found, then the
+/// whole input is consumed.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `pred`: Some predicate deciding whether an element terminates the prefix.
+///
+/// # Returns
+/// A tuple with the consumed elements and their [`Loc`], and the remainder of the input.
+///
+/// # Errors
+/// This function never errors.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn take_until<'a, E>(
+    input: Slice<'a, E>,
+    pred: impl Fn(&E) -> bool,
+) -> Result<((&'a [E], Loc), Slice<'a, E>), NibbleError<TakeFormatter, Infallible>> {
+    take_while(input, |e| !pred(e))
+}
+
+
+/// Consumes the longest prefix of UTF-8 characters in the input that satisfy `pred`, collecting
+/// them into an owned [`String`].
+///
+/// This is convenient for identifier- or word-like tokens of which you want to keep the text.
+/// Decoding stops at the first element that isn't a (complete) valid UTF-8 character, as if it
+/// didn't satisfy `pred`.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `min`: The minimum number of characters to consume for this to match.
+/// - `pred`: Some predicate deciding whether to consume a character.
+///
+/// # Returns
+/// A tuple with the consumed text and its [`Loc`], and the remainder of the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if fewer than `min` characters satisfy
+/// `pred`. If that's because the input ended (possibly part-way through a character), then it
+/// needs at least the missing number of bytes more. It never returns a [`NibbleError::Error`].
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn take_string<'a>(
+    input: Slice<'a, u8>,
+    min: usize,
+    pred: impl Fn(char) -> bool,
+) -> Result<((String, Loc), Slice<'a, u8>), NibbleError<TakeFormatter, Infallible>> {
+    let mut res: String = String::new();
+    let mut count: usize = 0;
+    let mut rem: Slice<u8> = input;
+    while let (Some(c), next) = rem.head_char()
+        && pred(c)
+    {
+        res.push(c);
+        count += 1;
+        rem = next;
+    }
+    if count < min {
+        if rem.is_empty() {
+            return Err(NibbleError::Unmatched(TakeFormatter, Some(Needed::AtLeast(min - count))));
+        }
+
+        // The input may also end part-way through a character
+        let head: &[u8] = &rem[..rem.len().min(4)];
+        if let Err(err) = std::str::from_utf8(head)
+            && err.valid_up_to() == 0
+            && err.error_len().is_none()
+        {
+            let len: usize = match head[0] {
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                _ => 4,
+            };
+            // We need the rest of this character, and then at least a byte for every next one
+            return Err(NibbleError::Unmatched(TakeFormatter, Some(Needed::AtLeast(len - head.len() + min - count - 1))));
+        }
+        return Err(NibbleError::Unmatched(TakeFormatter, None));
+    }
+    Ok(((res, input.loc_until(&rem)), rem))
+}
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+
+    #[test]
+    fn test_take_while() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"abc123".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"123".as_slice());
+
+        let ((head, loc), rem) = take_while(input1, u8::is_ascii_alphabetic).unwrap();
+        assert_eq!((head, TestLoc(loc), rem), (b"abc".as_slice(), TestLoc(Loc::encapsulate_range(ID, ..3)), input1.slice(3..)));
+        let ((head, loc), rem) = take_while(input2, u8::is_ascii_alphabetic).unwrap();
+        assert_eq!((head, TestLoc(loc), rem), (b"".as_slice(), TestLoc(Loc::encapsulate_range(ID, ..0)), input2));
+        let ((head, _), rem) = take_while(input2, u8::is_ascii_digit).unwrap();
+        assert_eq!((head, rem), (b"123".as_slice(), input2.slice(3..)));
+    }
+
+    #[test]
+    fn test_take_while1() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"abc123".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"123".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"".as_slice());
+
+        let ((head, loc), rem) = take_while1(input1, u8::is_ascii_alphabetic).unwrap();
+        assert_eq!((head, TestLoc(loc), rem), (b"abc".as_slice(), TestLoc(Loc::encapsulate_range(ID, ..3)), input1.slice(3..)));
+        assert_eq!(take_while1(input2, u8::is_ascii_alphabetic), Err(NibbleError::Unmatched(TakeFormatter, None)));
+        assert_eq!(take_while1(input3, u8::is_ascii_alphabetic), Err(NibbleError::Unmatched(TakeFormatter, Some(Needed::AtLeast(1)))));
+    }
+
+    #[test]
+    fn test_take_until() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"hello\" world".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"hello".as_slice());
+
+        let ((head, loc), rem) = take_until(input1, |b| *b == b'"').unwrap();
+        assert_eq!((head, TestLoc(loc), rem), (b"hello".as_slice(), TestLoc(Loc::encapsulate_range(ID, ..5)), input1.slice(5..)));
+        let ((head, _), rem) = take_until(input2, |b| *b == b'"').unwrap();
+        assert_eq!((head, rem), (b"hello".as_slice(), input2.slice(5..)));
+    }
+
+    #[test]
+    fn test_take_string() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"abc123 def".as_slice());
+        let input2 = Slice::with_raw_id(ID, "héllo!".as_bytes());
+        let input3 = Slice::with_raw_id(ID, b"ab".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"ab!".as_slice());
+
+        let ((text, loc), rem) = take_string(input1, 1, char::is_alphanumeric).unwrap();
+        assert_eq!((text.as_str(), TestLoc(loc), rem), ("abc123", TestLoc(Loc::encapsulate_range(ID, ..6)), input1.slice(6..)));
+        let ((text, loc), rem) = take_string(input2, 1, char::is_alphanumeric).unwrap();
+        assert_eq!((text.as_str(), TestLoc(loc), rem), ("héllo", TestLoc(Loc::encapsulate_range(ID, ..6)), input2.slice(6..)));
+        let ((text, _), rem) = take_string(input4, 0, char::is_numeric).unwrap();
+        assert_eq!((text.as_str(), rem), ("", input4));
+
+        // Too short
+        assert_eq!(take_string(input3, 3, char::is_alphanumeric), Err(NibbleError::Unmatched(TakeFormatter, Some(Needed::AtLeast(1)))));
+        assert_eq!(take_string(input4, 3, char::is_alphanumeric), Err(NibbleError::Unmatched(TakeFormatter, None)));
+
+        // Cut off part-way through a character
+        let input5 = Slice::with_raw_id(ID, b"a\xC3".as_slice());
+        let input6 = Slice::with_raw_id(ID, b"a\xE2\x82".as_slice());
+        let input7 = Slice::with_raw_id(ID, b"a\xFF".as_slice());
+        assert_eq!(take_string(input5, 2, char::is_alphanumeric), Err(NibbleError::Unmatched(TakeFormatter, Some(Needed::AtLeast(1)))));
+        assert_eq!(take_string(input5, 3, char::is_alphanumeric), Err(NibbleError::Unmatched(TakeFormatter, Some(Needed::AtLeast(2)))));
+        assert_eq!(take_string(input6, 2, char::is_alphanumeric), Err(NibbleError::Unmatched(TakeFormatter, Some(Needed::AtLeast(1)))));
+        // But invalid UTF-8 can never be fixed
+        assert_eq!(take_string(input7, 2, char::is_alphanumeric), Err(NibbleError::Unmatched(TakeFormatter, None)));
+    }
+}
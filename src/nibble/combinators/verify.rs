@@ -0,0 +1,175 @@
+//  VERIFY.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for validating parsed values against some
+//!   predicate.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use thiserror::Error;
+
+use super::super::error::ResultExt;
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted by [`verify_or_error()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum VerifyError<F, E> {
+    /// The value was parsed, but did not pass verification.
+    #[error("Expected {0}")]
+    Failed(VerifyFormatter<F>),
+    /// The nested parser failed.
+    #[error(transparent)]
+    Inner(E),
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`verify()`] and [`verify_or_error()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct VerifyFormatter<F> {
+    /// The formatter of the verified node.
+    fmt: F,
+}
+impl<F: Display> Display for VerifyFormatter<F> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        Display::fmt(&self.fmt, f)?;
+        write!(f, " (that passes verification)")
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a `T`, and then checks whether it satisfies some predicate.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `pred`: The predicate that the parsed value must satisfy.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if `T` does, or if the value does not satisfy
+/// `pred`. The latter allows alternatives to be tried; see [`verify_or_error()`] if failing the
+/// predicate is always illegal.
+///
+/// It returns a [`NibbleError::Error`] if `T` does.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn verify<'a, T, E>(
+    input: Slice<'a, E>,
+    pred: impl FnOnce(&T) -> bool,
+) -> Result<(T, Slice<'a, E>), NibbleError<VerifyFormatter<T::Formatter>, T::Error>>
+where
+    T: Parsable<E>,
+{
+    let (value, rem): (T, Slice<E>) = T::parse(input).map_fmt(|fmt| VerifyFormatter { fmt })?;
+    if pred(&value) { Ok((value, rem)) } else { Err(NibbleError::Unmatched(VerifyFormatter { fmt: T::expects() }, None)) }
+}
+
+/// Parses a `T`, and then checks whether it satisfies some predicate, erroring if it doesn't.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `pred`: The predicate that the parsed value must satisfy.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if `T` does. Unlike [`verify()`], it returns
+/// a [`NibbleError::Error`] with [`VerifyError::Failed`] if the value does not satisfy `pred`.
+///
+/// It also returns a [`NibbleError::Error`] if `T` does.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn verify_or_error<'a, T, E>(
+    input: Slice<'a, E>,
+    pred: impl FnOnce(&T) -> bool,
+) -> Result<(T, Slice<'a, E>), NibbleError<VerifyFormatter<T::Formatter>, VerifyError<T::Formatter, T::Error>>>
+where
+    T: Parsable<E>,
+{
+    let (value, rem): (T, Slice<E>) = T::parse(input).map_err(|err| err.map_fmt(|fmt| VerifyFormatter { fmt }).map_nerr(VerifyError::Inner))?;
+    if pred(&value) { Ok((value, rem)) } else { Err(NibbleError::Error(VerifyError::Failed(VerifyFormatter { fmt: T::expects() }))) }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+
+    /// The keywords that aren't valid identifiers.
+    const KEYWORDS: [&[u8]; 2] = [b"if", b"else"];
+
+    /// Parses a (non-empty) sequence of alphabetic characters.
+    #[derive(Debug, Eq, PartialEq)]
+    struct Ident(Vec<u8>);
+    impl Parsable<u8> for Ident {
+        type Formatter = &'static str;
+        type Error = Infallible;
+
+        #[inline]
+        fn expects() -> Self::Formatter { "an identifier" }
+
+        #[inline]
+        fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+            let len: usize = input.iter().take_while(|b| b.is_ascii_alphabetic()).count();
+            if len == 0 {
+                return Err(NibbleError::Unmatched(Self::expects(), None));
+            }
+            let (head, rem) = input.head_slice(len);
+            Ok((Self(head.to_vec()), rem))
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"foo bar".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"if bar".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"123".as_slice());
+
+        let not_keyword = |i: &Ident| !KEYWORDS.contains(&i.0.as_slice());
+        assert_eq!(verify(input1, not_keyword).map(|(i, rem)| (i.0, rem)), Ok((b"foo".to_vec(), input1.slice(3..))));
+        assert_eq!(verify(input2, not_keyword).map(|(i, rem)| (i.0, rem)), Err(NibbleError::Unmatched(VerifyFormatter { fmt: "an identifier" }, None)));
+        assert_eq!(verify(input3, not_keyword).map(|(i, rem)| (i.0, rem)), Err(NibbleError::Unmatched(VerifyFormatter { fmt: "an identifier" }, None)));
+        assert_eq!(verify(input2, not_keyword).map(|(i, _)| i.0).unwrap_err().to_string(), "an identifier (that passes verification)");
+    }
+
+    #[test]
+    fn test_verify_or_error() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"foo bar".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"else".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"123".as_slice());
+
+        let not_keyword = |i: &Ident| !KEYWORDS.contains(&i.0.as_slice());
+        assert_eq!(verify_or_error(input1, not_keyword).map(|(i, rem)| (i.0, rem)), Ok((b"foo".to_vec(), input1.slice(3..))));
+        assert_eq!(
+            verify_or_error(input2, not_keyword).map(|(i, rem)| (i.0, rem)),
+            Err(NibbleError::Error(VerifyError::Failed(VerifyFormatter { fmt: "an identifier" })))
+        );
+        assert_eq!(
+            verify_or_error(input3, not_keyword).map(|(i, rem)| (i.0, rem)),
+            Err(NibbleError::Unmatched(VerifyFormatter { fmt: "an identifier" }, None))
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//  WORD.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements combinators for parsing nodes (typically keywords) only if
+//!   they end at a word boundary.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::marker::PhantomData;
+
+use super::super::error::ResultExt;
+use super::super::{NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`Word::expects()`] and [`word()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct WordFormatter<F> {
+    /// The formatter of the nested node.
+    fmt: F,
+}
+impl<F: Display> Display for WordFormatter<F> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        Display::fmt(&self.fmt, f)?;
+        write!(f, " (as a whole word)")
+    }
+}
+
+
+
+
+
+/***** INTERFACES *****/
+/// Decides which elements continue a word, and thus may not follow a [`Word`].
+pub trait Boundary {
+    /// Checks whether the given element continues the word before it.
+    ///
+    /// # Arguments
+    /// - `elem`: The element directly following the parsed node.
+    ///
+    /// # Returns
+    /// True if `elem` is part of the same word (i.e., there is no boundary), or false otherwise.
+    fn is_continuation(elem: u8) -> bool;
+}
+
+
+
+/// The default [`Boundary`], which matches identifier-like words.
+///
+/// In particular, ASCII alphanumeric characters and underscores continue a word.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct IdentBoundary;
+impl Boundary for IdentBoundary {
+    #[inline]
+    fn is_continuation(elem: u8) -> bool { elem.is_ascii_alphanumeric() || elem == b'_' }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a `T`, but only if it ends at a word boundary as decided by `B`.
+///
+/// This is useful for keywords, which shouldn't match the start of identifiers (e.g., `if` in
+/// `iffy`). See [`word()`] to decide the boundary with a closure instead.
+///
+/// The [`Loc`] of the resulting node only covers `T`, not the boundary.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Word<T, B = IdentBoundary>(pub T, pub PhantomData<B>);
+impl<T, B> Word<T, B> {
+    /// Returns the parsed node.
+    ///
+    /// # Returns
+    /// The `T` that was parsed.
+    #[inline]
+    pub fn into_inner(self) -> T { self.0 }
+}
+
+// Loc
+impl<T: Located, B> Located for Word<T, B> {
+    #[inline]
+    fn loc(&self) -> Loc { self.0.loc() }
+}
+
+// Parsing
+impl<T: Parsable<u8>, B: Boundary> Parsable<u8> for Word<T, B> {
+    type Formatter = WordFormatter<T::Formatter>;
+    type Error = T::Error;
+
+    #[inline]
+    fn expects() -> Self::Formatter { WordFormatter { fmt: T::expects() } }
+
+    #[inline]
+    fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+        word(input, B::is_continuation).map(|(value, rem)| (Self(value, PhantomData), rem))
+    }
+}
+
+
+
+/// Parses a `T`, but only if it is not directly followed by an element continuing the word.
+///
+/// Note that the end of the input counts as a word boundary.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `is_continuation`: Decides whether the element after `T` continues the word. See
+///   [`IdentBoundary`] for the usual choice.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input. The boundary itself is not
+/// consumed.
+///
+/// # Errors
+/// This function returns [`NibbleError::Unmatched`] if `T` does, or if `T` is followed by an
+/// element continuing the word. It returns a [`NibbleError::Error`] if `T` does.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn word<'a, T>(
+    input: Slice<'a, u8>,
+    is_continuation: impl FnOnce(u8) -> bool,
+) -> Result<(T, Slice<'a, u8>), NibbleError<WordFormatter<T::Formatter>, T::Error>>
+where
+    T: Parsable<u8>,
+{
+    let (value, rem): (T, Slice<u8>) = T::parse(input).map_fmt(|fmt| WordFormatter { fmt })?;
+    match rem.peek() {
+        Some(next) if is_continuation(*next) => Err(NibbleError::Unmatched(WordFormatter { fmt: T::expects() }, None)),
+        _ => Ok((value, rem)),
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::tag;
+
+    tag!(If, b"if");
+
+    /// A boundary that also lets dashes continue words.
+    #[derive(Debug, Eq, PartialEq)]
+    struct DashBoundary;
+    impl Boundary for DashBoundary {
+        #[inline]
+        fn is_continuation(elem: u8) -> bool { IdentBoundary::is_continuation(elem) || elem == b'-' }
+    }
+
+    #[test]
+    fn test_word() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"if x".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"iffy".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"if_".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"if".as_slice());
+        let input5 = Slice::with_raw_id(ID, b"if-".as_slice());
+
+        assert_eq!(<Word<If>>::parse(input1), Ok((Word(If(TestLoc(Loc::encapsulate_range(ID, ..2))), PhantomData), input1.slice(2..))));
+        assert_eq!(<Word<If>>::parse(input2), Err(NibbleError::Unmatched(<Word<If>>::expects(), None)));
+        assert_eq!(<Word<If>>::parse(input3), Err(NibbleError::Unmatched(<Word<If>>::expects(), None)));
+        // The end of the input is a boundary too
+        assert_eq!(<Word<If>>::parse(input4), Ok((Word(If(TestLoc(Loc::encapsulate_range(ID, ..2))), PhantomData), input4.slice(2..))));
+        assert_eq!(<Word<If>>::parse(input5), Ok((Word(If(TestLoc(Loc::encapsulate_range(ID, ..2))), PhantomData), input5.slice(2..))));
+        assert_eq!(<Word<If>>::expects().to_string(), "[105, 102] (as a whole word)");
+    }
+
+    #[test]
+    fn test_word_boundary() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"if-".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"if+".as_slice());
+
+        assert_eq!(<Word<If, DashBoundary>>::parse(input1), Err(NibbleError::Unmatched(<Word<If, DashBoundary>>::expects(), None)));
+        assert!(<Word<If, DashBoundary>>::parse(input2).is_ok());
+        assert_eq!(word::<If>(input2, |b| b == b'+'), Err(NibbleError::Unmatched(<Word<If>>::expects(), None)));
+    }
+}
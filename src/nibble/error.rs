@@ -5,8 +5,14 @@
 //!   Defines errors for this crate.
 //
 
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FResult};
+
 use thiserror::Error;
 
+use super::fmt::WithSource;
+use crate::loc::Loc;
+
 
 /***** PRELUDE *****/
 /// Trait for conveniently calling [`NibbleError`]'s map functions through a [`Result`].
@@ -43,6 +49,27 @@ pub trait ResultExt<T, F, E> {
     /// # Returns
     /// An equivalent error but with a mapped `E`.
     fn map_nerr<F2: From<F>, E2>(self, map: impl FnOnce(E) -> E2) -> Result<T, NibbleError<F2, E2>>;
+
+    /// Allows one to call [`NibbleError::map_unmatched()`] through a [`Result`].
+    ///
+    /// # Arguments
+    /// - `map`: Some [`FnOnce`] doing the mapping.
+    ///
+    /// # Returns
+    /// An equivalent error but with a mapped `F` and [`Needed`].
+    fn map_unmatched<F2>(self, map: impl FnOnce(F, Option<Needed>) -> (F2, Option<Needed>)) -> Result<T, NibbleError<F2, E>>;
+
+    /// Transforms any [`NibbleError::Unmatched()`] into an [`Option`], and unwraps the
+    /// [`NibbleError::Error`] otherwise.
+    ///
+    /// This is a friendlier version of [`ResultExt::transpose()`] for when you don't need the
+    /// formatter anymore.
+    ///
+    /// # Returns
+    /// The same but now the result is [`Some`] if it wasn't an error, or [`None`] if
+    /// [`NibbleError::Unmatched`] was the error. Any [`NibbleError::Error`] is returned as a plain
+    /// `E`.
+    fn ok_unmatched(self) -> Result<Option<T>, E>;
 }
 impl<T, F, E> ResultExt<T, F, E> for Result<T, NibbleError<F, E>> {
     #[inline]
@@ -79,6 +106,23 @@ impl<T, F, E> ResultExt<T, F, E> for Result<T, NibbleError<F, E>> {
             Err(err) => Err(err.map_nerr(map)),
         }
     }
+
+    #[inline]
+    fn map_unmatched<F2>(self, map: impl FnOnce(F, Option<Needed>) -> (F2, Option<Needed>)) -> Result<T, NibbleError<F2, E>> {
+        match self {
+            Ok(res) => Ok(res),
+            Err(err) => Err(err.map_unmatched(map)),
+        }
+    }
+
+    #[inline]
+    fn ok_unmatched(self) -> Result<Option<T>, E> {
+        match self {
+            Ok(res) => Ok(Some(res)),
+            Err(NibbleError::Unmatched(_, _)) => Ok(None),
+            Err(NibbleError::Error(err)) => Err(err),
+        }
+    }
 }
 
 
@@ -109,6 +153,53 @@ pub enum Needed {
 
 // Needed
 impl Needed {
+    /// Merges this hint with another one, conservatively.
+    ///
+    /// This is useful when multiple alternatives failed, and you want to know how much input
+    /// _any_ of them needs. In particular:
+    /// - Two [`Needed::Bounded`]s become the union of both ranges;
+    /// - A [`Needed::AtLeast`] dominates a [`Needed::Bounded`], taking the lowest minimum of both;
+    ///   and
+    /// - [`Needed::Unknown`] dominates everything.
+    ///
+    /// # Arguments
+    /// - `other`: The other hint to merge with.
+    ///
+    /// # Returns
+    /// A new Needed that covers both `self` and `other`.
+    #[inline]
+    pub const fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Unknown, _) | (_, Self::Unknown) => Self::Unknown,
+            (Self::Bounded(lmin, lmax), Self::Bounded(rmin, rmax)) => {
+                Self::Bounded(if lmin <= rmin { lmin } else { rmin }, if lmax >= rmax { lmax } else { rmax })
+            },
+            (Self::Bounded(lmin, _) | Self::AtLeast(lmin), Self::Bounded(rmin, _) | Self::AtLeast(rmin)) => {
+                Self::AtLeast(if lmin <= rmin { lmin } else { rmin })
+            },
+        }
+    }
+
+    /// Merges two optional hints, where [`None`] means "more input won't help".
+    ///
+    /// If only one alternative may be helped by more input, then its hint is kept. Else, they are
+    /// merged with [`Needed::combine()`].
+    ///
+    /// # Arguments
+    /// - `lhs`: The one hint to merge.
+    /// - `rhs`: The other hint to merge.
+    ///
+    /// # Returns
+    /// The merged hint, or [`None`] if neither alternative is helped by more input.
+    #[inline]
+    pub const fn combine_opt(lhs: Option<Self>, rhs: Option<Self>) -> Option<Self> {
+        match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => Some(lhs.combine(rhs)),
+            (Some(needed), None) | (None, Some(needed)) => Some(needed),
+            (None, None) => None,
+        }
+    }
+
     /// Yields an [`Iterator::size_hint()`]-like size hint based on how many additional elements
     /// are needed.
     ///
@@ -212,6 +303,69 @@ impl<F, E> NibbleError<F, E> {
             Self::Error(err) => NibbleError::Error(map(err)),
         }
     }
+
+    /// Maps the `F`ormatter and [`Needed`]-hint in this NibbleError to something else.
+    ///
+    /// Does nothing if this is not a [`NibbleError::Unmatched`]. Unlike
+    /// [`NibbleError::map_fmt()`], the `E`rror is passed untouched.
+    ///
+    /// # Arguments
+    /// - `map`: Some closure mapping `F` and the hint to something else.
+    ///
+    /// # Returns
+    /// A new instance of `Self` with `F` and the hint mapped.
+    #[inline]
+    pub fn map_unmatched<F2>(self, map: impl FnOnce(F, Option<Needed>) -> (F2, Option<Needed>)) -> NibbleError<F2, E> {
+        match self {
+            Self::Unmatched(fmt, needed) => {
+                let (fmt, needed) = map(fmt, needed);
+                NibbleError::Unmatched(fmt, needed)
+            },
+            Self::Error(err) => NibbleError::Error(err),
+        }
+    }
+}
+
+// Accessors
+impl<F, E> NibbleError<F, E> {
+    /// Returns how much more input is needed for this error to (maybe) resolve.
+    ///
+    /// # Returns
+    /// The [`Needed`]-hint if this is a [`NibbleError::Unmatched`] that carries one, or [`None`]
+    /// otherwise.
+    #[inline]
+    pub const fn needed(&self) -> Option<Needed> {
+        match self {
+            Self::Unmatched(_, needed) => *needed,
+            Self::Error(_) => None,
+        }
+    }
+
+    /// Checks whether this error may be resolved by giving more input.
+    ///
+    /// This is useful for e.g. REPLs, which can then decide to read another line instead of
+    /// reporting the error.
+    ///
+    /// # Returns
+    /// True if this is a [`NibbleError::Unmatched`] with a [`Needed`]-hint, or false otherwise.
+    #[inline]
+    pub const fn is_incomplete(&self) -> bool { self.needed().is_some() }
+}
+
+// Formatting
+impl<F, E> NibbleError<F, E> {
+    /// Returns a formatter that displays this error together with a snippet of its source text.
+    ///
+    /// See [`render_error()`](super::fmt::render_error()) for what that looks like.
+    ///
+    /// # Arguments
+    /// - `source`: The source text that `loc` points into.
+    /// - `loc`: The [`Loc`] where the error occurred.
+    ///
+    /// # Returns
+    /// A [`WithSource`] that implements [`Display`].
+    #[inline]
+    pub const fn display_with<'a>(&'a self, source: &'a str, loc: Loc) -> WithSource<'a, F, E> { WithSource(self, source, loc) }
 }
 
 // Ops
@@ -226,3 +380,195 @@ impl<F: PartialEq, E: PartialEq> PartialEq for NibbleError<F, E> {
         }
     }
 }
+
+
+
+
+/// Collects multiple errors, such that they can be reported in one go.
+///
+/// Parsers that can recover from errors (see e.g.
+/// [`recover_with()`](super::combinators::recover_with())) push into this, and can then still
+/// produce a (partial) AST. Once done, use [`Errors::into_result()`] to decide whether the
+/// parse succeeded after all.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Errors<E> {
+    /// The errors collected so far.
+    errors: Vec<E>,
+}
+impl<E> Default for Errors<E> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl<E> Errors<E> {
+    /// Constructor for an empty Errors.
+    ///
+    /// # Returns
+    /// A new Errors without any errors in it.
+    #[inline]
+    pub const fn new() -> Self { Self { errors: Vec::new() } }
+
+    /// Adds an error to the collection.
+    ///
+    /// # Arguments
+    /// - `err`: The error to add.
+    #[inline]
+    pub fn push(&mut self, err: E) { self.errors.push(err); }
+
+    /// Returns the number of errors collected.
+    ///
+    /// # Returns
+    /// The number of errors in this collection.
+    #[inline]
+    pub fn len(&self) -> usize { self.errors.len() }
+
+    /// Checks whether any error was collected.
+    ///
+    /// # Returns
+    /// True if there are no errors in this collection, or false otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.errors.is_empty() }
+
+    /// Returns an iterator over the collected errors.
+    ///
+    /// # Returns
+    /// An iterator yielding references to the errors, in the order they were pushed.
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, E> { self.errors.iter() }
+
+    /// Decides on the result of a parse based on whether any errors were collected.
+    ///
+    /// # Arguments
+    /// - `value`: The value that was parsed.
+    ///
+    /// # Returns
+    /// The `value` if no errors were collected.
+    ///
+    /// # Errors
+    /// This function errors with itself if it is not [empty](Errors::is_empty()).
+    #[inline]
+    pub fn into_result<T>(self, value: T) -> Result<T, Self> { if self.is_empty() { Ok(value) } else { Err(self) } }
+}
+
+// Formatting
+impl<E: Display> Display for Errors<E> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        for (i, err) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            Display::fmt(err, f)?;
+        }
+        Ok(())
+    }
+}
+impl<E: StdError> StdError for Errors<E> {}
+
+// Iteration
+impl<E> IntoIterator for Errors<E> {
+    type Item = E;
+    type IntoIter = std::vec::IntoIter<E>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.errors.into_iter() }
+}
+impl<'a, E> IntoIterator for &'a Errors<E> {
+    type Item = &'a E;
+    type IntoIter = std::slice::Iter<'a, E>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter { self.errors.iter() }
+}
+impl<E> FromIterator<E> for Errors<E> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = E>>(iter: T) -> Self { Self { errors: iter.into_iter().collect() } }
+}
+impl<E> Extend<E> for Errors<E> {
+    #[inline]
+    fn extend<T: IntoIterator<Item = E>>(&mut self, iter: T) { self.errors.extend(iter) }
+}
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nibble_error_needed() {
+        let err: NibbleError<&str, ()> = NibbleError::Unmatched("foo", Some(Needed::Bounded(1, 1)));
+        assert_eq!((err.needed(), err.is_incomplete()), (Some(Needed::Bounded(1, 1)), true));
+        let err: NibbleError<&str, ()> = NibbleError::Unmatched("foo", None);
+        assert_eq!((err.needed(), err.is_incomplete()), (None, false));
+        let err: NibbleError<&str, ()> = NibbleError::Error(());
+        assert_eq!((err.needed(), err.is_incomplete()), (None, false));
+    }
+
+    #[test]
+    fn test_nibble_error_map_unmatched() {
+        let err: NibbleError<&str, &str> = NibbleError::Unmatched("foo", None);
+        assert_eq!(err.map_unmatched(|fmt, _| (fmt.len(), Some(Needed::Unknown))), NibbleError::Unmatched(3, Some(Needed::Unknown)));
+        // Errors are passed untouched
+        let err: NibbleError<&str, &str> = NibbleError::Error("bar");
+        assert_eq!(err.map_unmatched(|fmt, needed| (fmt.len(), needed)), NibbleError::Error("bar"));
+
+        // Same for results
+        let res: Result<(), NibbleError<&str, &str>> = Err(NibbleError::Unmatched("foo", None));
+        assert_eq!(res.map_unmatched(|fmt, needed| (fmt.len(), needed)), Err(NibbleError::Unmatched(3, None)));
+        let res: Result<(), NibbleError<&str, &str>> = Err(NibbleError::Error("bar"));
+        assert_eq!(res.map_unmatched(|fmt, needed| (fmt.len(), needed)), Err(NibbleError::Error("bar")));
+    }
+
+    #[test]
+    fn test_result_ok_unmatched() {
+        let res: Result<u32, NibbleError<&str, &str>> = Ok(42);
+        assert_eq!(res.ok_unmatched(), Ok(Some(42)));
+        let res: Result<u32, NibbleError<&str, &str>> = Err(NibbleError::Unmatched("foo", Some(Needed::Unknown)));
+        assert_eq!(res.ok_unmatched(), Ok(None));
+        // Errors are passed untouched
+        let res: Result<u32, NibbleError<&str, &str>> = Err(NibbleError::Error("bar"));
+        assert_eq!(res.ok_unmatched(), Err("bar"));
+    }
+
+    #[test]
+    fn test_needed_combine() {
+        // Bounded/Bounded is the union
+        assert_eq!(Needed::Bounded(1, 1).combine(Needed::Bounded(3, 5)), Needed::Bounded(1, 5));
+        assert_eq!(Needed::Bounded(2, 4).combine(Needed::Bounded(1, 3)), Needed::Bounded(1, 4));
+        // AtLeast dominates Bounded
+        assert_eq!(Needed::Bounded(1, 1).combine(Needed::AtLeast(2)), Needed::AtLeast(1));
+        assert_eq!(Needed::AtLeast(2).combine(Needed::Bounded(3, 4)), Needed::AtLeast(2));
+        assert_eq!(Needed::AtLeast(2).combine(Needed::AtLeast(1)), Needed::AtLeast(1));
+        // Unknown dominates everything
+        assert_eq!(Needed::Unknown.combine(Needed::Bounded(1, 1)), Needed::Unknown);
+        assert_eq!(Needed::Bounded(1, 1).combine(Needed::Unknown), Needed::Unknown);
+        assert_eq!(Needed::Unknown.combine(Needed::AtLeast(1)), Needed::Unknown);
+        assert_eq!(Needed::AtLeast(1).combine(Needed::Unknown), Needed::Unknown);
+        assert_eq!(Needed::Unknown.combine(Needed::Unknown), Needed::Unknown);
+    }
+
+    #[test]
+    fn test_needed_combine_opt() {
+        assert_eq!(Needed::combine_opt(Some(Needed::Bounded(1, 1)), Some(Needed::Bounded(2, 2))), Some(Needed::Bounded(1, 2)));
+        assert_eq!(Needed::combine_opt(Some(Needed::Bounded(1, 1)), None), Some(Needed::Bounded(1, 1)));
+        assert_eq!(Needed::combine_opt(None, Some(Needed::AtLeast(1))), Some(Needed::AtLeast(1)));
+        assert_eq!(Needed::combine_opt(None, None), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut errors: Errors<&str> = Errors::new();
+        assert!(errors.is_empty());
+        assert_eq!(errors.clone().into_result(42), Ok(42));
+
+        errors.push("Expected a statement");
+        errors.push("Expected an expression");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors.to_string(), "Expected a statement\nExpected an expression");
+        assert_eq!(errors.iter().copied().collect::<Vec<_>>(), vec!["Expected a statement", "Expected an expression"]);
+        assert_eq!(errors.clone().into_result(42), Err(errors.clone()));
+        assert_eq!(errors.into_iter().collect::<Vec<_>>(), vec!["Expected a statement", "Expected an expression"]);
+    }
+}
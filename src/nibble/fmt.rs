@@ -0,0 +1,251 @@
+//  FMT.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements rendering [`NibbleError`]s together with the source text
+//!   they occurred in.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult, Write as _};
+
+use super::{Needed, NibbleError};
+use crate::loc::Loc;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Resolves the byte range of a [`Loc`] in `source` to one that can be used to slice it.
+///
+/// Out-of-bounds positions are clamped to the end of `source`. Positions within a multi-byte
+/// [`char`] are widened to include that whole [`char`] (i.e., the start is rounded down and the
+/// end up).
+///
+/// # Arguments
+/// - `loc`: The [`Loc`] to resolve.
+/// - `source`: The source text that `loc` points into.
+///
+/// # Returns
+/// A tuple with the start (inclusive) and end (exclusive) byte positions. The end is never
+/// before the start.
+fn char_bounds(loc: &Loc, source: &str) -> (usize, usize) {
+    let len: usize = source.len();
+    let mut start: usize = usize::try_from(loc.start()).unwrap_or(usize::MAX).min(len);
+    let mut end: usize = usize::try_from(loc.end_in(len as u64)).unwrap_or(usize::MAX).clamp(start, len);
+    while !source.is_char_boundary(start) {
+        start -= 1;
+    }
+    while !source.is_char_boundary(end) {
+        end += 1;
+    }
+    (start, end)
+}
+
+/// Renders a snippet of the line in `source` that `loc` starts in, underlining `loc`.
+///
+/// # Arguments
+/// - `res`: The [`String`] to render to.
+/// - `loc`: The [`Loc`] to underline.
+/// - `source`: The source text that `loc` points into.
+fn render_snippet(res: &mut String, loc: &Loc, source: &str) {
+    // Find the line that the loc starts on
+    let len: usize = source.len();
+    let (start, end): (usize, usize) = char_bounds(loc, source);
+    let line_start: usize = source[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line_end: usize = source[start..].find('\n').map(|i| start + i).unwrap_or(len);
+    let line_no: usize = source[..line_start].matches('\n').count() + 1;
+    let line: &str = source[line_start..line_end].trim_end_matches('\r');
+
+    // Render the line, then underline the loc (within that line)
+    let prefix: String = line_no.to_string();
+    let indent: usize = source[line_start..start].chars().count();
+    let width: usize = source[start..end.min(line_start + line.len()).max(start)].chars().count().max(1);
+    let _ = writeln!(res, "{prefix} | {line}");
+    let _ = write!(res, "{} | {}{}", " ".repeat(prefix.len()), " ".repeat(indent), "^".repeat(width));
+}
+
+/// Renders what more input a [`Needed`] hints at.
+///
+/// # Arguments
+/// - `needed`: The [`Needed`] to render.
+///
+/// # Returns
+/// A [`String`] like `needs 2 more characters`.
+fn render_needed(needed: &Needed) -> String {
+    match *needed {
+        Needed::Bounded(min, max) if min == max => format!("needs {min} more character{}", if min == 1 { "" } else { "s" }),
+        Needed::Bounded(min, max) => format!("needs between {min} and {max} more characters"),
+        Needed::AtLeast(min) => format!("needs at least {min} more character{}", if min == 1 { "" } else { "s" }),
+        Needed::Unknown => "needs more characters".into(),
+    }
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Displays a [`NibbleError`] together with a snippet of the source text it occurred in.
+///
+/// This renders exactly like [`render_error()`], but can be used in formatting macros directly.
+/// The plain [`Display`] of a [`NibbleError`] stays minimal. You can also construct this with
+/// [`NibbleError::display_with()`].
+#[derive(Clone, Copy, Debug)]
+pub struct WithSource<'a, F, E>(
+    /// The error to display.
+    pub &'a NibbleError<F, E>,
+    /// The source text that the Loc points into.
+    pub &'a str,
+    /// The [`Loc`] where the error occurred.
+    pub Loc,
+);
+impl<'a, F: Display, E: Display> Display for WithSource<'a, F, E> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { f.write_str(&render_error(self.0, &self.2, self.1)) }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Renders a [`NibbleError`] together with a snippet of the source text it occurred in.
+///
+/// For [`NibbleError::Unmatched`], this says what was expected and what was found instead. For
+/// [`NibbleError::Error`], it shows the nested error. Both are followed by the line in `source`
+/// where `loc` is, with `loc` underlined; or by `<generated>` if `loc` is
+/// [synthetic](Loc::synthetic()).
+///
+/// # Arguments
+/// - `err`: The [`NibbleError`] to render.
+/// - `loc`: The [`Loc`] where the error occurred (e.g., the remainder of the input when parsing
+///   failed).
+/// - `source`: The source text that `loc` points into.
+///
+/// # Returns
+/// A [`String`] with the rendered error.
+pub fn render_error<F: Display, E: Display>(err: &NibbleError<F, E>, loc: &Loc, source: &str) -> String {
+    let mut res: String = String::new();
+    match err {
+        // Synthetic Locs have nothing to be found at
+        NibbleError::Unmatched(fmt, needed) if loc.is_synthetic() => {
+            let _ = write!(res, "Expected {fmt}");
+            if let Some(needed) = needed {
+                let _ = write!(res, " ({})", render_needed(needed));
+            }
+        },
+        NibbleError::Unmatched(fmt, needed) => {
+            let (start, end): (usize, usize) = char_bounds(loc, source);
+            match source.get(start..end) {
+                Some(found) if !found.is_empty() => {
+                    let _ = write!(res, "Expected {fmt} but found {found:?}");
+                },
+                _ if start >= source.len() => {
+                    let _ = write!(res, "Expected {fmt} but found end of input");
+                },
+                _ => {
+                    let _ = write!(res, "Expected {fmt}");
+                },
+            }
+            if let Some(needed) = needed {
+                let _ = write!(res, " ({})", render_needed(needed));
+            }
+        },
+        NibbleError::Error(err) => {
+            let _ = write!(res, "{err}");
+        },
+    }
+    res.push('\n');
+    if loc.is_synthetic() {
+        res.push_str("<generated>");
+    } else {
+        render_snippet(&mut res, loc, source);
+    }
+    res
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::nibble::test::{illegal, tag};
+    use crate::nibble::{Parsable as _, Slice};
+
+    tag!(Hello, b"Hello");
+    illegal!(Bad, b"let");
+
+    #[test]
+    fn test_render_error() {
+        const ID: u64 = 0;
+        let source: &str = "let x = 5;\nlet Hullo = 6;";
+
+        // An unmatched tag
+        let input = Slice::with_raw_id(ID, source.as_bytes()).slice(15..);
+        let err = Hello::parse(input).unwrap_err();
+        assert_eq!(
+            render_error(&err, &Loc::encapsulate_range(ID, 15..20), source),
+            "Expected [72, 101, 108, 108, 111] but found \"Hullo\"\n2 | let Hullo = 6;\n  |     ^^^^^"
+        );
+
+        // A nested error
+        let err = Bad::parse(Slice::with_raw_id(ID, source.as_bytes())).unwrap_err();
+        assert_eq!(render_error(&err, &Loc::encapsulate_range(ID, ..3), source), "Illegal Bad\n1 | let x = 5;\n  | ^^^");
+    }
+
+    #[test]
+    fn test_render_error_synthetic() {
+        let source: &str = "let x = 5;";
+
+        let err = Hello::parse(Slice::with_raw_id(0, source.as_bytes())).unwrap_err();
+        assert_eq!(render_error(&err, &Loc::synthetic(), source), "Expected [72, 101, 108, 108, 111]\n<generated>");
+        let err = Bad::parse(Slice::with_raw_id(0, source.as_bytes())).unwrap_err();
+        assert_eq!(render_error(&err, &Loc::synthetic(), source), "Illegal Bad\n<generated>");
+    }
+
+    #[test]
+    fn test_render_error_needed() {
+        const ID: u64 = 0;
+        let source: &str = "Hel";
+
+        let err = Hello::parse(Slice::with_raw_id(ID, source.as_bytes())).unwrap_err();
+        assert_eq!(
+            render_error(&err, &Loc::encapsulate_range(ID, 3..), source),
+            "Expected [72, 101, 108, 108, 111] but found end of input (needs 2 more characters)\n1 | Hel\n  |    ^"
+        );
+        assert_eq!(render_needed(&Needed::Bounded(1, 1)), "needs 1 more character");
+        assert_eq!(render_needed(&Needed::Bounded(1, 3)), "needs between 1 and 3 more characters");
+        assert_eq!(render_needed(&Needed::AtLeast(2)), "needs at least 2 more characters");
+    }
+
+    #[test]
+    fn test_render_error_multibyte() {
+        const ID: u64 = 0;
+        let source: &str = "é = 5;\nlet ü = ö;";
+
+        // Locs within a character cover all of it
+        let err: NibbleError<&str, &str> = NibbleError::Unmatched("x", None);
+        assert_eq!(render_error(&err, &Loc::encapsulate_range(ID, 1..2), "é"), "Expected x but found \"é\"\n1 | é\n  | ^");
+        assert_eq!(render_error(&err, &Loc::encapsulate_range(ID, 1..), source), "Expected x but found \"é = 5;\\nlet ü = ö;\"\n1 | é = 5;\n  | ^^^^^^");
+        assert_eq!(render_error(&err, &Loc::encapsulate_range(ID, 13..14), source), "Expected x but found \"ü\"\n2 | let ü = ö;\n  |     ^");
+        // Also when they start on the next line
+        assert_eq!(render_error(&err, &Loc::encapsulate_range(ID, 18..19), source), "Expected x but found \"ö\"\n2 | let ü = ö;\n  |         ^");
+        assert_eq!(err.display_with(source, Loc::encapsulate_range(ID, 18..)).to_string(), "Expected x but found \"ö;\"\n2 | let ü = ö;\n  |         ^^");
+    }
+
+    #[test]
+    fn test_with_source() {
+        const ID: u64 = 0;
+        let source: &str = "let x = 5;\nlet Hullo = 6;";
+
+        let err = Hello::parse(Slice::with_raw_id(ID, source.as_bytes()).slice(15..)).unwrap_err();
+        let rendered: String = err.display_with(source, Loc::encapsulate_range(ID, 15..20)).to_string();
+        assert!(rendered.contains(&format!("Expected {}", Hello::expects())));
+        assert!(rendered.contains("\"Hullo\""));
+        assert_eq!(rendered, render_error(&err, &Loc::encapsulate_range(ID, 15..20), source));
+        assert_eq!(format!("{}", WithSource(&err, source, Loc::encapsulate_range(ID, 15..20))), rendered);
+        // The plain Display remains minimal
+        assert_eq!(err.to_string(), Hello::expects().to_string());
+    }
+}
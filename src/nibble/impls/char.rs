@@ -0,0 +1,129 @@
+//  CHAR.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`Parsable`] for [`char`]s over UTF-8 encoded bytes.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use thiserror::Error;
+
+use super::super::{Needed, NibbleError, Parsable, Slice};
+use crate::loc::Loc;
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted when parsing a [`char`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum CharError {
+    /// The bytes at the head of the input are not valid UTF-8.
+    #[error("Invalid UTF-8")]
+    InvalidUtf8(Loc),
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`char`]'s [`Parsable::expects()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct CharFormatter;
+impl Display for CharFormatter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "a UTF-8 character") }
+}
+
+
+
+
+
+/***** IMPL *****/
+/// Parses a single UTF-8 encoded character, consuming all (1 to 4) bytes encoding it.
+///
+/// Use [`Slice::loc_until()`] to find the location of the encoded bytes.
+impl Parsable<u8> for char {
+    type Formatter = CharFormatter;
+    type Error = CharError;
+
+    #[inline]
+    fn expects() -> Self::Formatter { CharFormatter }
+
+    fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+        // A UTF-8 character is at most 4 bytes long
+        let head: &[u8] = &input[..input.len().min(4)];
+        let valid: &str = match std::str::from_utf8(head) {
+            Ok(valid) => valid,
+            Err(err) if err.valid_up_to() > 0 => std::str::from_utf8(&head[..err.valid_up_to()]).unwrap_or_default(),
+            Err(err) => match err.error_len() {
+                // The sequence is cut off by the end of the input
+                None => {
+                    let len: usize = match head[0] {
+                        0xC0..=0xDF => 2,
+                        0xE0..=0xEF => 3,
+                        _ => 4,
+                    };
+                    let needed: usize = len - head.len();
+                    return Err(NibbleError::Unmatched(CharFormatter, Some(Needed::Bounded(needed, needed))));
+                },
+                Some(len) => {
+                    let ((_, loc), _) = input.head_slice_loc(len);
+                    return Err(NibbleError::Error(CharError::InvalidUtf8(loc)));
+                },
+            },
+        };
+        match valid.chars().next() {
+            Some(c) => Ok((c, input.slice(c.len_utf8()..))),
+            None => Err(NibbleError::Unmatched(CharFormatter, Some(Needed::Bounded(1, 4)))),
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+
+    #[test]
+    fn test_char() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, "aé".as_bytes());
+        let input2 = Slice::with_raw_id(ID, "é!".as_bytes());
+        let input3 = Slice::with_raw_id(ID, "🦀".as_bytes());
+
+        assert_eq!(char::parse(input1), Ok(('a', input1.slice(1..))));
+        let (c, rem) = char::parse(input2).unwrap();
+        assert_eq!((c, TestLoc(input2.loc_until(&rem)), rem), ('é', TestLoc(Loc::encapsulate_range(ID, ..2)), input2.slice(2..)));
+        assert_eq!(char::parse(input3), Ok(('🦀', input3.slice(4..))));
+        assert_eq!(char::expects().to_string(), "a UTF-8 character");
+    }
+
+    #[test]
+    fn test_char_needed() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, &"é".as_bytes()[..1]);
+        let input2 = Slice::with_raw_id(ID, &"🦀".as_bytes()[..2]);
+        let input3 = Slice::with_raw_id(ID, b"".as_slice());
+
+        assert_eq!(char::parse(input1), Err(NibbleError::Unmatched(CharFormatter, Some(Needed::Bounded(1, 1)))));
+        assert_eq!(char::parse(input2), Err(NibbleError::Unmatched(CharFormatter, Some(Needed::Bounded(2, 2)))));
+        assert_eq!(char::parse(input3), Err(NibbleError::Unmatched(CharFormatter, Some(Needed::Bounded(1, 4)))));
+    }
+
+    #[test]
+    fn test_char_invalid() {
+        const ID: u64 = 0;
+        // A lone continuation byte, a lead byte followed by a non-continuation byte and an overlong encoding
+        for raw in [b"\x80a".as_slice(), b"\xC3a", b"\xC0\xAF"] {
+            let input = Slice::with_raw_id(ID, raw);
+            let Err(NibbleError::Error(CharError::InvalidUtf8(loc))) = char::parse(input) else { panic!("Expected an error for {raw:?}") };
+            assert_eq!(TestLoc(loc), TestLoc(Loc::encapsulate_range(ID, ..1)));
+        }
+    }
+}
@@ -6,7 +6,10 @@
 //
 
 // The modules doing the implementations
+mod char;
 mod option;
+mod ptr;
 #[cfg(feature = "tree")]
 mod tag;
+mod tuple;
 mod vec;
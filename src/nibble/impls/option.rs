@@ -42,13 +42,11 @@ impl<T: Parsable<E>, E> Parsable<E> for Option<T> {
     fn expects() -> Self::Formatter { OptionFormatter { fmt: T::expects() } }
 
     /// NOTE: This parser can never be [`NibbleError::Unmatched`] (as it will simply return
-    /// [`None`] then).
+    /// [`None`] then). See [`opt_with_needed()`](crate::nibble::combinators::opt_with_needed())
+    /// to find out if more input may have matched it.
     #[inline]
-    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
-        match T::parse(input) {
-            Ok((res, rem)) => Ok((Some(res), rem)),
-            Err(NibbleError::Unmatched(_, _)) => Ok((None, input)),
-            Err(NibbleError::Error(err)) => Err(NibbleError::Error(err)),
-        }
+    fn parse(mut input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        let res: Option<T> = T::parse_peek(&mut input).map_err(NibbleError::auto_map)?;
+        Ok((res, input))
     }
 }
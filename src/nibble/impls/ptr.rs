@@ -0,0 +1,80 @@
+//  PTR.rs
+//    by Lut99
+//
+//  Description:
+//!   Provides [`Parsable`] implementations for pointer-like types, like
+//!   [`Rc`] and [`Arc`].
+//!
+//!   These are useful for defining recursive nodes, which would otherwise
+//!   be infinitely sized.
+//!
+//!   Note that there is no implementation for [`Box`]. Because it is
+//!   `#[fundamental]`, downstream crates may implement
+//!   [`Tag`](crate::tree::Tag) for it, which would conflict with the blanket
+//!   implementation for tags.
+//
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::super::slice::Slice;
+use super::super::{NibbleError, Parsable};
+
+
+/***** HELPER MACROS *****/
+/// Implements [`Parsable`] for a pointer-like type that can be constructed with `new()`.
+macro_rules! ptr_impl {
+    ($ty:ident) => {
+        impl<T: Parsable<E>, E> Parsable<E> for $ty<T> {
+            type Formatter = T::Formatter;
+            type Error = T::Error;
+
+            #[inline]
+            fn expects() -> Self::Formatter { T::expects() }
+
+            #[inline]
+            fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+                let (res, rem): (T, Slice<E>) = T::parse(input)?;
+                Ok(($ty::new(res), rem))
+            }
+        }
+    };
+}
+
+
+
+
+
+/***** IMPL *****/
+ptr_impl!(Rc);
+ptr_impl!(Arc);
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::loc::{Loc, Located};
+    use crate::nibble::test::tag;
+
+    tag!(Hello, b"Hello");
+
+    #[test]
+    fn test_ptr() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"Hello!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Goodbye".as_slice());
+
+        let (res, rem) = Rc::<Hello>::parse(input1).unwrap();
+        assert_eq!((TestLoc(res.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..5)), input1.slice(5..)));
+        assert_eq!(*res, Hello(TestLoc(Loc::encapsulate_range(ID, ..5))));
+        assert_eq!(Rc::<Hello>::parse(input2).map(|(res, _)| res.loc()), Err(NibbleError::Unmatched(Hello::expects(), None)));
+
+        let (res, rem) = Arc::<Hello>::parse(input1).unwrap();
+        assert_eq!((TestLoc(res.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..5)), input1.slice(5..)));
+    }
+}
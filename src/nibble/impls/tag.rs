@@ -51,7 +51,7 @@ where
     fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
         // Get a slice of bytes equal to (at most) the tag size
         let ((head, loc), rem) = input.head_slice_loc(Self::TAG.len());
-        for (h, t) in head.into_iter().zip(Self::TAG.into_iter()) {
+        for (h, t) in head.iter().zip(Self::TAG.iter()) {
             if h != t {
                 // Divirging bytes. More input can never fix this!
                 return Err(NibbleError::Unmatched(Self::expects(), None));
@@ -89,7 +89,15 @@ mod tests {
             #[inline]
             fn loc(&self) -> Loc { self.0.into() }
         }
-        impl Node for Hello {}
+        impl Node for Hello {
+            #[inline]
+            fn kind(&self) -> crate::tree::NodeKind { crate::tree::NodeKind::Term }
+
+            #[inline]
+            fn walk(&self, visitor: &mut dyn crate::tree::visit::Visitor) -> std::ops::ControlFlow<()> {
+                crate::tree::visit::walk_term(self, visitor)
+            }
+        }
         impl Term for Hello {}
         impl Tag<u8> for Hello {
             const TAG: &'static [u8] = b"Hello";
@@ -116,5 +124,8 @@ mod tests {
         assert_eq!(Hello::parse(input3), Err(NibbleError::Unmatched(TagFormatter { _t: PhantomData }, Some(Needed::Bounded(1, 1)))));
         assert_eq!(Hello::parse(input4), Err(NibbleError::Unmatched(TagFormatter { _t: PhantomData }, None)));
         assert_eq!(Hello::parse(input5), Err(NibbleError::Unmatched(TagFormatter { _t: PhantomData }, Some(Needed::Bounded(5, 5)))));
+        assert_eq!(Hello::parse(input3).unwrap_err().needed(), Some(Needed::Bounded(1, 1)));
+        assert!(Hello::parse(input3).unwrap_err().is_incomplete());
+        assert!(!Hello::parse(input4).unwrap_err().is_incomplete());
     }
 }
@@ -0,0 +1,215 @@
+//  TUPLE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`Parsable`] for tuples of [`Parsable`] things, parsing them
+//!   in sequence.
+//
+
+use std::fmt::{Debug, Display, Formatter, Result as FResult};
+
+use thiserror::Error;
+
+use super::super::{NibbleError, Parsable, Slice};
+
+
+/***** HELPER MACROS *****/
+/// Implements [`Parsable`] for a tuple of the given arity, together with its error type.
+macro_rules! tuple_impl {
+    (
+        $err:ident;
+        ($t0:ident, $e0:ident, $v0:ident, $idx0:tt, $errv0:ident)
+        $(, ($t:ident, $f:ident, $e:ident, $v:ident, $idx:tt, $unmv:ident, $errv:ident))+
+    ) => {
+        /// Defines the errors emitted when parsing a tuple (i.e., a sequence) of nodes.
+        ///
+        /// The `F`s are the formatters of the nested nodes that may still be unmatched after the
+        /// first one matched; the `E`s are the errors of all nested nodes.
+        ///
+        /// Note that if a later node is unmatched only because the input ended early, the tuple
+        /// as a whole is [`NibbleError::Unmatched`] with its [`Needed`](super::super::Needed)-hint
+        /// instead.
+        #[derive(Debug, Eq, Error, PartialEq)]
+        pub enum $err<$($f,)+ $e0, $($e),+> {
+            $(
+                /// The node at this position was not matched, even though the preceding ones were.
+                #[error("Expected {0}")]
+                $unmv($f),
+            )+
+            /// The node at this position failed to parse.
+            #[error(transparent)]
+            $errv0($e0),
+            $(
+                /// The node at this position failed to parse.
+                #[error(transparent)]
+                $errv($e),
+            )+
+        }
+
+        impl<$t0: Display, $($t: Display),+> Display for TupleFormatter<($t0, $($t),+)> {
+            #[inline]
+            fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+                Display::fmt(&self.fmts.$idx0, f)?;
+                $(
+                    write!(f, ", then ")?;
+                    Display::fmt(&self.fmts.$idx, f)?;
+                )+
+                Ok(())
+            }
+        }
+
+        impl<E, $t0: Parsable<E>, $($t: Parsable<E>),+> Parsable<E> for ($t0, $($t),+)
+        where
+            $($t::Formatter: 'static + Debug,)+
+        {
+            type Formatter = TupleFormatter<($t0::Formatter, $($t::Formatter),+)>;
+            type Error = $err<$($t::Formatter,)+ $t0::Error, $($t::Error),+>;
+
+            #[inline]
+            fn expects() -> Self::Formatter { TupleFormatter { fmts: ($t0::expects(), $($t::expects()),+) } }
+
+            #[inline]
+            fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+                // The first element decides whether the sequence matches at all
+                let ($v0, rem): ($t0, Slice<E>) = match $t0::parse(input) {
+                    Ok(res) => res,
+                    Err(NibbleError::Unmatched(_, needed)) => return Err(NibbleError::Unmatched(Self::expects(), needed)),
+                    Err(NibbleError::Error(err)) => return Err(NibbleError::Error($err::$errv0(err))),
+                };
+
+                // After that, we're committed; unless we ran out of input, in which case more may
+                // still make it match
+                $(
+                    let ($v, rem): ($t, Slice<E>) = match $t::parse(rem) {
+                        Ok(res) => res,
+                        Err(NibbleError::Unmatched(_, Some(needed))) => return Err(NibbleError::Unmatched(Self::expects(), Some(needed))),
+                        Err(NibbleError::Unmatched(fmt, None)) => return Err(NibbleError::Error($err::$unmv(fmt))),
+                        Err(NibbleError::Error(err)) => return Err(NibbleError::Error($err::$errv(err))),
+                    };
+                )+
+                Ok((($v0, $($v),+), rem))
+            }
+        }
+    };
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Formatter for tuples of [`Parsable`] things.
+///
+/// `T` is a tuple of the formatters of the nested nodes.
+#[derive(Debug, Eq, PartialEq)]
+pub struct TupleFormatter<T> {
+    /// The nested formatters.
+    fmts: T,
+}
+
+
+
+
+
+/***** IMPL *****/
+tuple_impl!(Tuple2Error; (T0, E0, v0, 0, Error0), (T1, F1, E1, v1, 1, Unmatched1, Error1));
+tuple_impl!(Tuple3Error; (T0, E0, v0, 0, Error0), (T1, F1, E1, v1, 1, Unmatched1, Error1), (T2, F2, E2, v2, 2, Unmatched2, Error2));
+tuple_impl!(Tuple4Error; (T0, E0, v0, 0, Error0), (T1, F1, E1, v1, 1, Unmatched1, Error1), (T2, F2, E2, v2, 2, Unmatched2, Error2), (T3, F3, E3, v3, 3, Unmatched3, Error3));
+tuple_impl!(
+    Tuple5Error;
+    (T0, E0, v0, 0, Error0),
+    (T1, F1, E1, v1, 1, Unmatched1, Error1),
+    (T2, F2, E2, v2, 2, Unmatched2, Error2),
+    (T3, F3, E3, v3, 3, Unmatched3, Error3),
+    (T4, F4, E4, v4, 4, Unmatched4, Error4)
+);
+tuple_impl!(
+    Tuple6Error;
+    (T0, E0, v0, 0, Error0),
+    (T1, F1, E1, v1, 1, Unmatched1, Error1),
+    (T2, F2, E2, v2, 2, Unmatched2, Error2),
+    (T3, F3, E3, v3, 3, Unmatched3, Error3),
+    (T4, F4, E4, v4, 4, Unmatched4, Error4),
+    (T5, F5, E5, v5, 5, Unmatched5, Error5)
+);
+tuple_impl!(
+    Tuple7Error;
+    (T0, E0, v0, 0, Error0),
+    (T1, F1, E1, v1, 1, Unmatched1, Error1),
+    (T2, F2, E2, v2, 2, Unmatched2, Error2),
+    (T3, F3, E3, v3, 3, Unmatched3, Error3),
+    (T4, F4, E4, v4, 4, Unmatched4, Error4),
+    (T5, F5, E5, v5, 5, Unmatched5, Error5),
+    (T6, F6, E6, v6, 6, Unmatched6, Error6)
+);
+tuple_impl!(
+    Tuple8Error;
+    (T0, E0, v0, 0, Error0),
+    (T1, F1, E1, v1, 1, Unmatched1, Error1),
+    (T2, F2, E2, v2, 2, Unmatched2, Error2),
+    (T3, F3, E3, v3, 3, Unmatched3, Error3),
+    (T4, F4, E4, v4, 4, Unmatched4, Error4),
+    (T5, F5, E5, v5, 5, Unmatched5, Error5),
+    (T6, F6, E6, v6, 6, Unmatched6, Error6),
+    (T7, F7, E7, v7, 7, Unmatched7, Error7)
+);
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::Needed;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Hello, b"Hello");
+    tag!(World, b"World");
+    tag!(Bang, b"!");
+    illegal!(Bad, b"World");
+
+    #[test]
+    fn test_tuple() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"HelloWorld!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"World".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"Hello!".as_slice());
+
+        assert_eq!(
+            <(Hello, World)>::parse(input1),
+            Ok(((Hello(TestLoc(Loc::encapsulate_range(ID, ..5))), World(TestLoc(Loc::encapsulate_range(ID, 5..10)))), input1.slice(10..)))
+        );
+        assert_eq!(
+            <(Hello, World, Bang)>::parse(input1),
+            Ok((
+                (
+                    Hello(TestLoc(Loc::encapsulate_range(ID, ..5))),
+                    World(TestLoc(Loc::encapsulate_range(ID, 5..10))),
+                    Bang(TestLoc(Loc::encapsulate_range(ID, 10..11)))
+                ),
+                input1.slice(11..)
+            ))
+        );
+        // The first not matching means the whole doesn't match
+        assert_eq!(<(Hello, World)>::parse(input2), Err(NibbleError::Unmatched(<(Hello, World)>::expects(), None)));
+        // But later ones not matching is an error
+        assert_eq!(<(Hello, World)>::parse(input3), Err(NibbleError::Error(Tuple2Error::Unmatched1(World::expects()))));
+        // Unless that's because of a lack of input
+        let err = <(Hello, World)>::parse(Slice::with_raw_id(ID, b"HelloWor".as_slice())).unwrap_err();
+        assert!(err.is_incomplete());
+        assert_eq!(err, NibbleError::Unmatched(<(Hello, World)>::expects(), Some(Needed::Bounded(2, 2))));
+        assert_eq!(<(Hello, World, Bang)>::expects().to_string(), "[72, 101, 108, 108, 111], then [87, 111, 114, 108, 100], then [33]");
+    }
+
+    #[test]
+    fn test_tuple_error() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"HelloWorld".as_slice());
+
+        assert_eq!(<(Hello, Bad)>::parse(input), Err(NibbleError::Error(Tuple2Error::Error1(TestError("Bad")))));
+    }
+}
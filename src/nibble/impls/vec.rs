@@ -7,12 +7,12 @@
 
 use std::fmt::{Display, Formatter, Result as FResult};
 
-use super::super::error::ResultExt;
+use super::super::combinators::many_with_needed;
 use super::super::{NibbleError, Parsable, Slice};
 
 
 /***** FORMATTERS *****/
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq)]
 pub struct VecFormatter<F> {
     /// The inner formatter
     fmt: F,
@@ -41,28 +41,11 @@ impl<E, T: Parsable<E>> Parsable<E> for Vec<T> {
     #[inline]
     fn expects() -> Self::Formatter { VecFormatter { fmt: T::expects() } }
 
+    /// NOTE: This parser can never be [`NibbleError::Unmatched`] (as it will simply return an
+    /// empty list then). See [`many_with_needed()`] to find out if more input may have matched
+    /// more elements.
     #[inline]
-    fn parse(mut input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
-        // Read the input stream
-        // NOTE: I suspect it's quite optimal to avoid allocating until the first element. This
-        // because of the brute-force nature of the parser, and we'll probably see more failing
-        // calls then successful calls.
-        let mut res = Vec::new();
-        while let Some((item, rem)) = input.parse::<T>().transpose().auto_map()? {
-            // Do some optimized scaling if necessary
-            if res.is_empty() {
-                res.reserve(4);
-            } else if res.len() >= res.capacity() {
-                res.reserve(res.len());
-            }
-
-            // Then push the item
-            res.push(item);
-            input = rem;
-        }
-
-        // Shrink to something efficient
-        res.shrink_to_fit();
-        Ok((res, input))
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        many_with_needed(input).map(|((res, _), rem)| (res, rem))
     }
 }
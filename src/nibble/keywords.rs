@@ -0,0 +1,147 @@
+//  KEYWORDS.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements matching any of a set of keywords, and the formatter used by
+//!   the [`keywords!`](super::keywords!)-macro.
+//
+
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use super::error::Needed;
+use super::{NibbleError, Slice};
+use crate::loc::Loc;
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`longest_tag()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct KeywordsFormatter {
+    /// The keywords that we expected.
+    tags: &'static [&'static str],
+}
+impl KeywordsFormatter {
+    /// Constructor for the KeywordsFormatter.
+    ///
+    /// # Arguments
+    /// - `tags`: The keywords that we expected.
+    ///
+    /// # Returns
+    /// A new KeywordsFormatter that displays `tags` as alternatives.
+    #[inline]
+    pub const fn new(tags: &'static [&'static str]) -> Self { Self { tags } }
+}
+impl Display for KeywordsFormatter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
+        for (i, tag) in self.tags.iter().enumerate() {
+            if i > 0 && i + 1 < self.tags.len() {
+                write!(f, ", ")?;
+            } else if i > 0 {
+                write!(f, " or ")?;
+            }
+            write!(f, "{tag:?}")?;
+        }
+        Ok(())
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Matches the longest of the given keywords at the start of the input.
+///
+/// Unlike trying the keywords in sequence, this makes sure that a keyword is never shadowed by one
+/// of its prefixes (e.g., `"in"` doesn't match the start of `"instanceof"` if both are given).
+///
+/// Note that this doesn't check word boundaries, so `"from"` matches the start of `"fromage"`.
+///
+/// This tries every keyword in turn. For large sets of keywords that are matched often, see
+/// [`TagTrie`](super::trie::TagTrie) instead.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `tags`: The keywords to match.
+///
+/// # Returns
+/// The index of the matched keyword in `tags`, together with its [`Loc`] and the remaining input.
+///
+/// # Errors
+/// This function returns a [`NibbleError::Unmatched`] if none of the keywords matched. If any of
+/// them is a strict prefix of the input, it is accompanied by a [`Needed`]-hint.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn longest_tag<'a>(
+    input: Slice<'a, u8>,
+    tags: &'static [&'static str],
+) -> Result<((usize, Loc), Slice<'a, u8>), NibbleError<KeywordsFormatter, Infallible>> {
+    let mut best: Option<((usize, Loc), Slice<'a, u8>)> = None;
+    let mut needed: Option<Needed> = None;
+    for (i, tag) in tags.iter().enumerate() {
+        let tag: &[u8] = tag.as_bytes();
+        if best.as_ref().is_some_and(|((j, _), _)| tags[*j].len() >= tag.len()) {
+            continue;
+        }
+
+        let ((head, loc), rem) = input.head_slice_loc(tag.len());
+        if head == tag {
+            best = Some(((i, loc), rem));
+        } else if tag.starts_with(head) {
+            let n: usize = tag.len() - head.len();
+            needed = Needed::combine_opt(needed, Some(Needed::Bounded(n, n)));
+        }
+    }
+    best.ok_or(NibbleError::Unmatched(KeywordsFormatter::new(tags), needed))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+
+    const TAGS: &[&str] = &["in", "instanceof", "is"];
+
+    #[test]
+    fn test_longest_tag() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"in x".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"instanceof x".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"isn't".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"x".as_slice());
+
+        let ((i, loc), rem) = longest_tag(input1, TAGS).unwrap();
+        assert_eq!((i, TestLoc(loc), rem), (0, TestLoc(Loc::encapsulate_range(ID, ..2)), input1.slice(2..)));
+        let ((i, loc), rem) = longest_tag(input2, TAGS).unwrap();
+        assert_eq!((i, TestLoc(loc), rem), (1, TestLoc(Loc::encapsulate_range(ID, ..10)), input2.slice(10..)));
+        let ((i, loc), rem) = longest_tag(input3, TAGS).unwrap();
+        assert_eq!((i, TestLoc(loc), rem), (2, TestLoc(Loc::encapsulate_range(ID, ..2)), input3.slice(2..)));
+        assert_eq!(longest_tag(input4, TAGS), Err(NibbleError::Unmatched(KeywordsFormatter::new(TAGS), None)));
+    }
+
+    #[test]
+    fn test_longest_tag_needed() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"i".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"inst".as_slice());
+
+        assert_eq!(longest_tag(input1, TAGS), Err(NibbleError::Unmatched(KeywordsFormatter::new(TAGS), Some(Needed::Bounded(1, 9)))));
+        // A shorter match still wins if the longer one would need more input
+        let ((i, _), _) = longest_tag(input2, TAGS).unwrap();
+        assert_eq!(i, 0);
+    }
+
+    #[test]
+    fn test_keywords_formatter() {
+        assert_eq!(KeywordsFormatter::new(&["in"]).to_string(), "\"in\"");
+        assert_eq!(KeywordsFormatter::new(&["in", "is"]).to_string(), "\"in\" or \"is\"");
+        assert_eq!(KeywordsFormatter::new(TAGS).to_string(), "\"in\", \"instanceof\" or \"is\"");
+    }
+}
@@ -0,0 +1,152 @@
+//  LEX.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`lex()`], which tokenizes an entire input in one go for
+//!   a classic lexer-then-parser workflow.
+//
+
+use std::convert::Infallible;
+
+use super::{NibbleError, Parsable, Slice};
+use crate::loc::Loc;
+
+
+/***** HELPER FUNCTIONS *****/
+/// Skips as many `W`s as possible at the start of the given input.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to skip in.
+///
+/// # Returns
+/// The remainder of the input.
+#[inline]
+fn skip<W: Parsable<E, Error = Infallible>, E>(mut input: Slice<E>) -> Slice<E> {
+    loop {
+        match W::parse(input) {
+            // Stop if nothing is consumed, or we'd loop forever
+            Ok((_, rem)) if rem.offset() > input.offset() => input = rem,
+            Ok(_) | Err(NibbleError::Unmatched(_, _)) => return input,
+            Err(NibbleError::Error(err)) => match err {},
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Tokenizes the entire input into a list of `T`s, together with their [`Loc`]s.
+///
+/// Before every token (and at the end of the input), anything matched by `W` is skipped. This is
+/// typically [`Whitespace`](super::whitespace::Whitespace), or an [`Either`] of it and a
+/// comment. `W` is parsed repeatedly until it no longer matches or consumes anything.
+///
+/// The [`Loc`] of each token spans everything `T` consumed, like [`recognize()`] does.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to tokenize.
+///
+/// # Returns
+/// A [`Vec`] with all the parsed tokens, in order.
+///
+/// # Errors
+/// This function errors if a `T` (i.e., any token) could not be parsed at a point that's not the
+/// end of the input. Then, the first [`NibbleError::Unmatched`] or [`NibbleError::Error`] is
+/// returned as-is, together with the remainder of the input at which lexing stopped.
+///
+/// A `T` that matches without consuming anything (e.g., an [`Option`] or [`Vec`]) counts as
+/// [`NibbleError::Unmatched`], as it would otherwise be lexed forever.
+///
+/// [`Either`]: super::combinators::Either
+/// [`recognize()`]: super::combinators::recognize()
+#[allow(clippy::type_complexity)]
+pub fn lex<'a, T, W, E>(input: Slice<'a, E>) -> Result<Vec<(T, Loc)>, (NibbleError<T::Formatter, T::Error>, Slice<'a, E>)>
+where
+    T: Parsable<E>,
+    W: Parsable<E, Error = Infallible>,
+{
+    let mut tokens: Vec<(T, Loc)> = Vec::new();
+    let mut input: Slice<E> = skip::<W, E>(input);
+    while !input.is_empty() {
+        let (token, rem): (T, Slice<E>) = T::parse(input).map_err(|err| (err, input))?;
+        if rem.offset() == input.offset() {
+            return Err((NibbleError::Unmatched(T::expects(), None), input));
+        }
+        tokens.push((token, input.loc_until(&rem)));
+        input = skip::<W, E>(rem);
+    }
+    Ok(tokens)
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::combinators::Either;
+    use crate::nibble::test::{TestError, illegal, tag};
+    use crate::nibble::whitespace::Whitespace;
+
+    tag!(Hello, b"Hello");
+    tag!(World, b"World");
+    illegal!(Bad, b"!");
+
+    type Token = Either<Hello, Either<World, Bad>>;
+
+    /// Lexes and makes the [`Loc`]s strictly comparable.
+    #[allow(clippy::type_complexity)]
+    fn lex_test<T: Parsable<u8>>(input: Slice<u8>) -> Result<Vec<(T, TestLoc)>, (NibbleError<T::Formatter, T::Error>, usize)> {
+        lex::<T, Whitespace, u8>(input)
+            .map(|tokens| tokens.into_iter().map(|(t, loc)| (t, TestLoc(loc))).collect())
+            .map_err(|(err, rem)| (err, rem.offset()))
+    }
+
+    #[test]
+    fn test_lex() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"Hello World".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"  World\n Hello  ".as_slice());
+        let input3 = Slice::with_raw_id(ID, b" \t ".as_slice());
+
+        assert_eq!(
+            lex_test::<Token>(input1),
+            Ok(vec![
+                (Either::Left(Hello(TestLoc(Loc::encapsulate_range(ID, 0..5)))), TestLoc(Loc::encapsulate_range(ID, 0..5))),
+                (Either::Right(Either::Left(World(TestLoc(Loc::encapsulate_range(ID, 6..11))))), TestLoc(Loc::encapsulate_range(ID, 6..11))),
+            ])
+        );
+        assert_eq!(
+            lex_test::<Token>(input2),
+            Ok(vec![
+                (Either::Right(Either::Left(World(TestLoc(Loc::encapsulate_range(ID, 2..7))))), TestLoc(Loc::encapsulate_range(ID, 2..7))),
+                (Either::Left(Hello(TestLoc(Loc::encapsulate_range(ID, 9..14)))), TestLoc(Loc::encapsulate_range(ID, 9..14))),
+            ])
+        );
+        assert_eq!(lex_test::<Token>(input3), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_lex_error() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"Hello Goodbye".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Hello ! World".as_slice());
+
+        assert_eq!(lex_test::<Token>(input1), Err((NibbleError::Unmatched(Token::expects(), None), 6)));
+        assert_eq!(lex_test::<Token>(input2), Err((NibbleError::Error(Either::Right(Either::Right(TestError("Bad")))), 6)));
+    }
+
+    #[test]
+    fn test_lex_zero_width() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"Hello World".as_slice());
+
+        // An optional token matches (consuming nothing) at `World`, which is not a token
+        assert_eq!(lex_test::<Option<Hello>>(input), Err((NibbleError::Unmatched(<Option<Hello>>::expects(), None), 6)));
+    }
+}
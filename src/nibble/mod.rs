@@ -16,15 +16,31 @@
 //
 
 // Modules
+pub mod combinators;
 mod error;
+pub mod fmt;
 mod impls;
+pub mod keywords;
+mod lex;
+pub mod num;
 mod slice;
+#[cfg(all(test, feature = "tree"))]
+mod test;
+pub mod trie;
+pub mod trivia;
+pub mod whitespace;
 
 // Imports
 use std::error::Error;
 use std::fmt::Display;
 
-pub use error::NibbleError;
+pub use ast_toolkit2_decl_macros::spanned;
+#[cfg(feature = "tree")]
+pub use ast_toolkit2_decl_macros::keywords;
+#[cfg(feature = "proc-macros")]
+pub use ast_toolkit2_proc_macros::Parsable;
+pub use error::{Errors, Needed, NibbleError};
+pub use lex::lex;
 pub use slice::Slice;
 
 /// Shorthand for including all the traits of this crate.
@@ -56,8 +72,137 @@ pub trait Parsable<E>: Sized {
     /// string.
     fn expects() -> Self::Formatter;
 
+    /// Convenience function for rendering [`Parsable::expects()`] to a [`String`].
+    ///
+    /// Useful for embedding it in custom error types or logging.
+    ///
+    /// # Returns
+    /// A [`String`] with what [`Parsable::Formatter`] would display.
+    #[inline]
+    fn expects_string() -> String { Self::expects().to_string() }
+
     /// The actual parsing function.
     ///
     /// TODO.
+    #[allow(clippy::type_complexity)]
     fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>>;
+
+    /// Attempts to parse this node, but leaves the input untouched if it is not matched.
+    ///
+    /// This works by parsing a copy of the input, and only writing it back on success. Because a
+    /// [`Slice`] is just a pointer, length and offset, this is cheap.
+    ///
+    /// # Arguments
+    /// - `input`: The [`Slice`] to parse from. Only advanced if the parse succeeded.
+    ///
+    /// # Returns
+    /// The parsed node, or [`None`] if it was [`NibbleError::Unmatched`].
+    ///
+    /// # Errors
+    /// This function errors if [`Parsable::parse()`] returned a [`NibbleError::Error`].
+    #[inline]
+    fn parse_peek(input: &mut Slice<E>) -> Result<Option<Self>, NibbleError<Self::Formatter, Self::Error>> {
+        match Self::parse(*input) {
+            Ok((res, rem)) => {
+                *input = rem;
+                Ok(Some(res))
+            },
+            Err(NibbleError::Unmatched(_, _)) => Ok(None),
+            Err(NibbleError::Error(err)) => Err(NibbleError::Error(err)),
+        }
+    }
+}
+
+
+
+
+/// Parses a `P` from a string, where every element is a [`char`].
+///
+/// This is different from parsing the string as bytes (e.g., through
+/// [`Slice::from(&str)`](Slice::from())), because all [`Loc`](crate::loc::Loc)s produced are in
+/// characters instead of bytes. As such, they only line up with byte-based ones for ASCII input,
+/// and cannot be used to slice the string directly. Use them with e.g. [`str::chars()`] instead.
+///
+/// **Do not mix these [`Loc`](crate::loc::Loc)s with byte-based ones**, e.g., by
+/// [`Loc::join()`](crate::loc::Loc::join())ing them or by rendering them through
+/// [`line_col()`](crate::loc::line_col()) or [`render_error()`](fmt::render_error()), which
+/// expect byte offsets. To make merging them fail safely, their ID is not the pointer of `s`
+/// (like [`Slice::new()`] would use) but a hash of it together with the string `"chars"`, as if
+/// given to [`Slice::with_id()`].
+///
+/// # Arguments
+/// - `s`: The string to parse from.
+///
+/// # Returns
+/// A tuple with the parsed `P` and the offset (in characters) of the remaining input.
+///
+/// # Errors
+/// This function errors exactly when `P::parse()` does, with the same error.
+#[allow(clippy::type_complexity)]
+pub fn parse_str<P: Parsable<char>>(s: &str) -> Result<(P, usize), NibbleError<P::Formatter, P::Error>> {
+    let chars: Vec<char> = s.chars().collect();
+    let (res, rem): (P, Slice<char>) = P::parse(Slice::with_id(("chars", s.as_ptr() as u64), &chars))?;
+    Ok((res, rem.offset()))
+}
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::Loc;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::{TestError, illegal, tag};
+
+    tag!(Hello, b"Hello");
+    tag!(HelloChars: char, &['H', 'e', 'l', 'l', 'o']);
+    tag!(Accent: char, &['é']);
+    illegal!(Bad, b"Hello");
+
+    #[test]
+    fn test_parse_peek() {
+        const ID: u64 = 0;
+        let mut input = Slice::with_raw_id(ID, b"Hello!".as_slice());
+
+        // On success, we advance
+        assert_eq!(Hello::parse_peek(&mut input), Ok(Some(Hello(TestLoc(Loc::encapsulate_range(ID, ..5))))));
+        assert_eq!(input.offset(), 5);
+        // But not when unmatched
+        assert_eq!(Hello::parse_peek(&mut input), Ok(None));
+        assert_eq!(input.offset(), 5);
+
+        // Errors are propagated
+        let mut input = Slice::with_raw_id(ID, b"Hello!".as_slice());
+        assert_eq!(Bad::parse_peek(&mut input).map(|res| res.is_some()), Err(NibbleError::Error(TestError("Bad"))));
+        assert_eq!(input.offset(), 0);
+    }
+
+    #[test]
+    fn test_expects_string() {
+        assert_eq!(Hello::expects_string(), "[72, 101, 108, 108, 111]");
+        assert_eq!(Bad::expects_string(), "Bad");
+    }
+
+    #[test]
+    fn test_parse_str() {
+        let input: &str = "éHello!";
+        let id: u64 = Slice::with_id(("chars", input.as_ptr() as u64), b"".as_slice()).id();
+        let byte_id: u64 = input.as_ptr() as u64;
+        assert_ne!(id, byte_id);
+
+        // The Locs count characters, not bytes
+        assert_eq!(
+            parse_str::<(Accent, HelloChars)>(input),
+            Ok(((Accent(TestLoc(Loc::encapsulate_range(id, 0..1))), HelloChars(TestLoc(Loc::encapsulate_range(id, 1..6)))), 6))
+        );
+        // Unlike the byte-based one, which uses another ID
+        let (hello, _) = Slice::from(input).slice(2..).parse::<Hello>().unwrap();
+        assert_eq!(hello, Hello(TestLoc(Loc::encapsulate_range(byte_id, 2..7))));
+        // So they aren't accidentally merged
+        let (accent, _) = parse_str::<Accent>(input).unwrap();
+        assert_eq!(TestLoc(accent.0.join(*hello.0)), TestLoc(Loc::encapsulate_range(id, 0..1)));
+        assert_eq!(parse_str::<HelloChars>(input), Err(NibbleError::Unmatched(HelloChars::expects(), None)));
+    }
 }
@@ -0,0 +1,235 @@
+//  NUM.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements parsers for numeric literals.
+//
+
+use std::fmt::{Display, Formatter, Result as FResult};
+
+use thiserror::Error;
+
+use super::{Needed, NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted when parsing an [`IntLiteral`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum IntLiteralError {
+    /// The literal does not fit in the target type.
+    #[error("Integer overflow")]
+    Overflow(Loc),
+}
+
+
+
+
+
+/***** INTERFACES *****/
+/// Defines elements that may represent ASCII characters.
+pub trait AsciiElem {
+    /// Returns the ASCII character represented by this element.
+    ///
+    /// # Returns
+    /// The ASCII character as a byte, or [`None`] if this element is not ASCII.
+    fn as_ascii(&self) -> Option<u8>;
+}
+impl AsciiElem for u8 {
+    #[inline]
+    fn as_ascii(&self) -> Option<u8> { if self.is_ascii() { Some(*self) } else { None } }
+}
+impl AsciiElem for char {
+    #[inline]
+    fn as_ascii(&self) -> Option<u8> { if self.is_ascii() { Some(*self as u8) } else { None } }
+}
+
+
+
+/// Defines integer types that an [`IntLiteral`] can parse to.
+pub trait Integer: Copy {
+    /// Whether this type is signed, i.e., whether a leading `-` is accepted.
+    const SIGNED: bool;
+    /// The zero of this type.
+    const ZERO: Self;
+
+    /// Shifts a new digit into this integer, i.e., computes `self * 10 + digit`.
+    ///
+    /// # Arguments
+    /// - `digit`: The digit to add. Guaranteed to be in the range `0..=9`.
+    ///
+    /// # Returns
+    /// The new integer, or [`None`] if it overflowed.
+    fn push_digit(self, digit: u8) -> Option<Self>;
+
+    /// Shifts a new digit into this integer as a negative number, i.e., computes
+    /// `self * 10 - digit`.
+    ///
+    /// # Arguments
+    /// - `digit`: The digit to subtract. Guaranteed to be in the range `0..=9`.
+    ///
+    /// # Returns
+    /// The new integer, or [`None`] if it overflowed.
+    fn push_neg_digit(self, digit: u8) -> Option<Self>;
+}
+
+/// Implements [`Integer`] for primitive integers.
+macro_rules! integer_impl {
+    ($signed:literal: $($ty:ty),+) => {
+        $(
+            impl Integer for $ty {
+                const SIGNED: bool = $signed;
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn push_digit(self, digit: u8) -> Option<Self> { self.checked_mul(10)?.checked_add(digit as Self) }
+
+                #[inline]
+                fn push_neg_digit(self, digit: u8) -> Option<Self> { self.checked_mul(10)?.checked_sub(digit as Self) }
+            }
+        )+
+    };
+}
+integer_impl!(false: u8, u16, u32, u64, u128, usize);
+integer_impl!(true: i8, i16, i32, i64, i128, isize);
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`IntLiteral::expects()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct IntLiteralFormatter;
+impl Display for IntLiteralFormatter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "an integer literal") }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses a decimal integer literal into some [`Integer`] `N`.
+///
+/// The literal is a non-empty run of ASCII digits, optionally preceded by a `-` if `N` is signed.
+/// Leading zeroes are allowed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct IntLiteral<N> {
+    /// The parsed value.
+    pub value: N,
+    /// The location of the whole literal, including any sign.
+    pub loc:   Loc,
+}
+
+// Loc
+impl<N> Located for IntLiteral<N> {
+    #[inline]
+    fn loc(&self) -> Loc { self.loc }
+}
+
+// Parsing
+impl<E: AsciiElem, N: Integer> Parsable<E> for IntLiteral<N> {
+    type Formatter = IntLiteralFormatter;
+    type Error = IntLiteralError;
+
+    #[inline]
+    fn expects() -> Self::Formatter { IntLiteralFormatter }
+
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        // Check for a sign first
+        let neg: bool = N::SIGNED && input.first().and_then(E::as_ascii) == Some(b'-');
+        let start: usize = if neg { 1 } else { 0 };
+
+        // Then accumulate the digits
+        let mut value: N = N::ZERO;
+        let mut overflow: bool = false;
+        let mut len: usize = 0;
+        for digit in input[start..].iter().map_while(|e| e.as_ascii().filter(u8::is_ascii_digit)) {
+            if !overflow {
+                let res = if neg { value.push_neg_digit(digit - b'0') } else { value.push_digit(digit - b'0') };
+                match res {
+                    Some(res) => value = res,
+                    None => overflow = true,
+                }
+            }
+            len += 1;
+        }
+        if len == 0 {
+            return Err(NibbleError::Unmatched(IntLiteralFormatter, if input.len() <= start { Some(Needed::AtLeast(1)) } else { None }));
+        }
+
+        // Done
+        let ((_, loc), rem) = input.head_slice_loc(start + len);
+        if overflow { Err(NibbleError::Error(IntLiteralError::Overflow(loc))) } else { Ok((Self { value, loc }, rem)) }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+
+    #[test]
+    fn test_int_literal() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"18446744073709551615".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"0042;".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"abc".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"".as_slice());
+
+        let (lit, rem) = IntLiteral::<u64>::parse(input1).unwrap();
+        assert_eq!((lit.value, TestLoc(lit.loc), rem), (u64::MAX, TestLoc(Loc::encapsulate_range(ID, ..20)), input1.slice(20..)));
+        let (lit, rem) = IntLiteral::<u8>::parse(input2).unwrap();
+        assert_eq!((lit.value, TestLoc(lit.loc), rem), (42, TestLoc(Loc::encapsulate_range(ID, ..4)), input2.slice(4..)));
+        assert_eq!(IntLiteral::<u64>::parse(input3), Err(NibbleError::Unmatched(IntLiteralFormatter, None)));
+        assert_eq!(IntLiteral::<u64>::parse(input4), Err(NibbleError::Unmatched(IntLiteralFormatter, Some(Needed::AtLeast(1)))));
+
+        // Chars work too
+        let chars: Vec<char> = "123".chars().collect();
+        let input5 = Slice::with_raw_id(ID, chars.as_slice());
+        let (lit, rem) = IntLiteral::<u16>::parse(input5).unwrap();
+        assert_eq!((lit.value, rem), (123, input5.slice(3..)));
+    }
+
+    #[test]
+    fn test_int_literal_signed() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"-128".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"127".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"-".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"-x".as_slice());
+
+        let (lit, rem) = IntLiteral::<i8>::parse(input1).unwrap();
+        assert_eq!((lit.value, TestLoc(lit.loc), rem), (-128, TestLoc(Loc::encapsulate_range(ID, ..4)), input1.slice(4..)));
+        let (lit, _) = IntLiteral::<i8>::parse(input2).unwrap();
+        assert_eq!(lit.value, 127);
+        assert_eq!(IntLiteral::<i8>::parse(input3), Err(NibbleError::Unmatched(IntLiteralFormatter, Some(Needed::AtLeast(1)))));
+        assert_eq!(IntLiteral::<i8>::parse(input4), Err(NibbleError::Unmatched(IntLiteralFormatter, None)));
+        // Unsigned types don't accept a sign
+        assert_eq!(IntLiteral::<u8>::parse(input1), Err(NibbleError::Unmatched(IntLiteralFormatter, None)));
+    }
+
+    #[test]
+    fn test_int_literal_overflow() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"18446744073709551616".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"-129".as_slice());
+
+        match IntLiteral::<u64>::parse(input1) {
+            Err(NibbleError::Error(IntLiteralError::Overflow(loc))) => assert_eq!(TestLoc(loc), TestLoc(Loc::encapsulate_range(ID, ..20))),
+            res => panic!("Expected overflow error, got {res:?}"),
+        }
+        match IntLiteral::<i8>::parse(input2) {
+            Err(NibbleError::Error(IntLiteralError::Overflow(loc))) => assert_eq!(TestLoc(loc), TestLoc(Loc::encapsulate_range(ID, ..4))),
+            res => panic!("Expected overflow error, got {res:?}"),
+        }
+        assert_eq!(IntLiteral::<u64>::parse(input1).unwrap_err().to_string(), "Integer overflow");
+    }
+}
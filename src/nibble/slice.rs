@@ -41,6 +41,8 @@ use crate::loc::{Loc, Located};
 pub struct Slice<'a, T> {
     /// The actual slice wrapper.
     slice:  &'a [T],
+    /// The position of the start of `slice` in the source. Goes in elements.
+    base:   usize,
     /// The offset that this slice points to. Goes in elements.
     offset: usize,
     /// Any ID to set in [`Loc`]s.
@@ -54,13 +56,18 @@ impl<'a, T> Slice<'a, T> {
     /// This will assign an ID that is the pointer of the given slice to any [`Loc`] that it
     /// generates. Use [`Slice::with_id()`] instead to give your own.
     ///
+    /// Note that such IDs are merely convenient, not robust: they differ between runs, and two
+    /// slices may end up with the same ID if one is deallocated before the other is allocated.
+    /// Prefer an explicit ID (e.g., the file name) if [`Loc`]s from different buffers of the same
+    /// source must be combined, as they can not be [`Loc::extend()`]ed otherwise.
+    ///
     /// # Arguments
     /// - `slice`: Some [`&[T]`](std::slice) to wrap.
     ///
     /// # Returns
     /// A new Slice that can be used for parsing.
     #[inline]
-    pub fn new(slice: &'a [T]) -> Self { Self { slice, offset: 0, id: slice.as_ptr() as u64 } }
+    pub fn new(slice: &'a [T]) -> Self { Self { slice, base: 0, offset: 0, id: slice.as_ptr() as u64 } }
 
     /// Constructor for the slice that builds it given the given slice and [`Loc`] ID.
     ///
@@ -79,6 +86,7 @@ impl<'a, T> Slice<'a, T> {
     pub fn with_id(id: impl Hash, slice: &'a [T]) -> Self {
         Self {
             slice,
+            base: 0,
             offset: 0,
             id: {
                 let mut hasher = DefaultHasher::new();
@@ -100,21 +108,57 @@ impl<'a, T> Slice<'a, T> {
     /// # Returns
     /// A new Slice that can be used for parsing.
     #[inline]
-    pub const fn with_raw_id(id: u64, slice: &'a [T]) -> Self { Self { slice, offset: 0, id } }
+    pub const fn with_raw_id(id: u64, slice: &'a [T]) -> Self { Self { slice, base: 0, offset: 0, id } }
+
+    /// Constructor for the slice that builds it with the given slice, [`Loc`] ID and base offset.
+    ///
+    /// This is useful if the given slice is only a part of the source, e.g., when parsing a file
+    /// chunk-by-chunk. Then the [`Loc`]s generated refer to positions in the whole source instead
+    /// of the chunk.
+    ///
+    /// # Arguments
+    /// - `id`: The identifier that uniquely defines this source.
+    /// - `base`: The position of the first element of `slice` in the source.
+    /// - `slice`: Some [`&[T]`](std::slice) to wrap.
+    ///
+    /// # Returns
+    /// A new Slice that can be used for parsing.
+    #[inline]
+    pub const fn with_raw_id_at(id: u64, base: usize, slice: &'a [T]) -> Self { Self { slice, base, offset: 0, id } }
+}
+
+// Accessors
+impl<'a, T> Slice<'a, T> {
+    /// Returns the ID of the source that is set in generated [`Loc`]s.
+    ///
+    /// # Returns
+    /// The ID, as a [`u64`].
+    #[inline]
+    pub const fn id(&self) -> u64 { self.id }
+
+    /// Returns the position of the head of this slice in the source.
+    ///
+    /// This takes any base offset (see [`Slice::with_raw_id_at()`]) into account, and is thus the
+    /// same position as used in generated [`Loc`]s.
+    ///
+    /// # Returns
+    /// The position, in elements.
+    #[inline]
+    pub const fn offset(&self) -> usize { self.base + self.offset }
 }
 
 // Ops
 impl<'a, T> Copy for Slice<'a, T> {}
 impl<'a, T> Clone for Slice<'a, T> {
     #[inline]
-    fn clone(&self) -> Self { Self { slice: self.slice, offset: self.offset, id: self.id } }
+    fn clone(&self) -> Self { *self }
 }
 impl<'a, T> Debug for Slice<'a, T> {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> FResult {
         let mut fmt = f.debug_struct("Slice");
         fmt.field("id", &self.id);
-        fmt.field("range", &(self.offset..));
+        fmt.field("range", &(self.base + self.offset..));
         fmt.finish()
     }
 }
@@ -123,7 +167,7 @@ impl<'a, T: Hash> Hash for Slice<'a, T> {
     /// NOTE: Hashing also includes the internal ID.
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
-        let Self { slice, offset, id } = self;
+        let Self { slice, base: _, offset, id } = self;
         id.hash(state);
         slice[*offset..].hash(state);
     }
@@ -133,18 +177,18 @@ impl<'a, T: PartialEq> PartialEq for Slice<'a, T> {
     /// the source!
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        let Self { slice, offset, id } = self;
+        let Self { slice, base: _, offset, id } = self;
         id == &other.id && slice[*offset..] == other.slice[other.offset..]
     }
 }
 impl<'a, T: PartialEq<T2>, T2> PartialEq<[T2]> for Slice<'a, T> {
     #[inline]
     fn eq(&self, other: &[T2]) -> bool {
-        let Self { slice, offset, id: _ } = self;
+        let Self { slice, base: _, offset, id: _ } = self;
         &slice[*offset..] == other
     }
 }
-impl<'a, 'b, T2, T> PartialEq<&'a T2> for Slice<'a, T>
+impl<'a, T2, T> PartialEq<&'a T2> for Slice<'a, T>
 where
     T2: ?Sized,
     Slice<'a, T>: PartialEq<T2>,
@@ -197,7 +241,7 @@ impl<'a, T> Slice<'a, T> {
     /// # Returns
     /// A new [`Slice`] that a subset of this slice.
     #[inline]
-    pub const fn slice(self, range: RangeFrom<usize>) -> Self { Self { slice: self.slice, offset: self.offset + range.start, id: self.id } }
+    pub const fn slice(self, range: RangeFrom<usize>) -> Self { Self { slice: self.slice, base: self.base, offset: self.offset + range.start, id: self.id } }
 
     /// Returns the head of this slice separately and a new Slice encoding the rest.
     ///
@@ -226,14 +270,34 @@ impl<'a, T> Slice<'a, T> {
     /// empty, then so is the head.
     #[inline]
     pub fn head_ref_loc(self) -> (Option<(&'a T, Loc)>, Self) {
-        let Self { slice, offset, id } = self;
+        let Self { slice, base, offset, id } = self;
         if let [head, ..] = &slice[offset..] {
-            (Some((head, Loc::encapsulate_range(id, offset..=offset))), Self { slice, offset: offset + 1, id })
+            (Some((head, Loc::encapsulate_range(id, base + offset..=base + offset))), Self { slice, base, offset: offset + 1, id })
         } else {
             (None, self)
         }
     }
 
+    /// Returns the head of this slice without popping it.
+    ///
+    /// This is idempotent, i.e., peeking multiple times always returns the same element. Use
+    /// [`Slice::head_ref()`] to also get the remainder.
+    ///
+    /// # Returns
+    /// The head element (by reference), or [`None`] if this slice is empty.
+    #[inline]
+    pub fn peek(&self) -> Option<&'a T> { self.head_ref().0 }
+
+    /// Returns the head of this slice without popping it.
+    ///
+    /// This overload returns a [`Loc`] to describe where in the slice this element came from. Use
+    /// [`Slice::peek()`] if you don't care.
+    ///
+    /// # Returns
+    /// The head element (by reference) and its [`Loc`], or [`None`] if this slice is empty.
+    #[inline]
+    pub fn peek_loc(&self) -> Option<(&'a T, Loc)> { self.head_ref_loc().0 }
+
     /// Slices this Slice in half.
     ///
     /// # Arguments
@@ -246,11 +310,11 @@ impl<'a, T> Slice<'a, T> {
     /// empty.
     #[inline]
     pub fn head_slice(self, pos: usize) -> (&'a [T], Self) {
-        let Self { slice, offset, id } = self;
+        let Self { slice, base, offset, id } = self;
         if offset + pos <= slice.len() {
-            (&slice[offset..offset + pos], Self { slice, offset: offset + pos, id })
+            (&slice[offset..offset + pos], Self { slice, base, offset: offset + pos, id })
         } else {
-            (&slice[offset..], Self { slice, offset: slice.len(), id })
+            (&slice[offset..], Self { slice, base, offset: slice.len(), id })
         }
     }
 
@@ -269,14 +333,34 @@ impl<'a, T> Slice<'a, T> {
     /// empty.
     #[inline]
     pub fn head_slice_loc(self, pos: usize) -> ((&'a [T], Loc), Self) {
-        let Self { slice, offset, id } = self;
+        let Self { slice, base, offset, id } = self;
         if offset + pos <= slice.len() {
-            ((&slice[offset..offset + pos], Loc::encapsulate_range(id, offset..offset + pos)), Self { slice, offset: offset + pos, id })
+            ((&slice[offset..offset + pos], Loc::encapsulate_range(id, base + offset..base + offset + pos)), Self { slice, base, offset: offset + pos, id })
         } else {
-            ((&slice[offset..], Loc::encapsulate_range(id, offset..slice.len())), Self { slice, offset: slice.len(), id })
+            ((&slice[offset..], Loc::encapsulate_range(id, base + offset..base + slice.len())), Self { slice, base, offset: slice.len(), id })
         }
     }
 
+    /// Splits this Slice into a bounded view of the next `n` elements and the remainder.
+    ///
+    /// Unlike [`Slice::head_slice()`], the first half is a Slice again. It still generates the
+    /// same [`Loc`]s as this one would, but it ends after `n` elements. This makes it useful to
+    /// confine an (even greedy) inner parser to a region whose extent is already known, e.g.,
+    /// up to a matching closing delimiter.
+    ///
+    /// # Arguments
+    /// - `n`: The number of elements in the bounded view.
+    ///
+    /// # Returns
+    /// A tuple with the bounded view and then the remainder. If `n` is beyond the number of
+    /// elements in the slice, then the view is truncated and the remainder is empty.
+    #[inline]
+    pub fn take(self, n: usize) -> (Self, Self) {
+        let Self { slice, base, offset, id } = self;
+        let end: usize = offset.saturating_add(n).min(slice.len());
+        (Self { slice: &slice[..end], base, offset, id }, Self { slice, base, offset: end, id })
+    }
+
     /// Parses a full type from the head off the slice, returning the remainder.
     ///
     /// This function conveniently reverses the order on calling [`Parsable::parse()`].
@@ -286,11 +370,132 @@ impl<'a, T> Slice<'a, T> {
     #[inline]
     pub fn parse<T2: Parsable<T>>(self) -> Result<(T2, Self), NibbleError<T2::Formatter, T2::Error>> { T2::parse(self) }
 }
+impl<'a> Slice<'a, u8> {
+    /// Returns the head of this slice as a UTF-8 encoded [`char`], and a new Slice encoding the
+    /// rest.
+    ///
+    /// Unlike [`Slice::head()`], this pops all the bytes making up the character at once.
+    ///
+    /// # Returns
+    /// A tuple of the head character and then the rest of the slice. If this slice is empty, or
+    /// its head is not a valid UTF-8 character, then the head is [`None`] and the slice is
+    /// returned as-is.
+    #[inline]
+    pub fn head_char(self) -> (Option<char>, Self) {
+        let (elem, rem) = self.head_char_loc();
+        (elem.map(|(elem, _)| elem), rem)
+    }
+
+    /// Returns the head of this slice as a UTF-8 encoded [`char`], and a new Slice encoding the
+    /// rest.
+    ///
+    /// This overload returns a [`Loc`] to describe where in the slice this character came from.
+    /// It spans all bytes of the encoded character. Use [`Slice::head_char()`] if you don't care.
+    ///
+    /// # Returns
+    /// A tuple of the head character and then the rest of the slice. If this slice is empty, or
+    /// its head is not a valid UTF-8 character, then the head is [`None`] and the slice is
+    /// returned as-is.
+    #[inline]
+    pub fn head_char_loc(self) -> (Option<(char, Loc)>, Self) {
+        // A UTF-8 character is at most 4 bytes long
+        let head: &[u8] = &self.as_slice()[..self.len().min(4)];
+        let valid: &str = match std::str::from_utf8(head) {
+            Ok(valid) => valid,
+            Err(err) => std::str::from_utf8(&head[..err.valid_up_to()]).unwrap_or_default(),
+        };
+        match valid.chars().next() {
+            Some(c) => {
+                let ((_, loc), rem) = self.head_slice_loc(c.len_utf8());
+                (Some((c, loc)), rem)
+            },
+            None => (None, self),
+        }
+    }
+}
+impl<'a> Slice<'a, u8> {
+    /// Returns the bytes that a [`Loc`] covers in the source of this slice.
+    ///
+    /// This includes bytes that were already consumed, so it works for any [`Loc`] generated by
+    /// this slice or its ancestors.
+    ///
+    /// # Arguments
+    /// - `loc`: The [`Loc`] to extract.
+    ///
+    /// # Returns
+    /// The sub-slice covered by `loc`, or [`None`] if it's from another source or (partially) out
+    /// of range.
+    #[inline]
+    pub fn slice_bytes(&self, loc: &Loc) -> Option<&'a [u8]> {
+        if loc.source != Some(self.id) {
+            return None;
+        }
+        let start: usize = usize::try_from(loc.start()).ok()?.checked_sub(self.base)?;
+        let end: usize = match loc.end() {
+            Some(end) => usize::try_from(end).ok()?.checked_sub(self.base)?,
+            None => self.slice.len(),
+        };
+        self.slice.get(start..end)
+    }
+
+    /// Returns the text that a [`Loc`] covers in the source of this slice.
+    ///
+    /// See [`Slice::slice_bytes()`] for more information.
+    ///
+    /// # Arguments
+    /// - `loc`: The [`Loc`] to extract.
+    ///
+    /// # Returns
+    /// The text covered by `loc`, or [`None`] if it's from another source, (partially) out of
+    /// range or not valid UTF-8.
+    #[inline]
+    pub fn slice_str(&self, loc: &Loc) -> Option<&'a str> { std::str::from_utf8(self.slice_bytes(loc)?).ok() }
+}
+impl<'a, T> Slice<'a, (T, Loc)> {
+    /// Returns the head of this slice of pre-lexed tokens separately, and a new Slice encoding the
+    /// rest.
+    ///
+    /// Unlike [`Slice::head_ref_loc()`], this returns the [`Loc`] stored with the token instead of
+    /// one pointing into this slice. As such, it points back to the source the token was lexed
+    /// from, e.g., by [`lex()`](super::lex()).
+    ///
+    /// # Returns
+    /// A tuple of the head token (by reference) with its stored [`Loc`], and then the rest of the
+    /// slice. If this slice is empty, then so is the head.
+    #[inline]
+    pub fn head_token(self) -> (Option<(&'a T, Loc)>, Self) {
+        let (elem, rem) = self.head_ref();
+        (elem.map(|(token, loc)| (token, *loc)), rem)
+    }
+
+    /// Returns the head of this slice of pre-lexed tokens without popping it.
+    ///
+    /// See [`Slice::head_token()`] for more information.
+    ///
+    /// # Returns
+    /// The head token (by reference) and its stored [`Loc`], or [`None`] if this slice is empty.
+    #[inline]
+    pub fn peek_token(&self) -> Option<(&'a T, Loc)> { self.head_token().0 }
+}
 
 // Loc
+impl<'a, T> Slice<'a, T> {
+    /// Returns the [`Loc`] of everything consumed between this slice and some remainder of it.
+    ///
+    /// This is useful for computing the [`Loc`] of a node after having parsed all its parts.
+    ///
+    /// # Arguments
+    /// - `rem`: Some remainder of this slice, e.g., as returned by [`Parsable::parse()`].
+    ///
+    /// # Returns
+    /// A [`Loc`] spanning from the start of `self` up to the start of `rem`. If `rem` is not
+    /// further than `self`, then it's empty.
+    #[inline]
+    pub fn loc_until(&self, rem: &Self) -> Loc { Loc::encapsulate_range(self.id, self.offset()..rem.offset().max(self.offset())) }
+}
 impl<'a, T> Located for Slice<'a, T> {
     #[inline]
-    fn loc(&self) -> Loc { Loc::encapsulate_range(self.id, self.offset..self.slice.len()) }
+    fn loc(&self) -> Loc { Loc::encapsulate_range(self.id, self.base + self.offset..self.base + self.slice.len()) }
 }
 
 // Deref
@@ -339,3 +544,207 @@ impl<'a> TryFrom<Slice<'a, u8>> for &'a str {
     #[inline]
     fn try_from(value: Slice<'a, u8>) -> Result<Self, Self::Error> { std::str::from_utf8(value.as_slice()) }
 }
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::Range;
+    use crate::loc::test::TestLoc;
+
+    #[test]
+    fn test_slice_offset() {
+        const ID: u64 = 42;
+        let input = Slice::with_raw_id(ID, b"abc".as_slice());
+
+        // Offsets are contiguous across pops
+        assert_eq!((input.id(), input.offset()), (ID, 0));
+        let (_, rem) = input.head();
+        assert_eq!(rem.offset(), 1);
+        let (_, rem) = rem.head_slice(2);
+        assert_eq!(rem.offset(), 3);
+        let (_, rem) = rem.head();
+        assert_eq!(rem.offset(), 3);
+        // Copying doesn't change them
+        let copy = rem;
+        assert_eq!((copy.id(), copy.offset()), (ID, 3));
+
+        // Base offsets are taken into account everywhere
+        let input = Slice::with_raw_id_at(ID, 100, b"abc".as_slice());
+        assert_eq!(input.offset(), 100);
+        let (head, rem) = input.head_loc();
+        assert_eq!(head.map(|(h, loc)| (h, TestLoc(loc))), Some((b'a', TestLoc(Loc::encapsulate_range(ID, 100..101)))));
+        let ((_, loc), rem) = rem.head_slice_loc(2);
+        assert_eq!((TestLoc(loc), rem.offset()), (TestLoc(Loc::encapsulate_range(ID, 101..103)), 103));
+        assert_eq!(TestLoc(input.slice(1..).loc()), TestLoc(Loc::encapsulate_range(ID, 101..103)));
+    }
+
+    #[test]
+    fn test_slice_len() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id_at(ID, 100, b"abcde".as_slice());
+
+        // The length is always exactly what's left
+        assert_eq!(input.len(), 5);
+        let (_, rem) = input.head();
+        assert_eq!(rem.len(), 4);
+        let (_, rem) = rem.head_slice(3);
+        assert_eq!(rem.len(), 1);
+        let (_, rem) = rem.head_slice(3);
+        assert_eq!(rem.len(), 0);
+        assert_eq!(input.slice(2..).len(), 3);
+    }
+
+    #[test]
+    fn test_slice_head_slice() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"abc".as_slice());
+
+        let ((head, loc), rem) = input.head_slice_loc(2);
+        assert_eq!((head, TestLoc(loc), rem), (b"ab".as_slice(), TestLoc(Loc::encapsulate_range(ID, 0..2)), input.slice(2..)));
+        // Heads beyond the end are truncated
+        let ((head, loc), rem) = input.slice(1..).head_slice_loc(5);
+        assert_eq!((head, TestLoc(loc), rem.len()), (b"bc".as_slice(), TestLoc(Loc::encapsulate_range(ID, 1..3)), 0));
+        let ((head, loc), rem) = rem.head_slice_loc(1);
+        assert_eq!((head, TestLoc(loc), rem.offset()), (b"".as_slice(), TestLoc(Loc::encapsulate_range(ID, 3..3)), 3));
+    }
+
+    #[test]
+    fn test_slice_loc_until() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id_at(ID, 100, b"abcde".as_slice());
+
+        let (_, rem) = input.head_slice(3);
+        assert_eq!(TestLoc(input.loc_until(&rem)), TestLoc(Loc::encapsulate_range(ID, 100..103)));
+        assert_eq!(TestLoc(rem.loc_until(&rem)), TestLoc(Loc::encapsulate_range(ID, 103..103)));
+        assert_eq!(TestLoc(rem.loc_until(&input)), TestLoc(Loc::encapsulate_range(ID, 103..103)));
+    }
+
+    #[test]
+    fn test_slice_peek() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"ab".as_slice());
+
+        // Peeking never advances
+        assert_eq!(input.peek(), Some(&b'a'));
+        assert_eq!(input.peek(), Some(&b'a'));
+        assert_eq!(input.peek_loc().map(|(e, loc)| (e, TestLoc(loc))), Some((&b'a', TestLoc(Loc::encapsulate_range(ID, 0..1)))));
+        assert_eq!(input, b"ab".as_slice());
+        assert_eq!(input.slice(1..).peek_loc().map(|(e, loc)| (e, TestLoc(loc))), Some((&b'b', TestLoc(Loc::encapsulate_range(ID, 1..2)))));
+        assert_eq!(input.slice(2..).peek(), None);
+        assert_eq!(input.slice(2..).peek_loc(), None);
+    }
+
+    #[test]
+    fn test_slice_head_char() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, "héllo".as_bytes());
+
+        let (c, rem) = input.head_char_loc();
+        assert_eq!(c.map(|(c, loc)| (c, TestLoc(loc))), Some(('h', TestLoc(Loc::encapsulate_range(ID, 0..1)))));
+        let (c, rem) = rem.head_char_loc();
+        assert_eq!(c.map(|(c, loc)| (c, TestLoc(loc))), Some(('é', TestLoc(Loc::encapsulate_range(ID, 1..3)))));
+        assert_eq!(rem, input.slice(3..));
+        let (c, _) = rem.head_char();
+        assert_eq!(c, Some('l'));
+
+        // Multi-byte characters
+        let input = Slice::with_raw_id(ID, "日本".as_bytes());
+        let (c, rem) = input.head_char_loc();
+        assert_eq!(c.map(|(c, loc)| (c, TestLoc(loc))), Some(('日', TestLoc(Loc::encapsulate_range(ID, 0..3)))));
+        assert_eq!(rem, input.slice(3..));
+
+        // Invalid or empty input doesn't advance
+        let input = Slice::with_raw_id(ID, [0xC3u8, b'a'].as_slice());
+        assert_eq!(input.head_char(), (None, input));
+        let input = Slice::with_raw_id(ID, [0xE6u8, 0x97].as_slice());
+        assert_eq!(input.head_char(), (None, input));
+        let input = Slice::with_raw_id(ID, b"".as_slice());
+        assert_eq!(input.head_char(), (None, input));
+    }
+
+    #[test]
+    fn test_slice_head_token() {
+        const ID: u64 = 0;
+        const SRC: u64 = 1;
+        let tokens = [("Hello", Loc::encapsulate_range(SRC, 0..5)), ("World", Loc::encapsulate_range(SRC, 6..11))];
+        let input = Slice::with_raw_id(ID, tokens.as_slice());
+
+        // The stored locs are returned, not ones into the token slice
+        assert_eq!(input.peek_token().map(|(t, loc)| (t, TestLoc(loc))), Some((&"Hello", TestLoc(Loc::encapsulate_range(SRC, 0..5)))));
+        let (token, rem) = input.head_token();
+        assert_eq!(token.map(|(t, loc)| (t, TestLoc(loc))), Some((&"Hello", TestLoc(Loc::encapsulate_range(SRC, 0..5)))));
+        let (token, rem) = rem.head_token();
+        assert_eq!(token.map(|(t, loc)| (t, TestLoc(loc))), Some((&"World", TestLoc(Loc::encapsulate_range(SRC, 6..11)))));
+        assert_eq!(rem.head_token(), (None, rem));
+        assert_eq!(rem.offset(), 2);
+    }
+
+    #[test]
+    fn test_slice_take() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id_at(ID, 10, b"aaaaa".as_slice()).slice(1..);
+
+        // The view is bounded, but still generates the same Locs
+        let (view, rem) = input.take(2);
+        assert_eq!((view, rem), (Slice::with_raw_id(ID, b"aa".as_slice()), input.slice(2..)));
+        assert_eq!((TestLoc(view.loc()), rem.offset()), (TestLoc(Loc::encapsulate_range(ID, 11..13)), 13));
+        let (head, _) = view.slice(1..).head_loc();
+        assert_eq!(head.map(|(h, loc)| (h, TestLoc(loc))), Some((b'a', TestLoc(Loc::encapsulate_range(ID, 12..13)))));
+        // Greedy parsers stop at its boundary
+        let (res, view_rem) = Vec::<char>::parse(view).unwrap();
+        assert_eq!((res, view_rem.is_empty(), view_rem.offset()), (vec!['a', 'a'], true, 13));
+
+        // Overly large views are truncated
+        let (view, rem) = input.take(usize::MAX);
+        assert_eq!((view, rem.is_empty(), rem.offset()), (input, true, 15));
+    }
+
+    #[test]
+    fn test_slice_str() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"Hello World".as_slice());
+
+        let ((_, loc), rem) = input.head_slice_loc(5);
+        assert_eq!(rem.slice_str(&loc), Some("Hello"));
+        assert_eq!(rem.slice_bytes(&loc), Some(b"Hello".as_slice()));
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(ID, 6..)), Some("World"));
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(ID, 6..6)), Some(""));
+
+        // Mismatching sources and out-of-range locs
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(1, ..5)), None);
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(ID, 6..12)), None);
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(ID, 12..)), None);
+        let input = Slice::with_raw_id_at(ID, 2, b"llo".as_slice());
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(ID, 2..4)), Some("ll"));
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(ID, 1..4)), None);
+        let input = Slice::with_raw_id(ID, [0xC3u8, 0xA9].as_slice());
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(ID, ..1)), None);
+        assert_eq!(input.slice_str(&Loc::encapsulate_range(ID, ..2)), Some("é"));
+    }
+
+    #[test]
+    fn test_slice_with_id() {
+        // Two separate buffers of the same logical source
+        let buf1: Vec<u8> = b"Hello World".to_vec();
+        let buf2: Vec<u8> = b"Hello World".to_vec();
+
+        // By pointer, their Locs can't be merged...
+        let ((_, lhs), _) = Slice::new(buf1.as_slice()).head_slice_loc(5);
+        let ((_, rhs), _) = Slice::new(buf2.as_slice()).slice(6..).head_slice_loc(5);
+        let loc = lhs.join(rhs);
+        assert_eq!(loc.range, Range::from(..5));
+
+        // ...but with the same ID, they can
+        let ((_, lhs), _) = Slice::with_id("hello.txt", buf1.as_slice()).head_slice_loc(5);
+        let ((_, rhs), _) = Slice::with_id("hello.txt", buf2.as_slice()).slice(6..).head_slice_loc(5);
+        assert_eq!(TestLoc(lhs.join(rhs)), TestLoc(Loc::encapsulate_range(lhs.source.unwrap(), ..11)));
+        let ((_, lhs), _) = Slice::with_raw_id(42, buf1.as_slice()).head_slice_loc(5);
+        let ((_, rhs), _) = Slice::with_raw_id(42, buf2.as_slice()).slice(6..).head_slice_loc(5);
+        assert_eq!(TestLoc(lhs.join(rhs)), TestLoc(Loc::encapsulate_range(42, ..11)));
+    }
+}
@@ -0,0 +1,90 @@
+//  TEST.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines helpers for testing the parsers in this module.
+//
+
+use thiserror::Error;
+
+
+/***** LIBRARY *****/
+/// Defines a [`Tag`](crate::tree::Tag) over bytes for testing purposes.
+///
+/// The type is a tuple struct around a [`TestLoc`](crate::loc::test::TestLoc) such that parsed
+/// locations are compared strictly.
+///
+/// # Arguments
+/// - `name`: The identifier of the type to define.
+/// - `tag`: A byte string literal representing the tag to parse.
+/// - `elem`: Optionally, the type of element to parse the tag from (e.g., `tag!(Name: char, &['a'])`).
+///   If given, `tag` may be any expression of type `&'static [elem]`.
+macro_rules! tag {
+    ($name:ident, $tag:literal) => {
+        crate::nibble::test::tag!($name: u8, $tag);
+    };
+    ($name:ident: $elem:ty, $tag:expr) => {
+        #[derive(Debug, Eq, PartialEq)]
+        struct $name(crate::loc::test::TestLoc);
+        impl crate::loc::Located for $name {
+            #[inline]
+            fn loc(&self) -> crate::loc::Loc { self.0.into() }
+        }
+        impl crate::tree::Node for $name {
+            #[inline]
+            fn kind(&self) -> crate::tree::NodeKind { crate::tree::NodeKind::Term }
+
+            #[inline]
+            fn walk(&self, visitor: &mut dyn crate::tree::visit::Visitor) -> ::std::ops::ControlFlow<()> {
+                crate::tree::visit::walk_term(self, visitor)
+            }
+        }
+        impl crate::tree::Term for $name {}
+        impl crate::tree::Tag<$elem> for $name {
+            const TAG: &'static [$elem] = $tag;
+
+            #[inline]
+            fn with_loc(loc: crate::loc::Loc) -> Self { Self(crate::loc::test::TestLoc(loc)) }
+        }
+    };
+}
+pub(crate) use tag;
+
+/// Defines a parser over bytes that fails with a hard [`TestError`] when it sees the given tag, for
+/// testing purposes.
+///
+/// If the tag isn't there, it's unmatched instead.
+///
+/// # Arguments
+/// - `name`: The identifier of the type to define.
+/// - `tag`: A byte string literal representing the tag to error on.
+macro_rules! illegal {
+    ($name:ident, $tag:literal) => {
+        #[derive(Debug, Eq, PartialEq)]
+        struct $name;
+        impl crate::nibble::Parsable<u8> for $name {
+            type Formatter = &'static str;
+            type Error = crate::nibble::test::TestError;
+
+            #[inline]
+            fn expects() -> Self::Formatter { ::std::stringify!($name) }
+
+            #[inline]
+            fn parse(
+                input: crate::nibble::Slice<u8>,
+            ) -> Result<(Self, crate::nibble::Slice<u8>), crate::nibble::NibbleError<Self::Formatter, Self::Error>> {
+                if input.starts_with($tag) {
+                    Err(crate::nibble::NibbleError::Error(crate::nibble::test::TestError(::std::stringify!($name))))
+                } else {
+                    Err(crate::nibble::NibbleError::Unmatched(Self::expects(), None))
+                }
+            }
+        }
+    };
+}
+pub(crate) use illegal;
+
+/// The error emitted by parsers defined with [`illegal!()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+#[error("Illegal {0}")]
+pub struct TestError(pub &'static str);
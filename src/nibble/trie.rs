@@ -0,0 +1,206 @@
+//  TRIE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements [`TagTrie`], which matches the longest of a large set of
+//!   keywords in a single pass over the input.
+//
+
+use std::convert::Infallible;
+
+use super::error::Needed;
+use super::keywords::KeywordsFormatter;
+use super::{NibbleError, Slice};
+use crate::loc::Loc;
+
+
+/***** HELPERS *****/
+/// A single node in a [`TagTrie`].
+#[derive(Clone, Debug)]
+struct TrieNode {
+    /// The outgoing edges of this node, sorted by byte.
+    children: Vec<(u8, usize)>,
+    /// The index of the keyword ending in this node, if any.
+    tag: Option<usize>,
+    /// The minimum and maximum number of bytes to any keyword ending strictly below this node.
+    rest: Option<(usize, usize)>,
+}
+impl TrieNode {
+    /// Constructor for an empty TrieNode.
+    ///
+    /// # Returns
+    /// A new TrieNode without any children or keyword.
+    #[inline]
+    const fn new() -> Self { Self { children: Vec::new(), tag: None, rest: None } }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Matches the longest of a set of keywords at the start of the input.
+///
+/// This does the same as [`longest_tag()`](super::keywords::longest_tag()), but instead of trying
+/// every keyword in turn, it walks the input once through a trie of all of them. Building it
+/// takes some time, so it's worth it for large sets of keywords that are matched often. The
+/// [`keywords!`](super::keywords!)-macro builds one lazily for you.
+#[derive(Clone, Debug)]
+pub struct TagTrie {
+    /// The keywords in this trie.
+    tags:  &'static [&'static str],
+    /// The nodes of the trie, where the first one is the root.
+    nodes: Vec<TrieNode>,
+}
+impl TagTrie {
+    /// Constructor for the TagTrie.
+    ///
+    /// # Arguments
+    /// - `tags`: The keywords to match. If one occurs multiple times, then its first index is
+    ///   reported.
+    ///
+    /// # Returns
+    /// A new TagTrie that matches any of the `tags`.
+    pub fn new(tags: &'static [&'static str]) -> Self {
+        let mut nodes: Vec<TrieNode> = vec![TrieNode::new()];
+        for (i, tag) in tags.iter().enumerate() {
+            let mut node: usize = 0;
+            for (depth, b) in tag.bytes().enumerate() {
+                // Note down that there's a keyword below this one
+                let rest: usize = tag.len() - depth;
+                let (min, max) = nodes[node].rest.get_or_insert((rest, rest));
+                *min = (*min).min(rest);
+                *max = (*max).max(rest);
+
+                // Find (or insert) the next node
+                node = match nodes[node].children.binary_search_by_key(&b, |(c, _)| *c) {
+                    Ok(j) => nodes[node].children[j].1,
+                    Err(j) => {
+                        let next: usize = nodes.len();
+                        nodes.push(TrieNode::new());
+                        nodes[node].children.insert(j, (b, next));
+                        next
+                    },
+                };
+            }
+            nodes[node].tag.get_or_insert(i);
+        }
+        Self { tags, nodes }
+    }
+
+    /// Returns the keywords in this trie.
+    ///
+    /// # Returns
+    /// The keywords this trie was built with, in order.
+    #[inline]
+    pub const fn tags(&self) -> &'static [&'static str] { self.tags }
+
+    /// Matches the longest of the keywords in this trie at the start of the input.
+    ///
+    /// See [`longest_tag()`](super::keywords::longest_tag()) for more information.
+    ///
+    /// # Arguments
+    /// - `input`: The [`Slice`] to parse from.
+    ///
+    /// # Returns
+    /// The index of the matched keyword in [`TagTrie::tags()`], together with its [`Loc`] and the
+    /// remaining input.
+    ///
+    /// # Errors
+    /// This function returns a [`NibbleError::Unmatched`] if none of the keywords matched. If any
+    /// of them is a strict prefix of the input, it is accompanied by a [`Needed`]-hint.
+    #[allow(clippy::type_complexity)]
+    pub fn longest<'a>(&self, input: Slice<'a, u8>) -> Result<((usize, Loc), Slice<'a, u8>), NibbleError<KeywordsFormatter, Infallible>> {
+        let mut best: Option<(usize, usize)> = self.nodes[0].tag.map(|i| (i, 0));
+        let mut node: usize = 0;
+        let mut diverged: bool = false;
+        for (depth, b) in input.iter().enumerate() {
+            match self.nodes[node].children.binary_search_by_key(b, |(c, _)| *c) {
+                Ok(j) => node = self.nodes[node].children[j].1,
+                Err(_) => {
+                    diverged = true;
+                    break;
+                },
+            }
+            if let Some(i) = self.nodes[node].tag {
+                best = Some((i, depth + 1));
+            }
+        }
+
+        match best {
+            Some((i, len)) => {
+                let ((_, loc), rem) = input.head_slice_loc(len);
+                Ok(((i, loc), rem))
+            },
+            // We only need more input if we ran out while still in the trie; diverging input can
+            // never be fixed by more of it
+            None if diverged => Err(NibbleError::Unmatched(KeywordsFormatter::new(self.tags), None)),
+            None => Err(NibbleError::Unmatched(KeywordsFormatter::new(self.tags), self.nodes[node].rest.map(|(min, max)| Needed::Bounded(min, max)))),
+        }
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::keywords::longest_tag;
+
+    const TAGS: &[&str] = &["in", "instanceof", "is"];
+    const JS: &[&str] = &[
+        "abstract", "arguments", "await", "boolean", "break", "byte", "case", "catch", "char", "class", "const", "continue", "debugger", "default",
+        "delete", "do", "double", "else", "enum", "eval", "export", "extends", "false", "final", "finally", "float", "for", "function", "goto",
+        "if", "implements", "import", "in", "instanceof", "int", "interface", "let", "long", "native", "new", "null", "package", "private",
+        "protected", "public", "return", "short", "static", "super", "switch", "synchronized", "this", "throw", "throws", "transient", "true",
+        "try", "typeof", "var", "void", "volatile", "while", "with", "yield",
+    ];
+
+    #[test]
+    fn test_tag_trie() {
+        const ID: u64 = 0;
+        let trie = TagTrie::new(TAGS);
+        let input1 = Slice::with_raw_id(ID, b"in x".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"instanceof x".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"insta".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"x".as_slice());
+
+        let ((i, loc), rem) = trie.longest(input1).unwrap();
+        assert_eq!((i, TestLoc(loc), rem), (0, TestLoc(Loc::encapsulate_range(ID, ..2)), input1.slice(2..)));
+        let ((i, loc), rem) = trie.longest(input2).unwrap();
+        assert_eq!((i, TestLoc(loc), rem), (1, TestLoc(Loc::encapsulate_range(ID, ..10)), input2.slice(10..)));
+        // A shorter match still wins if the longer one would need more input
+        let ((i, _), rem) = trie.longest(input3).unwrap();
+        assert_eq!((i, rem), (0, input3.slice(2..)));
+        assert_eq!(trie.longest(input4), Err(NibbleError::Unmatched(KeywordsFormatter::new(TAGS), None)));
+    }
+
+    #[test]
+    fn test_tag_trie_needed() {
+        const ID: u64 = 0;
+        let trie = TagTrie::new(TAGS);
+
+        assert_eq!(trie.longest(Slice::with_raw_id(ID, b"i".as_slice())), Err(NibbleError::Unmatched(KeywordsFormatter::new(TAGS), Some(Needed::Bounded(1, 9)))));
+        assert_eq!(trie.longest(Slice::with_raw_id(ID, b"".as_slice())), Err(NibbleError::Unmatched(KeywordsFormatter::new(TAGS), Some(Needed::Bounded(2, 10)))));
+        assert_eq!(trie.longest(Slice::with_raw_id(ID, b"ix".as_slice())), Err(NibbleError::Unmatched(KeywordsFormatter::new(TAGS), None)));
+    }
+
+    #[test]
+    fn test_tag_trie_agrees_with_longest_tag() {
+        const ID: u64 = 0;
+        let trie = TagTrie::new(JS);
+
+        // Try every keyword, every prefix of it and every keyword followed by some more input
+        for tag in JS {
+            for input in (0..=tag.len()).map(|i| tag[..i].to_string()).chain([format!("{tag} x"), format!("{tag}_"), format!("{tag}s")]) {
+                let input = Slice::with_raw_id(ID, input.as_bytes());
+                let expected = longest_tag(input, JS).map(|((i, loc), rem)| ((i, TestLoc(loc)), rem));
+                assert_eq!(trie.longest(input).map(|((i, loc), rem)| ((i, TestLoc(loc)), rem)), expected, "{:?}", std::str::from_utf8(&input));
+            }
+        }
+    }
+}
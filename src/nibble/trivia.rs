@@ -0,0 +1,284 @@
+//  TRIVIA.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements skipping of trivia, i.e., whitespace and comments, between
+//!   nodes.
+//!
+//!   Unlike the [`whitespace`](super::whitespace)-module, what counts as a
+//!   comment is configured at runtime through a [`Trivia`].
+//
+
+use thiserror::Error;
+
+use super::combinators::Either;
+use super::{NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+
+
+/***** ERRORS *****/
+/// Defines the errors emitted by [`Trivia::skip()`].
+#[derive(Debug, Eq, Error, PartialEq)]
+pub enum TriviaError {
+    /// A block comment was opened but never closed.
+    #[error("Unterminated block comment (missing {close:?})")]
+    Unterminated { loc: Loc, close: &'static str },
+}
+impl Located for TriviaError {
+    #[inline]
+    fn loc(&self) -> Loc {
+        match self {
+            Self::Unterminated { loc, .. } => *loc,
+        }
+    }
+}
+
+
+
+
+
+/***** LIBRARY *****/
+/// Defines what counts as trivia, i.e., what can be skipped between nodes.
+///
+/// ASCII whitespace is always trivia. On top of that, you can configure line comments (which run
+/// until the end of the line) and block comments (which run until their closing delimiter).
+///
+/// Empty tokens are ignored, as they would match everywhere.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Trivia {
+    /// The tokens starting line comments.
+    line:   &'static [&'static str],
+    /// The tokens opening and closing block comments.
+    block:  &'static [(&'static str, &'static str)],
+    /// Whether block comments may be nested.
+    nested: bool,
+}
+impl Default for Trivia {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl Trivia {
+    /// Constructor for a Trivia that only skips whitespace.
+    ///
+    /// # Returns
+    /// A new Trivia without any comments.
+    #[inline]
+    pub const fn new() -> Self { Self { line: &[], block: &[], nested: false } }
+
+    /// Sets the tokens that start line comments.
+    ///
+    /// Empty tokens are ignored.
+    ///
+    /// # Arguments
+    /// - `line`: The tokens, e.g., `["//", "#"]`.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_line(mut self, line: &'static [&'static str]) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Sets the pairs of tokens that open and close block comments.
+    ///
+    /// Pairs with an empty opening token are ignored. An empty closing token closes the comment
+    /// immediately after its opening token.
+    ///
+    /// # Arguments
+    /// - `block`: The pairs of tokens, e.g., `[("/*", "*/")]`.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_block(mut self, block: &'static [(&'static str, &'static str)]) -> Self {
+        self.block = block;
+        self
+    }
+
+    /// Sets whether block comments may be nested.
+    ///
+    /// If so, every opening token within a block comment must be matched by its own closing
+    /// token. Only the same pair of tokens nests.
+    ///
+    /// # Arguments
+    /// - `nested`: Whether to allow nesting.
+    ///
+    /// # Returns
+    /// Self for chaining.
+    #[inline]
+    pub const fn with_nested(mut self, nested: bool) -> Self {
+        self.nested = nested;
+        self
+    }
+
+    /// Skips all trivia at the start of the given input.
+    ///
+    /// Because trivia is optional, this always matches; if there's none, then the [`Loc`] is
+    /// empty.
+    ///
+    /// # Arguments
+    /// - `input`: The [`Slice`] to skip in.
+    ///
+    /// # Returns
+    /// A tuple with the [`Loc`] of the skipped trivia and the remainder of the input.
+    ///
+    /// # Errors
+    /// This function errors if a block comment is not closed before the end of the input.
+    pub fn skip<'a>(&self, input: Slice<'a, u8>) -> Result<(Loc, Slice<'a, u8>), TriviaError> {
+        let mut rem: Slice<u8> = input;
+        loop {
+            // Whitespace
+            let len: usize = rem.iter().take_while(|b| b.is_ascii_whitespace()).count();
+            rem = rem.slice(len..);
+
+            // Line comments
+            if self.line.iter().any(|open| !open.is_empty() && rem.starts_with(open.as_bytes())) {
+                let len: usize = rem.iter().take_while(|b| **b != b'\n').count();
+                rem = rem.slice(len..);
+                continue;
+            }
+
+            // Block comments
+            if let Some((open, close)) = self.block.iter().find(|(open, _)| !open.is_empty() && rem.starts_with(open.as_bytes())) {
+                let start: Slice<u8> = rem;
+                let mut depth: usize = 1;
+                rem = rem.slice(open.len()..);
+                while depth > 0 {
+                    if rem.is_empty() {
+                        return Err(TriviaError::Unterminated { loc: start.loc_until(&rem), close });
+                    } else if rem.starts_with(close.as_bytes()) {
+                        depth -= 1;
+                        rem = rem.slice(close.len()..);
+                    } else if self.nested && rem.starts_with(open.as_bytes()) {
+                        depth += 1;
+                        rem = rem.slice(open.len()..);
+                    } else {
+                        rem = rem.slice(1..);
+                    }
+                }
+                continue;
+            }
+
+            // Anything else isn't trivia
+            if len == 0 {
+                break;
+            }
+        }
+        Ok((input.loc_until(&rem), rem))
+    }
+}
+
+
+
+/// Parses a `T`, skipping any [`Trivia`] before and after it.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+/// - `trivia`: The [`Trivia`] defining what to skip.
+///
+/// # Returns
+/// A tuple with the parsed `T` and the remainder of the input, after the trivia following it.
+///
+/// # Errors
+/// This function errors if `T::parse()` does, or if a block comment around it is unterminated.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn with_trivia<'a, T: Parsable<u8>>(
+    input: Slice<'a, u8>,
+    trivia: &Trivia,
+) -> Result<(T, Slice<'a, u8>), NibbleError<T::Formatter, Either<TriviaError, T::Error>>> {
+    let (_, rem) = trivia.skip(input).map_err(|err| NibbleError::Error(Either::Left(err)))?;
+    let (res, rem) = T::parse(rem).map_err(|err| match err {
+        NibbleError::Unmatched(fmt, needed) => NibbleError::Unmatched(fmt, needed),
+        NibbleError::Error(err) => NibbleError::Error(Either::Right(err)),
+    })?;
+    let (_, rem) = trivia.skip(rem).map_err(|err| NibbleError::Error(Either::Left(err)))?;
+    Ok((res, rem))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::tag;
+
+    tag!(Hello, b"Hello");
+
+    const TRIVIA: Trivia = Trivia::new().with_line(&["//", "#"]).with_block(&[("/*", "*/")]);
+
+    #[test]
+    fn test_trivia_skip() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"  # a\n// b\n\tHello".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"/* a */Hello".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"Hello".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"// a".as_slice());
+
+        let (loc, rem) = TRIVIA.skip(input1).unwrap();
+        assert_eq!((TestLoc(loc), rem), (TestLoc(Loc::encapsulate_range(ID, ..12)), input1.slice(12..)));
+        let (loc, rem) = TRIVIA.skip(input2).unwrap();
+        assert_eq!((TestLoc(loc), rem), (TestLoc(Loc::encapsulate_range(ID, ..7)), input2.slice(7..)));
+        let (loc, rem) = TRIVIA.skip(input3).unwrap();
+        assert_eq!((TestLoc(loc), rem), (TestLoc(Loc::encapsulate_range(ID, ..0)), input3));
+        assert_eq!(TRIVIA.skip(input4).map(|(_, rem)| rem), Ok(input4.slice(4..)));
+        // Only whitespace, by default
+        assert_eq!(Trivia::new().skip(input1).map(|(_, rem)| rem), Ok(input1.slice(2..)));
+    }
+
+    #[test]
+    fn test_trivia_nested() {
+        const ID: u64 = 0;
+        let input = Slice::with_raw_id(ID, b"/* a /* b */ c */ Hello".as_slice());
+
+        assert_eq!(TRIVIA.with_nested(true).skip(input).map(|(_, rem)| rem), Ok(input.slice(18..)));
+        // Without nesting, the first close ends the comment
+        assert_eq!(TRIVIA.skip(input).map(|(_, rem)| rem), Ok(input.slice(13..)));
+    }
+
+    #[test]
+    fn test_trivia_unterminated() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b" /* a".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"/* a /* b */".as_slice());
+
+        let err = TRIVIA.skip(input1).unwrap_err();
+        assert_eq!(TestLoc(err.loc()), TestLoc(Loc::encapsulate_range(ID, 1..5)));
+        assert_eq!(err.to_string(), "Unterminated block comment (missing \"*/\")");
+        assert!(TRIVIA.skip(input2).is_ok());
+        assert!(TRIVIA.with_nested(true).skip(input2).is_err());
+    }
+
+    #[test]
+    fn test_trivia_empty_tokens() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b" Hello".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"/**/Hello".as_slice());
+
+        // Empty tokens must not match (and loop) everywhere
+        let trivia = Trivia::new().with_line(&[""]).with_block(&[("", "*/")]);
+        assert_eq!(trivia.skip(input1).map(|(_, rem)| rem), Ok(input1.slice(1..)));
+        assert_eq!(trivia.skip(input2).map(|(_, rem)| rem), Ok(input2));
+        // Empty closing tokens simply close immediately
+        assert_eq!(Trivia::new().with_block(&[("/*", "")]).skip(input3).map(|(_, rem)| rem), Ok(input3.slice(2..)));
+    }
+
+    #[test]
+    fn test_with_trivia() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"/* a /* b */ c */ Hello // d\n!".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"/* Hello */ World".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"Hello /*".as_slice());
+        let trivia = TRIVIA.with_nested(true);
+
+        assert_eq!(with_trivia::<Hello>(input1, &trivia), Ok((Hello(TestLoc(Loc::encapsulate_range(ID, 18..23))), input1.slice(29..))));
+        assert_eq!(with_trivia::<Hello>(input2, &trivia), Err(NibbleError::Unmatched(Hello::expects(), None)));
+        assert!(matches!(with_trivia::<Hello>(input3, &trivia), Err(NibbleError::Error(Either::Left(TriviaError::Unterminated { .. })))));
+    }
+}
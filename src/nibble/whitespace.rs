@@ -0,0 +1,251 @@
+//  WHITESPACE.rs
+//    by Lut99
+//
+//  Description:
+//!   Implements parsers for skipping whitespace between nodes.
+//!
+//!   What counts as whitespace is configurable through the
+//!   [`WhitespaceSet`]-trait. By default, [`AsciiWhitespace`] is used.
+//
+
+use std::convert::Infallible;
+use std::fmt::{Display, Formatter, Result as FResult};
+use std::marker::PhantomData;
+
+use super::{NibbleError, Parsable, Slice};
+use crate::loc::{Loc, Located};
+
+
+/***** INTERFACES *****/
+/// Defines which elements count as whitespace.
+///
+/// Implement this on your own type if the defaults don't fit your language, e.g., because
+/// newlines are significant in it.
+pub trait WhitespaceSet<E> {
+    /// Decides if the given element is whitespace.
+    ///
+    /// # Arguments
+    /// - `elem`: The element to decide for.
+    ///
+    /// # Returns
+    /// True if it is whitespace (and should be skipped), or false otherwise.
+    fn is_whitespace(elem: &E) -> bool;
+}
+
+
+
+/// The default [`WhitespaceSet`]: all ASCII whitespace (i.e., spaces, tabs, newlines, carriage
+/// returns and form feeds).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AsciiWhitespace;
+impl WhitespaceSet<u8> for AsciiWhitespace {
+    #[inline]
+    fn is_whitespace(elem: &u8) -> bool { elem.is_ascii_whitespace() }
+}
+impl WhitespaceSet<char> for AsciiWhitespace {
+    #[inline]
+    fn is_whitespace(elem: &char) -> bool { elem.is_ascii_whitespace() }
+}
+
+
+
+
+
+/***** FORMATTERS *****/
+/// Formatter for [`Whitespace::expects()`].
+#[derive(Debug, Eq, PartialEq)]
+pub struct WhitespaceFormatter;
+impl Display for WhitespaceFormatter {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> FResult { write!(f, "whitespace") }
+}
+
+
+
+
+
+/***** HELPER FUNCTIONS *****/
+/// Skips all whitespace at the start of the given input.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to skip in.
+///
+/// # Returns
+/// A tuple with the [`Loc`] of the skipped whitespace and the remainder of the input.
+#[inline]
+fn skip_loc<W: WhitespaceSet<E>, E>(input: Slice<E>) -> (Loc, Slice<E>) {
+    let len: usize = input.iter().take_while(|e| W::is_whitespace(e)).count();
+    let ((_, loc), rem) = input.head_slice_loc(len);
+    (loc, rem)
+}
+
+/// Like [`skip_loc()`], but discards the [`Loc`].
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to skip in.
+///
+/// # Returns
+/// The remainder of the input.
+#[inline]
+fn skip<W: WhitespaceSet<E>, E>(input: Slice<E>) -> Slice<E> { skip_loc::<W, E>(input).1 }
+
+
+
+
+
+/***** LIBRARY *****/
+/// Parses zero or more whitespace elements, as defined by some [`WhitespaceSet`] `W`.
+///
+/// Because whitespace is optional, this parser always matches, giving an empty [`Loc`] if no
+/// whitespace was found.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Whitespace<W = AsciiWhitespace> {
+    /// The location of the whitespace that was parsed.
+    pub loc: Loc,
+    /// Remembers the set of whitespace used.
+    _w:      PhantomData<W>,
+}
+impl<W> Whitespace<W> {
+    /// Constructor for the Whitespace.
+    ///
+    /// # Arguments
+    /// - `loc`: The location of the whitespace.
+    ///
+    /// # Returns
+    /// A new Whitespace.
+    #[inline]
+    pub const fn new(loc: Loc) -> Self { Self { loc, _w: PhantomData } }
+}
+
+// Loc
+impl<W> Located for Whitespace<W> {
+    #[inline]
+    fn loc(&self) -> Loc { self.loc }
+}
+
+// Parsing
+impl<E, W: WhitespaceSet<E>> Parsable<E> for Whitespace<W> {
+    type Formatter = WhitespaceFormatter;
+    type Error = Infallible;
+
+    #[inline]
+    fn expects() -> Self::Formatter { WhitespaceFormatter }
+
+    #[inline]
+    fn parse(input: Slice<E>) -> Result<(Self, Slice<E>), NibbleError<Self::Formatter, Self::Error>> {
+        let (loc, rem) = skip_loc::<W, E>(input);
+        Ok((Self::new(loc), rem))
+    }
+}
+
+
+
+/// Parses a `T`, skipping any [`AsciiWhitespace`] before and after it.
+///
+/// See [`ws_around_with()`] to use another [`WhitespaceSet`].
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input, positioned after any trailing
+/// whitespace.
+///
+/// # Errors
+/// This function errors whenever `T` does.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn ws_around<'a, T, E>(input: Slice<'a, E>) -> Result<(T, Slice<'a, E>), NibbleError<T::Formatter, T::Error>>
+where
+    T: Parsable<E>,
+    AsciiWhitespace: WhitespaceSet<E>,
+{
+    ws_around_with::<AsciiWhitespace, T, E>(input)
+}
+
+/// Parses a `T`, skipping any whitespace as defined by `W` before and after it.
+///
+/// # Arguments
+/// - `input`: The [`Slice`] to parse from.
+///
+/// # Returns
+/// A tuple with the parsed value and the remainder of the input, positioned after any trailing
+/// whitespace.
+///
+/// # Errors
+/// This function errors whenever `T` does.
+#[inline]
+#[allow(clippy::type_complexity)]
+pub fn ws_around_with<'a, W, T, E>(input: Slice<'a, E>) -> Result<(T, Slice<'a, E>), NibbleError<T::Formatter, T::Error>>
+where
+    W: WhitespaceSet<E>,
+    T: Parsable<E>,
+{
+    let (value, rem): (T, Slice<E>) = T::parse(skip::<W, E>(input))?;
+    Ok((value, skip::<W, E>(rem)))
+}
+
+
+
+
+
+/***** TESTS *****/
+#[cfg(all(test, feature = "tree"))]
+mod tests {
+    use super::*;
+    use crate::loc::test::TestLoc;
+    use crate::nibble::test::tag;
+
+    tag!(Hello, b"Hello");
+
+    /// Only considers spaces to be whitespace.
+    #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+    struct Spaces;
+    impl WhitespaceSet<u8> for Spaces {
+        #[inline]
+        fn is_whitespace(elem: &u8) -> bool { *elem == b' ' }
+    }
+
+    #[test]
+    fn test_whitespace() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"   Hello".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Hello".as_slice());
+        let input3 = Slice::with_raw_id(ID, b" \t\n ".as_slice());
+        let input4 = Slice::with_raw_id(ID, b"".as_slice());
+
+        let (ws, rem) = Whitespace::<AsciiWhitespace>::parse(input1).unwrap();
+        assert_eq!((TestLoc(ws.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..3)), input1.slice(3..)));
+        // Whitespace is optional
+        let (ws, rem) = Whitespace::<AsciiWhitespace>::parse(input2).unwrap();
+        assert_eq!((TestLoc(ws.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..0)), input2));
+        // Running into the end-of-input is fine
+        let (ws, rem) = Whitespace::<AsciiWhitespace>::parse(input3).unwrap();
+        assert_eq!((TestLoc(ws.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..4)), input3.slice(4..)));
+        let (ws, rem) = Whitespace::<AsciiWhitespace>::parse(input4).unwrap();
+        assert_eq!((TestLoc(ws.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..0)), input4));
+
+        // Custom sets are respected
+        let (ws, rem) = Whitespace::<Spaces>::parse(input3).unwrap();
+        assert_eq!((TestLoc(ws.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..1)), input3.slice(1..)));
+
+        // Chars work too
+        let chars: Vec<char> = "   Hello".chars().collect();
+        let input5 = Slice::with_raw_id(ID, chars.as_slice());
+        let (ws, rem) = Whitespace::<AsciiWhitespace>::parse(input5).unwrap();
+        assert_eq!((TestLoc(ws.loc()), rem), (TestLoc(Loc::encapsulate_range(ID, ..3)), input5.slice(3..)));
+    }
+
+    #[test]
+    fn test_ws_around() {
+        const ID: u64 = 0;
+        let input1 = Slice::with_raw_id(ID, b"   Hello".as_slice());
+        let input2 = Slice::with_raw_id(ID, b"Hello \n!".as_slice());
+        let input3 = Slice::with_raw_id(ID, b"   Goodbye".as_slice());
+
+        assert_eq!(ws_around::<Hello, _>(input1), Ok((Hello(TestLoc(Loc::encapsulate_range(ID, 3..8))), input1.slice(8..))));
+        assert_eq!(ws_around::<Hello, _>(input2), Ok((Hello(TestLoc(Loc::encapsulate_range(ID, ..5))), input2.slice(7..))));
+        assert_eq!(ws_around::<Hello, _>(input3), Err(NibbleError::Unmatched(Hello::expects(), None)));
+        assert_eq!(ws_around_with::<Spaces, Hello, _>(input2), Ok((Hello(TestLoc(Loc::encapsulate_range(ID, ..5))), input2.slice(6..))));
+    }
+}
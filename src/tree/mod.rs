@@ -15,19 +15,81 @@
 
 // Modules
 mod tag;
+pub mod visit;
 
 // Re-export some node macros
 #[cfg(feature = "proc-macros")]
 pub use ast_toolkit2_proc_macros::{Node, NonTerm, Tag, Term};
 pub use tag::Tag;
 
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
 use crate::loc::Located;
+use visit::Visitor;
 
 /// Shorthand for including all the traits of this crate.
 pub mod prelude {
     pub use super::*;
 }
 
+/// Implementation details of the derive macros. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    use super::NodeKind;
+
+    /// Provides the [`NodeKind`] for the `Node`-derive if neither the `Term`- nor the
+    /// `NonTerm`-derive emitted an inherent constant (which takes precedence) for it.
+    pub trait NodeKindFallback {
+        const __AST_TOOLKIT2_NODE_KIND: Option<NodeKind> = None;
+    }
+    impl<T: ?Sized> NodeKindFallback for T {}
+}
+
+
+/***** HELPER MACROS *****/
+/// Implements [`Node`] and [`NonTerm`] (and optionally [`Term`]) for a pointer-like type by
+/// delegating to its pointee.
+macro_rules! node_ptr_impl {
+    ($ty:ident, term) => {
+        node_ptr_impl!($ty);
+        impl<T: ?Sized + Term> Term for $ty<T> {}
+    };
+    ($ty:ident) => {
+        impl<T: ?Sized + Node> Node for $ty<T> {
+            #[inline]
+            fn kind(&self) -> NodeKind { <T as Node>::kind(self) }
+
+            #[inline]
+            fn walk(&self, visitor: &mut dyn Visitor) -> ControlFlow<()> { <T as Node>::walk(self, visitor) }
+        }
+        impl<T: ?Sized + NonTerm> NonTerm for $ty<T> {
+            #[inline]
+            fn children(&self) -> Vec<&dyn Node> { <T as NonTerm>::children(self) }
+        }
+    };
+}
+
+
+
+
+
+/***** AUXILLARY *****/
+/// Describes which flavour of [`Node`] some node is.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NodeKind {
+    /// It's a [`Term`]inal.
+    Term,
+    /// It's a [`NonTerm`]inal.
+    NonTerm,
+}
+
+
+
+
 
 /***** INTERFACE *****/
 /// Defines a generic node in your AST.
@@ -45,7 +107,33 @@ pub mod prelude {
 ///   Terminals explicitly _don't_ have any children.
 ///
 /// Despite this difference, this trait represents the general part of the two.
-pub trait Node: Located {}
+///
+/// Note that [`Term`] and [`NonTerm`] are mutually exclusive by convention. This isn't enforced
+/// by the compiler, but [`Node::kind()`] can only report one of them.
+pub trait Node: Located {
+    /// Returns whether this node is a [`Term`]inal or [`NonTerm`]inal at runtime.
+    ///
+    /// This is useful for generic tooling working with `&dyn Node`s, e.g., to decide whether to
+    /// descend into a node. The `Node`-derive implements this for you.
+    ///
+    /// # Returns
+    /// A [`NodeKind`] matching which of [`Term`] or [`NonTerm`] this node implements.
+    fn kind(&self) -> NodeKind;
+
+    /// Traverses this node and its children with the given [`Visitor`].
+    ///
+    /// Implementations should call [`Visitor::visit_node()`], followed by either
+    /// [`Visitor::visit_term()`] or [`Visitor::visit_nonterm()`], and then walk their children
+    /// in order. The `Node`-derive does this for you.
+    ///
+    /// # Arguments
+    /// - `visitor`: The [`Visitor`] to call for every node.
+    ///
+    /// # Returns
+    /// [`ControlFlow::Break`] if the visitor stopped the traversal early, or else
+    /// [`ControlFlow::Continue`].
+    fn walk(&self, visitor: &mut dyn Visitor) -> ControlFlow<()>;
+}
 
 
 
@@ -55,7 +143,15 @@ pub trait Node: Located {}
 /// syntax; rather, they tend to treat syntax as being tokenized, i.e., concerned with the count
 /// and order of specific, already parsed, constructs rather than with e.g. whitespace. You can
 /// think of them as an understanding of a stream of [`Term`]inals.
-pub trait NonTerm: Node {}
+pub trait NonTerm: Node {
+    /// Returns the children of this node.
+    ///
+    /// This allows ASTs to be traversed generically, e.g., by visitors or pretty-printers.
+    ///
+    /// # Returns
+    /// A list of references to this node's direct children, in the order they appear in the node.
+    fn children(&self) -> Vec<&dyn Node>;
+}
 
 
 
@@ -65,3 +161,14 @@ pub trait NonTerm: Node {}
 /// syntax, and parsing them requires worrying about encodings, whitespaces, etc. You can think of
 /// them forming a stream of the input, and [`NonTerm`]inals an understanding of that stream.
 pub trait Term: Node {}
+
+
+
+
+
+/***** IMPL *****/
+node_ptr_impl!(Box, term);
+// NOTE: These aren't `Term`, because that would allow downstream crates to implement `Tag` for
+// them, conflicting with their `Parsable`-implementations in `nibble`.
+node_ptr_impl!(Rc);
+node_ptr_impl!(Arc);
@@ -22,7 +22,7 @@ pub trait Tag<E: 'static>: Sized + Term {
 
     /// Constructor for the Tag.
     ///
-    /// The default implementation simply refers to [`Utf8Tag::with_loc()`] with a [`Loc::new()`].
+    /// The default implementation simply refers to [`Tag::with_loc()`] with a [`Loc::new()`].
     ///
     /// # Returns
     /// A new instance of Self that is not tied to any Loc.
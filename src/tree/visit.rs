@@ -0,0 +1,105 @@
+//  VISIT.rs
+//    by Lut99
+//
+//  Description:
+//!   Defines the [`Visitor`]-trait for traversing ASTs generically.
+//!
+//!   Traversal is driven by [`Node::walk()`], which calls the visitor's
+//!   hooks pre-order and then recurses into the node's children. As such,
+//!   a visitor only has to implement the hooks it's interested in.
+//
+
+use core::ops::ControlFlow;
+
+use super::Node;
+
+
+/***** LIBRARY *****/
+/// Defines something that can visit the nodes in an AST.
+///
+/// All hooks default to doing nothing and continuing the traversal. Return
+/// [`ControlFlow::Break`] from any of them to stop it early.
+///
+/// For every visited node, [`Visitor::visit_node()`] is called first, and then either
+/// [`Visitor::visit_term()`] or [`Visitor::visit_nonterm()`] depending on its flavour. After that,
+/// its children are walked.
+pub trait Visitor {
+    /// Called for every node in the tree.
+    ///
+    /// # Arguments
+    /// - `node`: The [`Node`] that is visited.
+    ///
+    /// # Returns
+    /// Whether to continue the traversal or not.
+    #[inline]
+    fn visit_node(&mut self, node: &dyn Node) -> ControlFlow<()> {
+        let _ = node;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for every [`Term`](super::Term)inal in the tree.
+    ///
+    /// # Arguments
+    /// - `term`: The terminal [`Node`] that is visited.
+    ///
+    /// # Returns
+    /// Whether to continue the traversal or not.
+    #[inline]
+    fn visit_term(&mut self, term: &dyn Node) -> ControlFlow<()> {
+        let _ = term;
+        ControlFlow::Continue(())
+    }
+
+    /// Called for every [`NonTerm`](super::NonTerm)inal in the tree, before its children are
+    /// walked.
+    ///
+    /// # Arguments
+    /// - `nonterm`: The non-terminal [`Node`] that is visited.
+    ///
+    /// # Returns
+    /// Whether to continue the traversal or not.
+    #[inline]
+    fn visit_nonterm(&mut self, nonterm: &dyn Node) -> ControlFlow<()> {
+        let _ = nonterm;
+        ControlFlow::Continue(())
+    }
+}
+
+
+
+/// Walks a [`Term`](super::Term)inal node.
+///
+/// This is a helper for implementing [`Node::walk()`].
+///
+/// # Arguments
+/// - `term`: The terminal [`Node`] to walk.
+/// - `visitor`: The [`Visitor`] to call.
+///
+/// # Returns
+/// Whether to continue the traversal or not.
+#[inline]
+pub fn walk_term(term: &dyn Node, visitor: &mut dyn Visitor) -> ControlFlow<()> {
+    visitor.visit_node(term)?;
+    visitor.visit_term(term)
+}
+
+/// Walks a [`NonTerm`](super::NonTerm)inal node.
+///
+/// This is a helper for implementing [`Node::walk()`].
+///
+/// # Arguments
+/// - `nonterm`: The non-terminal [`Node`] to walk.
+/// - `children`: The children of `nonterm`, which are walked in order after `nonterm` itself.
+/// - `visitor`: The [`Visitor`] to call.
+///
+/// # Returns
+/// Whether to continue the traversal or not.
+#[inline]
+pub fn walk_nonterm<'a>(nonterm: &dyn Node, children: impl IntoIterator<Item = &'a dyn Node>, visitor: &mut dyn Visitor) -> ControlFlow<()> {
+    visitor.visit_node(nonterm)?;
+    visitor.visit_nonterm(nonterm)?;
+    for child in children {
+        child.walk(visitor)?;
+    }
+    ControlFlow::Continue(())
+}
@@ -6,8 +6,10 @@
 //!   Showcases the use of the [`Located`](derive@Located)-macro.
 //
 
+use std::marker::PhantomData;
+
 use ast_toolkit2::loc::test::TestLoc;
-use ast_toolkit2::loc::{Loc, Located};
+use ast_toolkit2::loc::{Loc, Located, PackedLoc};
 
 
 /***** TESTS *****/
@@ -478,3 +480,202 @@ fn test_derive_located_enums() {
         TestLoc(Loc::encapsulate_range(25, 2..4))
     );
 }
+
+#[test]
+fn test_derive_located_skip_bound() {
+    /// Struct-style with a phantom generic that isn't a node.
+    #[derive(Located)]
+    #[loc(skip_bound(T))]
+    struct StructPhantom<T: ?Sized> {
+        loc: TestLoc,
+        _t:  PhantomData<T>,
+    }
+
+    /// Enum-style with one bound and one skipped generic.
+    #[derive(Located)]
+    #[loc(skip_bound(U))]
+    enum EnumPhantom<T, U: ?Sized> {
+        Foo(T),
+        Bar { loc: TestLoc, _u: PhantomData<U> },
+    }
+
+    assert_eq!(TestLoc(StructPhantom::<str> { loc: TestLoc(Loc::encapsulate(0)), _t: PhantomData }.loc()), TestLoc(Loc::encapsulate(0)));
+    assert_eq!(TestLoc(EnumPhantom::<TestLoc, str>::Foo(TestLoc(Loc::encapsulate(1))).loc()), TestLoc(Loc::encapsulate(1)));
+    assert_eq!(
+        TestLoc(EnumPhantom::<TestLoc, [u8]>::Bar { loc: TestLoc(Loc::encapsulate(2)), _u: PhantomData }.loc()),
+        TestLoc(Loc::encapsulate(2))
+    );
+}
+
+#[test]
+fn test_derive_located_call() {
+    /// Struct-style that computes its loc from its children.
+    #[derive(Located)]
+    #[loc(call = compute_loc)]
+    struct StructCall {
+        tokens: Vec<TestLoc>,
+    }
+    impl StructCall {
+        fn compute_loc(&self) -> Loc { Loc::merge_all(self.tokens.iter().map(Located::loc)) }
+    }
+
+    /// Enum-style that calls an associated function.
+    #[derive(Located)]
+    #[loc(call = Self::first_loc)]
+    enum EnumCall {
+        Foo(TestLoc, TestLoc),
+        Bar,
+    }
+    impl EnumCall {
+        fn first_loc(this: &Self) -> Loc {
+            match this {
+                Self::Foo(lhs, _) => lhs.loc(),
+                Self::Bar => Loc::new(),
+            }
+        }
+    }
+
+    assert_eq!(
+        TestLoc(StructCall { tokens: vec![TestLoc(Loc::encapsulate_range(0, ..2)), TestLoc(Loc::encapsulate_range(0, 4..6))] }.loc()),
+        TestLoc(Loc::encapsulate_range(0, ..6))
+    );
+    assert_eq!(TestLoc(StructCall { tokens: vec![] }.loc()), TestLoc(Loc::new()));
+    assert_eq!(
+        TestLoc(EnumCall::Foo(TestLoc(Loc::encapsulate_range(1, ..2)), TestLoc(Loc::encapsulate_range(1, 4..6))).loc()),
+        TestLoc(Loc::encapsulate_range(1, ..2))
+    );
+    assert_eq!(TestLoc(EnumCall::Bar.loc()), TestLoc(Loc::new()));
+}
+
+#[test]
+fn test_derive_located_strategy() {
+    /// Only uses the first of the fields.
+    #[derive(Located)]
+    #[loc(strategy = first)]
+    struct StructFirst {
+        #[loc]
+        foo: TestLoc,
+        #[loc]
+        bar: TestLoc,
+    }
+
+    /// Uses the overlap of all fields.
+    #[derive(Located)]
+    #[loc(all, strategy = intersect)]
+    struct StructIntersect(TestLoc, TestLoc);
+
+    /// Variants may override the strategy of the enum.
+    #[derive(Located)]
+    #[loc(all, strategy = first)]
+    enum EnumStrategy {
+        Foo(TestLoc, TestLoc),
+        #[loc(strategy = extend)]
+        Bar { foo: TestLoc, bar: TestLoc },
+    }
+
+    assert_eq!(
+        TestLoc(StructFirst { foo: TestLoc(Loc::encapsulate_range(0, ..2)), bar: TestLoc(Loc::encapsulate_range(0, 2..4)) }.loc()),
+        TestLoc(Loc::encapsulate_range(0, ..2))
+    );
+    assert_eq!(
+        TestLoc(StructIntersect(TestLoc(Loc::encapsulate_range(1, ..4)), TestLoc(Loc::encapsulate_range(1, 2..6))).loc()),
+        TestLoc(Loc::encapsulate_range(1, 2..4))
+    );
+    assert_eq!(
+        TestLoc(EnumStrategy::Foo(TestLoc(Loc::encapsulate_range(2, ..2)), TestLoc(Loc::encapsulate_range(2, 2..4))).loc()),
+        TestLoc(Loc::encapsulate_range(2, ..2))
+    );
+    assert_eq!(
+        TestLoc(EnumStrategy::Bar { foo: TestLoc(Loc::encapsulate_range(3, ..2)), bar: TestLoc(Loc::encapsulate_range(3, 2..4)) }.loc()),
+        TestLoc(Loc::encapsulate_range(3, ..4))
+    );
+}
+
+#[test]
+fn test_derive_located_optional() {
+    /// Struct-style with a single optional loc.
+    #[derive(Located)]
+    struct StructOptional {
+        #[loc(optional)]
+        loc: Option<TestLoc>,
+    }
+
+    /// Tuple-style where only some locs may be there.
+    #[derive(Located)]
+    struct StructOptionalMulti(#[loc(optional)] Option<TestLoc>, #[loc] TestLoc, #[loc(optional)] Option<TestLoc>);
+
+    /// Enum-style, also combined with `#[loc(all)]`.
+    #[derive(Located)]
+    #[loc(all)]
+    enum EnumOptional {
+        Foo {
+            #[loc(optional)]
+            foo: Option<TestLoc>,
+            #[loc(optional)]
+            bar: Option<TestLoc>,
+        },
+        Bar(#[loc(optional)] Option<TestLoc>),
+    }
+
+    /// Picks the first loc that is there.
+    #[derive(Located)]
+    #[loc(strategy = first)]
+    struct StructOptionalFirst(#[loc(optional)] Option<TestLoc>, #[loc(optional)] Option<TestLoc>, #[loc] TestLoc, #[loc] TestLoc);
+
+    assert_eq!(TestLoc(StructOptional { loc: Some(TestLoc(Loc::encapsulate(0))) }.loc()), TestLoc(Loc::encapsulate(0)));
+    assert_eq!(TestLoc(StructOptional { loc: None }.loc()), TestLoc(Loc::new()));
+
+    assert_eq!(
+        TestLoc(StructOptionalMulti(None, TestLoc(Loc::encapsulate_range(1, 2..4)), Some(TestLoc(Loc::encapsulate_range(1, 4..6)))).loc()),
+        TestLoc(Loc::encapsulate_range(1, 2..6))
+    );
+    assert_eq!(
+        TestLoc(StructOptionalMulti(Some(TestLoc(Loc::encapsulate_range(2, ..2))), TestLoc(Loc::encapsulate_range(2, 2..4)), None).loc()),
+        TestLoc(Loc::encapsulate_range(2, ..4))
+    );
+
+    assert_eq!(
+        TestLoc(EnumOptional::Foo { foo: None, bar: Some(TestLoc(Loc::encapsulate_range(3, 2..4))) }.loc()),
+        TestLoc(Loc::encapsulate_range(3, 2..4))
+    );
+    assert_eq!(TestLoc(EnumOptional::Foo { foo: None, bar: None }.loc()), TestLoc(Loc::new()));
+    assert_eq!(TestLoc(EnumOptional::Bar(Some(TestLoc(Loc::encapsulate(4)))).loc()), TestLoc(Loc::encapsulate(4)));
+
+    let (a, b, c) = (Loc::encapsulate_range(5, ..2), Loc::encapsulate_range(5, 2..4), Loc::encapsulate_range(5, 4..6));
+    assert_eq!(TestLoc(StructOptionalFirst(None, Some(TestLoc(a)), TestLoc(b), TestLoc(c)).loc()), TestLoc(a));
+    assert_eq!(TestLoc(StructOptionalFirst(Some(TestLoc(a)), Some(TestLoc(b)), TestLoc(c), TestLoc(c)).loc()), TestLoc(a));
+    assert_eq!(TestLoc(StructOptionalFirst(None, None, TestLoc(b), TestLoc(c)).loc()), TestLoc(b));
+}
+
+#[test]
+fn test_derive_located_collections() {
+    /// Points at a list of children instead of having its own loc.
+    #[derive(Located)]
+    struct Block(#[loc] Vec<TestLoc>, String);
+
+    assert_eq!(
+        TestLoc(
+            Block(
+                vec![TestLoc(Loc::encapsulate_range(0, ..2)), TestLoc(Loc::encapsulate_range(0, 2..4)), TestLoc(Loc::encapsulate_range(0, 4..6))],
+                "Hello, world!".into()
+            )
+            .loc()
+        ),
+        TestLoc(Loc::encapsulate_range(0, ..6))
+    );
+    assert_eq!(TestLoc(Block(Vec::new(), "Hello, world!".into()).loc()), TestLoc(Loc::new()));
+}
+
+#[test]
+fn test_derive_located_packed() {
+    /// Stores its location compactly, while still deriving comparisons.
+    #[derive(Debug, Eq, Hash, Located, PartialEq)]
+    struct Ident {
+        name: String,
+        loc:  PackedLoc,
+    }
+
+    let ident = Ident { name: "foo".into(), loc: PackedLoc::try_from(Loc::encapsulate_range(0, 2..5)).unwrap() };
+    assert_eq!(TestLoc(ident.loc()), TestLoc(Loc::encapsulate_range(0, 2..5)));
+    assert_eq!(ident, Ident { name: "foo".into(), loc: PackedLoc::try_from(Loc::new()).unwrap() });
+}
@@ -0,0 +1,133 @@
+#![allow(unused)]
+//  DERIVE PARSABLE.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases the use of the [`Parsable`](derive@Parsable)-macro.
+//
+
+use std::convert::Infallible;
+
+use ast_toolkit2::loc::test::TestLoc;
+use ast_toolkit2::loc::{Loc, Located};
+use ast_toolkit2::nibble::combinators::take_while1;
+use ast_toolkit2::nibble::whitespace::Whitespace;
+use ast_toolkit2::nibble::{NibbleError, Parsable, Slice};
+use ast_toolkit2::tree::{Node, Tag, Term};
+
+
+/***** HELPERS *****/
+#[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+#[tag(u8, b"Hello")]
+struct HelloTag {
+    loc: TestLoc,
+}
+
+#[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+#[tag(u8, b"World")]
+struct WorldTag {
+    loc: TestLoc,
+}
+
+
+#[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+#[tag(u8, b"select")]
+struct Select {
+    loc: TestLoc,
+}
+
+/// Parses a sequence of alphabetic characters.
+#[derive(Debug, Eq, PartialEq)]
+struct IdentTok {
+    loc: TestLoc,
+}
+impl Parsable<u8> for IdentTok {
+    type Formatter = &'static str;
+    type Error = Infallible;
+
+    #[inline]
+    fn expects() -> Self::Formatter { "an identifier" }
+
+    #[inline]
+    fn parse(input: Slice<u8>) -> Result<(Self, Slice<u8>), NibbleError<Self::Formatter, Self::Error>> {
+        let ((_, loc), rem) = take_while1(input, u8::is_ascii_alphabetic).map_err(|err| err.map_fmt(|_| Self::expects()))?;
+        Ok((Self { loc: TestLoc(loc) }, rem))
+    }
+}
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_derive_parsable_structs() {
+    #[derive(Debug, Parsable)]
+    #[parsable(elem = u8)]
+    struct Greeting {
+        hello: HelloTag,
+        ws:    Whitespace,
+        world: WorldTag,
+    }
+
+    #[derive(Debug, Eq, Parsable, PartialEq)]
+    struct Generic(HelloTag);
+
+    /// More fields than tuples implement `Parsable` for.
+    #[derive(Debug, Parsable)]
+    struct Long(HelloTag, HelloTag, HelloTag, HelloTag, HelloTag, HelloTag, HelloTag, HelloTag, HelloTag);
+
+    const ID: u64 = 0;
+    let input = Slice::with_raw_id(ID, b"Hello World!".as_slice());
+    let (greeting, rem) = Greeting::parse(input).unwrap();
+    assert_eq!(greeting.hello, HelloTag { loc: TestLoc(Loc::encapsulate_range(ID, ..5)) });
+    assert_eq!(greeting.ws.loc, Loc::encapsulate_range(ID, 5..6));
+    assert_eq!(greeting.world, WorldTag { loc: TestLoc(Loc::encapsulate_range(ID, 6..11)) });
+    assert_eq!(rem, input.slice(11..));
+
+    // It behaves like a tuple of its fields
+    assert!(matches!(Greeting::parse(Slice::with_raw_id(ID, b"Goodbye".as_slice())), Err(NibbleError::Unmatched(_, None))));
+    assert!(matches!(Greeting::parse(Slice::with_raw_id(ID, b"Hello there".as_slice())), Err(NibbleError::Error(_))));
+    assert_eq!(Greeting::expects_string(), "[72, 101, 108, 108, 111], then whitespace, then [87, 111, 114, 108, 100]");
+
+    let long = Slice::with_raw_id(ID, b"HelloHelloHelloHelloHelloHelloHelloHelloHello".as_slice());
+    let (res, rem) = Long::parse(long).unwrap();
+    assert_eq!(res.8, HelloTag { loc: TestLoc(Loc::encapsulate_range(ID, 40..45)) });
+    assert_eq!(rem, long.slice(45..));
+
+    assert_eq!(Generic::parse(input), Ok((Generic(HelloTag { loc: TestLoc(Loc::encapsulate_range(ID, ..5)) }), input.slice(5..))));
+}
+
+#[test]
+fn test_derive_parsable_enums() {
+    #[derive(Debug, Eq, Parsable, PartialEq)]
+    enum Token {
+        Kw(Select),
+        Ident(IdentTok),
+        #[parsable(skip)]
+        Other,
+    }
+
+    #[derive(Debug, Eq, Parsable, PartialEq)]
+    #[parsable(elem = u8)]
+    enum Sentence {
+        Greeting { hello: HelloTag, ws: Whitespace, world: WorldTag },
+        World(WorldTag),
+    }
+
+    const ID: u64 = 0;
+    let input1 = Slice::with_raw_id(ID, b"select".as_slice());
+    let input2 = Slice::with_raw_id(ID, b"foo".as_slice());
+    let input3 = Slice::with_raw_id(ID, b"123".as_slice());
+
+    // Variants are tried in order
+    assert_eq!(Token::parse(input1), Ok((Token::Kw(Select { loc: TestLoc(Loc::encapsulate_range(ID, ..6)) }), input1.slice(6..))));
+    assert_eq!(Token::parse(input2), Ok((Token::Ident(IdentTok { loc: TestLoc(Loc::encapsulate_range(ID, ..3)) }), input2.slice(3..))));
+    assert!(matches!(Token::parse(input3), Err(NibbleError::Unmatched(_, None))));
+    assert_eq!(Token::expects_string(), "[115, 101, 108, 101, 99, 116] or an identifier");
+
+    // A committed variant doesn't fall through
+    let input = Slice::with_raw_id(ID, b"World".as_slice());
+    assert_eq!(Sentence::parse(input), Ok((Sentence::World(WorldTag { loc: TestLoc(Loc::encapsulate_range(ID, ..5)) }), input.slice(5..))));
+    assert!(matches!(Sentence::parse(Slice::with_raw_id(ID, b"Hello!".as_slice())), Err(NibbleError::Error(_))));
+}
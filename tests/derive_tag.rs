@@ -18,23 +18,22 @@ fn test_derive_tag() {
     struct NamedField {
         loc: TestLoc,
     }
-    #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
-    #[tag(u8, b"bar")]
-    struct NamedFieldMulti {
-        #[loc]
-        loc1: TestLoc,
-        #[loc]
-        loc2: TestLoc,
-    }
 
     #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
     #[tag(u8, b"baz")]
     struct UnnamedField(TestLoc);
 
     #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
-    #[tag(u8, b"quz")]
-    #[loc(all)]
-    struct UnnamedFieldMulti(TestLoc, TestLoc);
+    #[tag(u8, b"kw")]
+    struct NonLocFields {
+        text: String,
+        #[loc]
+        loc:  TestLoc,
+    }
+
+    #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+    #[tag(u8, b"qux")]
+    struct NonLocFieldsUnnamed(String, #[loc] TestLoc, u32);
 
     // NOT POSSIBLE
     // No loc exist to derive `Located` on. Neat.
@@ -46,15 +45,62 @@ fn test_derive_tag() {
     assert_eq!(NamedField::new(), NamedField { loc: TestLoc(Loc::new()) });
     assert_eq!(NamedField::with_loc(Loc::encapsulate(0)), NamedField { loc: TestLoc(Loc::encapsulate(0)) });
 
-    assert_eq!(NamedFieldMulti::new(), NamedFieldMulti { loc1: TestLoc(Loc::new()), loc2: TestLoc(Loc::new()) });
-    assert_eq!(NamedFieldMulti::with_loc(Loc::encapsulate(0)), NamedFieldMulti {
-        loc1: TestLoc(Loc::encapsulate(0)),
-        loc2: TestLoc(Loc::encapsulate(0)),
-    });
+    assert_eq!(NonLocFields::TAG, b"kw");
+    assert_eq!(NonLocFields::new(), NonLocFields { text: String::new(), loc: TestLoc(Loc::new()) });
+    assert_eq!(NonLocFields::with_loc(Loc::encapsulate(0)), NonLocFields { text: String::new(), loc: TestLoc(Loc::encapsulate(0)) });
+
+    assert_eq!(NonLocFieldsUnnamed::new(), NonLocFieldsUnnamed(String::new(), TestLoc(Loc::new()), 0));
+    assert_eq!(NonLocFieldsUnnamed::with_loc(Loc::encapsulate(0)), NonLocFieldsUnnamed(String::new(), TestLoc(Loc::encapsulate(0)), 0));
 
     assert_eq!(UnnamedField::new(), UnnamedField(TestLoc(Loc::new())));
     assert_eq!(UnnamedField::with_loc(Loc::encapsulate(0)), UnnamedField(TestLoc(Loc::encapsulate(0))));
+}
+
+#[test]
+fn test_derive_tag_str() {
+    #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+    #[tag("select")]
+    struct Select(TestLoc);
+
+    #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+    #[tag("→")]
+    struct Arrow {
+        loc: TestLoc,
+    }
+
+    #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+    #[tag("from", no_loc)]
+    #[loc(new)]
+    struct From;
+
+    #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+    #[tag(u8, b"where", no_loc)]
+    #[loc(new)]
+    struct Where;
+
+
+    assert_eq!(Select::TAG, b"select");
+    assert_eq!(Select::with_loc(Loc::encapsulate(0)), Select(TestLoc(Loc::encapsulate(0))));
+    assert_eq!(Arrow::TAG, "→".as_bytes());
+    assert_eq!(Arrow::with_loc(Loc::encapsulate(0)), Arrow { loc: TestLoc(Loc::encapsulate(0)) });
+    assert_eq!(From::TAG, b"from");
+    assert_eq!(From::with_loc(Loc::encapsulate(0)), From);
+    assert_eq!(Where::TAG, b"where");
+    assert_eq!(Where::new(), Where);
+}
+
+#[cfg(feature = "nibble")]
+#[test]
+fn test_derive_tag_str_parse() {
+    use ast_toolkit2::nibble::{NibbleError, Parsable, Slice};
+
+    #[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+    #[tag("select")]
+    struct Select(Loc);
 
-    assert_eq!(UnnamedFieldMulti::new(), UnnamedFieldMulti(TestLoc(Loc::new()), TestLoc(Loc::new())));
-    assert_eq!(UnnamedFieldMulti::with_loc(Loc::encapsulate(0)), UnnamedFieldMulti(TestLoc(Loc::encapsulate(0)), TestLoc(Loc::encapsulate(0))));
+    let input = Slice::new(b"select *".as_slice());
+    let (res, rem) = Select::parse(input).unwrap();
+    assert_eq!(TestLoc(res.loc()), TestLoc(input.head_slice_loc(6).0.1));
+    assert_eq!(rem.as_slice(), b" *");
+    assert!(matches!(Select::parse(Slice::new(b"from".as_slice())), Err(NibbleError::Unmatched(_, None))));
 }
@@ -7,8 +7,12 @@
 //!   [`NonTerm`](derive@NonTerm)- and [`Term`](derive@Term)-macros.
 //
 
+use std::ops::ControlFlow;
+
+use ast_toolkit2::loc::test::TestLoc;
 use ast_toolkit2::loc::{Loc, Located};
-use ast_toolkit2::tree::{Node, NonTerm, Term};
+use ast_toolkit2::tree::visit::Visitor;
+use ast_toolkit2::tree::{Node, NodeKind, NonTerm, Term};
 
 
 /***** HELPER FUNCTIONS *****/
@@ -90,6 +94,212 @@ fn test_derive_nonterm_enums() {
     assert_nonterm::<EnumSimple>();
 }
 
+#[test]
+fn test_derive_nonterm_children() {
+    #[derive(Debug, Located, Node, Term)]
+    pub struct Lit(Loc);
+
+    #[derive(Located, Node, NonTerm)]
+    pub enum Expr {
+        Add {
+            #[child]
+            #[loc]
+            lhs: Lit,
+            op:  String,
+            #[child]
+            #[loc]
+            rhs: Lit,
+        },
+        Neg(String, #[child] #[loc] Lit),
+        #[loc(new)]
+        Empty,
+    }
+
+    #[derive(Located, Node, NonTerm)]
+    pub struct Gen<T>(#[child] #[loc] T, String);
+
+    let expr = Expr::Add { lhs: Lit(Loc::encapsulate_range(0, ..1)), op: "+".into(), rhs: Lit(Loc::encapsulate_range(0, 2..3)) };
+    let locs: Vec<TestLoc> = expr.children().into_iter().map(|c| TestLoc(c.loc())).collect();
+    assert_eq!(locs, vec![TestLoc(Loc::encapsulate_range(0, ..1)), TestLoc(Loc::encapsulate_range(0, 2..3))]);
+
+    let expr = Expr::Neg("-".into(), Lit(Loc::encapsulate_range(1, 1..2)));
+    let locs: Vec<TestLoc> = expr.children().into_iter().map(|c| TestLoc(c.loc())).collect();
+    assert_eq!(locs, vec![TestLoc(Loc::encapsulate_range(1, 1..2))]);
+    assert!(Expr::Empty.children().is_empty());
+
+    let expr = Gen(Lit(Loc::encapsulate(2)), "Hello, world!".into());
+    let locs: Vec<TestLoc> = expr.children().into_iter().map(|c| TestLoc(c.loc())).collect();
+    assert_eq!(locs, vec![TestLoc(Loc::encapsulate(2))]);
+}
+
+#[test]
+fn test_derive_nonterm_boxed() {
+    #[derive(Located, Node, Term)]
+    pub struct Op(Loc);
+
+    #[derive(Located, Node, Term)]
+    pub struct Lit(Loc);
+
+    #[derive(Located, Node, NonTerm)]
+    pub struct Unary {
+        #[child]
+        op: Op,
+        #[child]
+        operand: Box<Expr>,
+        loc: Loc,
+    }
+
+    #[derive(Located, Node, NonTerm)]
+    pub enum Expr {
+        Unary(#[child] #[loc] Unary),
+        Lit(#[child] #[loc] Lit),
+    }
+
+    assert_node::<Box<Expr>>();
+    assert_nonterm::<Box<Expr>>();
+    assert_term::<Box<Lit>>();
+    assert_nonterm::<std::rc::Rc<Expr>>();
+    assert_nonterm::<std::sync::Arc<Expr>>();
+
+    // `--1`
+    let expr = Expr::Unary(Unary {
+        op: Op(Loc::encapsulate_range(0, ..1)),
+        operand: Box::new(Expr::Unary(Unary {
+            op: Op(Loc::encapsulate_range(0, 1..2)),
+            operand: Box::new(Expr::Lit(Lit(Loc::encapsulate_range(0, 2..3)))),
+            loc: Loc::encapsulate_range(0, 1..3),
+        })),
+        loc: Loc::encapsulate_range(0, ..3),
+    });
+    let Expr::Unary(unary) = &expr else { unreachable!() };
+    let locs: Vec<TestLoc> = unary.children().into_iter().map(|c| TestLoc(c.loc())).collect();
+    assert_eq!(locs, vec![TestLoc(Loc::encapsulate_range(0, ..1)), TestLoc(Loc::encapsulate_range(0, 1..3))]);
+    assert_eq!(unary.operand.children().len(), 1);
+
+    /// Counts the terminals.
+    struct TermCounter(usize);
+    impl Visitor for TermCounter {
+        fn visit_term(&mut self, _term: &dyn Node) -> ControlFlow<()> {
+            self.0 += 1;
+            ControlFlow::Continue(())
+        }
+    }
+    let mut counter = TermCounter(0);
+    assert_eq!(expr.walk(&mut counter), ControlFlow::Continue(()));
+    assert_eq!(counter.0, 3);
+}
+
+#[test]
+fn test_derive_node_kind() {
+    #[derive(Located, Node, Term)]
+    pub struct Lit(Loc);
+
+    #[derive(Located, Node, NonTerm)]
+    pub struct Neg(#[child] #[loc] Lit);
+
+    let lit = Lit(Loc::encapsulate(0));
+    assert_eq!(lit.kind(), NodeKind::Term);
+    let neg = Neg(Lit(Loc::encapsulate(0)));
+    assert_eq!(neg.kind(), NodeKind::NonTerm);
+    assert_eq!((&neg as &dyn Node).kind(), NodeKind::NonTerm);
+    assert_eq!(neg.children()[0].kind(), NodeKind::Term);
+    assert_eq!(Box::new(neg).kind(), NodeKind::NonTerm);
+}
+
+#[test]
+fn test_derive_node_kind_childless() {
+    #[derive(Located, Node, NonTerm)]
+    #[loc(new)]
+    pub struct Empty;
+
+    #[derive(Located, Node, NonTerm)]
+    pub struct Gen<T>(T);
+
+    #[derive(Located, Node, Term)]
+    pub struct Lit(Loc);
+
+    /// Records which of the kind-specific visits were made.
+    struct KindRecorder(Vec<NodeKind>);
+    impl Visitor for KindRecorder {
+        fn visit_term(&mut self, _term: &dyn Node) -> ControlFlow<()> {
+            self.0.push(NodeKind::Term);
+            ControlFlow::Continue(())
+        }
+
+        fn visit_nonterm(&mut self, _nonterm: &dyn Node) -> ControlFlow<()> {
+            self.0.push(NodeKind::NonTerm);
+            ControlFlow::Continue(())
+        }
+    }
+
+    // The kind follows the derive, not the presence of children
+    assert_eq!(Empty.kind(), NodeKind::NonTerm);
+    assert_eq!(Gen(Lit(Loc::new())).kind(), NodeKind::NonTerm);
+    let mut recorder = KindRecorder(Vec::new());
+    assert_eq!(Empty.walk(&mut recorder), ControlFlow::Continue(()));
+    assert_eq!(recorder.0, vec![NodeKind::NonTerm]);
+
+    // Without either derive, it's still guessed from the children
+    #[derive(Located, Node)]
+    #[loc(new)]
+    pub struct Plain;
+    assert_eq!(Plain.kind(), NodeKind::Term);
+}
+
+#[test]
+fn test_derive_node_walk() {
+    #[derive(Located, Node, Term)]
+    pub struct Lit(Loc);
+
+    #[derive(Located, Node, NonTerm)]
+    pub struct Pair(#[child] #[loc] Lit, #[child] #[loc] Lit);
+
+    #[derive(Located, Node, NonTerm)]
+    pub enum Expr {
+        Add {
+            #[child]
+            #[loc]
+            lhs: Lit,
+            #[child]
+            #[loc]
+            rhs: Pair,
+        },
+        Lit(#[child] #[loc] Lit),
+    }
+
+    /// Counts the terminals, optionally stopping after a certain number.
+    struct TermCounter {
+        terms: usize,
+        nodes: usize,
+        limit: Option<usize>,
+    }
+    impl Visitor for TermCounter {
+        fn visit_node(&mut self, _node: &dyn Node) -> ControlFlow<()> {
+            self.nodes += 1;
+            ControlFlow::Continue(())
+        }
+
+        fn visit_term(&mut self, _term: &dyn Node) -> ControlFlow<()> {
+            self.terms += 1;
+            if self.limit.is_some_and(|limit| self.terms >= limit) { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        }
+    }
+
+    // `1 + (2, 3)`
+    let expr = Expr::Add { lhs: Lit(Loc::encapsulate(1)), rhs: Pair(Lit(Loc::encapsulate(2)), Lit(Loc::encapsulate(3))) };
+
+    let mut counter = TermCounter { terms: 0, nodes: 0, limit: None };
+    assert_eq!(expr.walk(&mut counter), ControlFlow::Continue(()));
+    assert_eq!(counter.terms, 3);
+    assert_eq!(counter.nodes, 5);
+
+    // Stopping early skips the rest of the tree
+    let mut counter = TermCounter { terms: 0, nodes: 0, limit: Some(2) };
+    assert_eq!(expr.walk(&mut counter), ControlFlow::Break(()));
+    assert_eq!(counter.terms, 2);
+    assert_eq!(counter.nodes, 4);
+}
+
 
 
 #[test]
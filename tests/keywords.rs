@@ -0,0 +1,83 @@
+//  KEYWORDS.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases the use of the [`keywords!`]-macro.
+//
+
+use ast_toolkit2::loc::test::TestLoc;
+use ast_toolkit2::loc::{Loc, Located};
+use ast_toolkit2::nibble::{NibbleError, Parsable, Slice, keywords};
+use ast_toolkit2::tree::Tag;
+
+
+/***** HELPERS *****/
+mod kw {
+    use super::*;
+
+    keywords! {
+        /// Some SQL-like keywords.
+        #[derive(Debug)]
+        pub enum Kw {
+            Select = "select",
+            From = "from",
+            Where = "where",
+            In = "in",
+            Instanceof = "instanceof",
+        }
+    }
+}
+use kw::Kw;
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_keywords() {
+    const ID: u64 = 0;
+    let input1 = Slice::with_raw_id(ID, b"from x".as_slice());
+    let input2 = Slice::with_raw_id(ID, b"instanceof".as_slice());
+    let input3 = Slice::with_raw_id(ID, b"in x".as_slice());
+    let input4 = Slice::with_raw_id(ID, b"x".as_slice());
+
+    let (res, rem) = Kw::parse(input1).unwrap();
+    assert!(matches!(res, Kw::From(_)));
+    assert_eq!(TestLoc(res.loc()), TestLoc(Loc::encapsulate_range(ID, ..4)));
+    assert_eq!(rem, input1.slice(4..));
+
+    // The longest match wins
+    let (res, rem) = Kw::parse(input2).unwrap();
+    assert!(matches!(res, Kw::Instanceof(_)));
+    assert_eq!(rem, input2.slice(10..));
+    let (res, _) = Kw::parse(input3).unwrap();
+    assert!(matches!(res, Kw::In(_)));
+
+    assert!(matches!(Kw::parse(input4), Err(NibbleError::Unmatched(_, None))));
+    assert_eq!(Kw::expects_string(), "\"select\", \"from\", \"where\", \"in\" or \"instanceof\"");
+}
+
+#[test]
+fn test_keywords_no_boundary() {
+    const ID: u64 = 0;
+    let input = Slice::with_raw_id(ID, b"fromage".as_slice());
+
+    // Word boundaries are not checked, so this matches only the prefix
+    let (res, rem) = Kw::parse(input).unwrap();
+    assert!(matches!(res, Kw::From(_)));
+    assert_eq!(rem.as_slice(), b"age");
+}
+
+#[test]
+fn test_keywords_tags() {
+    const ID: u64 = 0;
+    let input = Slice::with_raw_id(ID, b"select".as_slice());
+
+    // The individual keywords are ordinary tags
+    assert_eq!(kw::Select::TAG, b"select");
+    let (res, rem) = kw::Select::parse(input).unwrap();
+    assert_eq!(TestLoc(res.loc()), TestLoc(Loc::encapsulate_range(ID, ..6)));
+    assert!(rem.as_slice().is_empty());
+    assert!(kw::Where::parse(input).is_err());
+}
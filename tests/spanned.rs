@@ -0,0 +1,61 @@
+//  SPANNED.rs
+//    by Lut99
+//
+//  Description:
+//!   Showcases the use of the [`spanned!`]-macro.
+//
+
+use ast_toolkit2::loc::test::TestLoc;
+use ast_toolkit2::loc::{Loc, Located};
+use ast_toolkit2::nibble::{Parsable as _, Slice, spanned};
+use ast_toolkit2::tree::{Node, NonTerm, Tag, Term};
+
+
+/***** HELPERS *****/
+#[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+#[tag("Hello")]
+struct Hello(TestLoc);
+
+#[derive(Debug, Default, Eq, Located, Node, PartialEq, Tag, Term)]
+#[tag("World")]
+struct World(TestLoc);
+
+#[derive(Debug, Eq, Located, Node, NonTerm, PartialEq)]
+struct Greeting {
+    #[child]
+    hello: Hello,
+    #[child]
+    world: World,
+    loc:   TestLoc,
+}
+
+#[derive(Debug, Eq, Located, Node, NonTerm, PartialEq)]
+struct GreetingTuple(#[child] Hello, #[child] World, #[loc] TestLoc);
+
+
+
+
+
+/***** TESTS *****/
+#[test]
+fn test_spanned() {
+    const ID: u64 = 0;
+    let input = Slice::with_raw_id(ID, b"xHelloWorld!".as_slice()).slice(1..);
+
+    let (hello, rem) = Hello::parse(input).unwrap();
+    let (world, rem) = World::parse(rem).unwrap();
+    let greeting = spanned!(input, rem, Greeting { hello, world });
+    assert_eq!(greeting, Greeting {
+        hello: Hello(TestLoc(Loc::encapsulate_range(ID, 1..6))),
+        world: World(TestLoc(Loc::encapsulate_range(ID, 6..11))),
+        loc:   TestLoc(Loc::encapsulate_range(ID, 1..11)),
+    });
+    assert_eq!(greeting.children().len(), 2);
+
+    // Tuples and explicit fields work too
+    let (hello, rem) = Hello::parse(input).unwrap();
+    let greeting = spanned!(input, rem, GreetingTuple(hello, World::new()));
+    assert_eq!(TestLoc(greeting.loc()), TestLoc(Loc::encapsulate_range(ID, 1..6)));
+    let greeting = spanned!(input, rem, Greeting { hello: Hello::new(), world: World::new() });
+    assert_eq!(greeting.loc, TestLoc(Loc::encapsulate_range(ID, 1..6)));
+}